@@ -8,9 +8,21 @@ use std::io::{self, Write};
 
 use acres::{
     AcresError, Api, Cached,
-    artworks::{self, Manifest, request::artwork},
+    artworks::{self, ArtworkInfo, Manifest, request::artwork},
+    citation::{Citation, Style},
+    color,
+    dating::Span,
+    export::{Exporter, Format},
+    graph::{Explorer, Relation},
+    images::ImageOptions,
+    query,
+    random::Random,
+    rights::{Policy, Rights},
+    schema::{Checker, Model},
+    similar::{Similar, Weights},
+    sync::JsonStore,
 };
-use clap::{Arg, Command, command, value_parser};
+use clap::{Arg, ArgAction, ArgGroup, Command, command, value_parser};
 use clap_stdin::FileOrStdin;
 use color_eyre::{
     Result,
@@ -39,6 +51,9 @@ async fn main() -> Result<(), Report> {
     crate::logging::init()?;
     color_eyre::install()?;
 
+    let config = acres::Config::new().wrap_err("failed to load config")?;
+    let ascii_defaults = config.ascii.clone();
+
     let matches = command!()
         .propagate_version(true)
         .subcommand_required(true)
@@ -52,6 +67,154 @@ async fn main() -> Result<(), Report> {
                         .value_parser(value_parser!(u32)),
                 )
         )
+        .subcommand(
+            Command::new("artwork-cite")
+                .about("Cite a piece of artwork")
+                .arg(
+                    Arg::new("id")
+                        .help("the id of the artwork")
+                        .required(true)
+                        .value_parser(value_parser!(u32)),
+                )
+                .arg(
+                    Arg::new("style")
+                        .long("style")
+                        .help("citation style (chicago, mla, apa, bibtex or csl-json)")
+                        .default_value("chicago")
+                        .value_parser(value_parser!(Style)),
+                )
+                .arg(
+                    Arg::new("accessed")
+                        .long("accessed")
+                        .help("date the artwork was accessed, as YYYY-MM-DD (defaults to today)")
+                        .value_parser(value_parser!(chrono::NaiveDate)),
+                ),
+        )
+        .subcommand(
+            Command::new("artwork-similar")
+                .about("Find artworks similar to a piece of artwork")
+                .arg(
+                    Arg::new("id")
+                        .help("the id of the artwork")
+                        .required(true)
+                        .value_parser(value_parser!(u32)),
+                )
+                .arg(
+                    Arg::new("size")
+                        .long("size")
+                        .help("number of similar artworks to return")
+                        .default_value("10")
+                        .value_parser(value_parser!(u32)),
+                )
+                .arg(
+                    Arg::new("weights")
+                        .long("weights")
+                        .help("comma-separated signal=weight pairs for text, artist, style, classification, era and color (e.g., 'artist=5,color=0')")
+                        .value_parser(value_parser!(Weights)),
+                ),
+        )
+        .subcommand(
+            Command::new("artwork-graph")
+                .about("Map how artworks connect through artists, exhibitions, galleries and terms")
+                .arg(
+                    Arg::new("ids")
+                        .help("the ids of the artworks to start from")
+                        .required(true)
+                        .num_args(1..)
+                        .value_delimiter(',')
+                        .value_parser(value_parser!(u32)),
+                )
+                .arg(
+                    Arg::new("depth")
+                        .long("depth")
+                        .help("how many hops out from the artworks to go")
+                        .default_value("2")
+                        .value_parser(value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .help("max number of artworks or exhibitions to follow from each node")
+                        .default_value("10")
+                        .value_parser(value_parser!(u32)),
+                )
+                .arg(
+                    Arg::new("relations")
+                        .long("relations")
+                        .help("comma-separated relations to follow (artist, exhibition, gallery, term or related; defaults to all)")
+                        .value_delimiter(',')
+                        .value_parser(value_parser!(Relation)),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .help("output format (dot, graphml or json)")
+                        .default_value("dot")
+                        .value_parser(value_parser!(acres::graph::Format)),
+                ),
+        )
+        .subcommand(
+            Command::new("artwork-random")
+                .about("Pick a random piece of artwork, or the artwork of the day")
+                .arg(
+                    Arg::new("public-domain")
+                        .long("public-domain")
+                        .help("only pick works in the public domain (always so with --daily)")
+                        .conflicts_with("daily")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("has-image")
+                        .long("has-image")
+                        .help("only pick works with an image (always so with --daily)")
+                        .conflicts_with("daily")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("department")
+                        .long("department")
+                        .help("only pick works from this department (e.g., 'Arts of Asia')")
+                        .value_parser(value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .help("seed for the pick, so the same seed picks the same artwork")
+                        .conflicts_with("daily")
+                        .value_parser(value_parser!(u64)),
+                )
+                .arg(
+                    Arg::new("daily")
+                        .long("daily")
+                        .help("pick the artwork of the day, the same on every machine")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("date")
+                        .long("date")
+                        .help("day to pick the artwork of, as YYYY-MM-DD (defaults to today, in UTC)")
+                        .requires("daily")
+                        .value_parser(value_parser!(chrono::NaiveDate)),
+                )
+                .arg(
+                    Arg::new("ascii")
+                        .long("ascii")
+                        .help("print the artwork as ASCII art with its attribution, e.g. for a login banner")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("width")
+                        .long("width")
+                        .help("how many characters wide, with --ascii")
+                        .default_value(
+                            ascii_defaults
+                                .width
+                                .clone()
+                                .unwrap_or(CharWidth::default().to_string()),
+                        )
+                        .value_parser(value_parser!(CharWidth)),
+                ),
+        )
         .subcommand(
             Command::new("artwork-manifest").about("Retrieve the manifest for this artwork")
                 .arg(
@@ -111,6 +274,13 @@ async fn main() -> Result<(), Report> {
                         .help("complex query (in Elasticsearch domain syntax)")
                         .value_parser(value_parser!(String)),
                 )
+                .arg(
+                    Arg::new("find")
+                        .long("find")
+                        .help("compact query (e.g., 'artist:monet date:1870..1890 -type:print')")
+                        .conflicts_with_all(["query", "color"])
+                        .value_parser(value_parser!(String)),
+                )
                 .arg(
                     Arg::new("sort")
                         .long("sort")
@@ -135,6 +305,70 @@ async fn main() -> Result<(), Report> {
                     .help( "comman-separated list of 'count' aggregation facets to include in results")
                     .value_delimiter(',')
                     .value_parser(value_parser!(String))
+                )
+                .arg(
+                    Arg::new("dated")
+                        .long("dated")
+                        .help("limit to artworks dated within a span of years (e.g., '1870 to 1890' or '19th century')")
+                        .value_parser(value_parser!(Span)),
+                )
+                .arg(
+                    Arg::new("color")
+                        .long("color")
+                        .help("limit to artworks close to a color (CSS name, hex, rgb() or hsl()), closest first")
+                        .value_parser(value_parser!(color::Hsl)),
+                )
+                .arg(
+                    Arg::new("tolerance")
+                        .long("tolerance")
+                        .help("how far hue, saturation and lightness may stray from --color, or from a color: in --find")
+                        .requires("colored")
+                        .value_parser(value_parser!(f64)),
+                )
+                .group(ArgGroup::new("colored").args(["color", "find"])),
+        )
+        .subcommand(
+            Command::new("artworks-export")
+                .about("Export artworks as a table")
+                .arg(
+                    Arg::new("columns")
+                        .long("columns")
+                        .required(true)
+                        .help("comma-separated list of field paths to export (e.g., 'id,title,color.h')")
+                        .value_delimiter(',')
+                        .value_parser(value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .help("output format")
+                        .default_value("csv")
+                        .value_parser(value_parser!(acres::export::Format)),
+                )
+                .arg(
+                    Arg::new("q")
+                        .long("q")
+                        .help("export search results for this query instead of the listing")
+                        .value_parser(value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("query")
+                        .long("query")
+                        .help("export search results for this complex query (in Elasticsearch domain syntax)")
+                        .value_parser(value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("store")
+                        .long("store")
+                        .help("export from a local sync store directory instead of the API")
+                        .conflicts_with_all(["q", "query"])
+                        .value_parser(value_parser!(std::path::PathBuf)),
+                )
+                .arg(
+                    Arg::new("max")
+                        .long("max")
+                        .help("max number of artworks to export")
+                        .value_parser(value_parser!(usize)),
                 ),
         )
         .subcommand(
@@ -150,42 +384,72 @@ async fn main() -> Result<(), Report> {
                     Arg::new("alphabet")
                         .long("alphabet")
                         .help("alphabet to use")
-                        .default_value(Alphabet::default().to_string())
+                        .default_value(
+                            ascii_defaults
+                                .alphabet
+                                .clone()
+                                .unwrap_or(Alphabet::default().to_string()),
+                        )
                         .value_parser(value_parser!(Alphabet)),
                 )
                 .arg(
                     Arg::new("brightness-offset")
                         .long("brightness-offset")
                         .help("brightness offset")
-                        .default_value(BrightnessOffset::default().to_string())
+                        .default_value(
+                            ascii_defaults
+                                .brightness_offset
+                                .clone()
+                                .unwrap_or(BrightnessOffset::default().to_string()),
+                        )
                         .value_parser(value_parser!(BrightnessOffset)),
                 )
                 .arg(
                     Arg::new("conversion-algorithm")
                         .long("conversion-algorithm")
                         .help("alphabet to use")
-                        .default_value(ConversionAlgorithm::default().to_string())
+                        .default_value(
+                            ascii_defaults
+                                .conversion_algorithm
+                                .clone()
+                                .unwrap_or(ConversionAlgorithm::default().to_string()),
+                        )
                         .value_parser(value_parser!(ConversionAlgorithm)),
                 )
                 .arg(
                     Arg::new("font")
                         .long("font")
                         .help("font to use")
-                        .default_value(Font::default().to_string())
+                        .default_value(
+                            ascii_defaults
+                                .font
+                                .clone()
+                                .unwrap_or(Font::default().to_string()),
+                        )
                         .value_parser(value_parser!(Font)),
                 )
                 .arg(
                     Arg::new("metric")
                         .long("metric")
                         .help("the metric to use")
-                        .default_value(Metric::default().to_string())
+                        .default_value(
+                            ascii_defaults
+                                .metric
+                                .clone()
+                                .unwrap_or(Metric::default().to_string()),
+                        )
                         .value_parser(value_parser!(Metric)),
                 )
                 .arg(
                     Arg::new("width")
                         .long("width")
                         .help("how many characters wide")
-                        .default_value(CharWidth::default().to_string())
+                        .default_value(
+                            ascii_defaults
+                                .width
+                                .clone()
+                                .unwrap_or(CharWidth::default().to_string()),
+                        )
                         .value_parser(value_parser!(CharWidth)),
                 ),
         )
@@ -234,6 +498,12 @@ async fn main() -> Result<(), Report> {
                         .value_parser(value_parser!(iiif::Format)),
                 )
                 .arg(Arg::new("to").long("to").help("type of output").default_value("url").value_parser(value_parser!(IiifTo)))
+                .arg(
+                    Arg::new("enforce-rights")
+                        .long("enforce-rights")
+                        .help("limit the size of images for works that aren't in the public domain")
+                        .action(ArgAction::SetTrue),
+                )
         )
         .subcommand(
             Command::new("iiif-info")
@@ -247,6 +517,29 @@ async fn main() -> Result<(), Report> {
                 .arg(Arg::new("to").long("to").help("type of output").default_value("url").value_parser(value_parser!(IiifTo)))
                 .subcommand(Command::new("info").about("Retrieve image information.")),
         )
+        .subcommand(
+            Command::new("schema-check")
+                .about("Check that API responses still match the typed models")
+                .arg(
+                    Arg::new("models")
+                        .help("comma-separated models to check (artworks, agents, tours, mobile-sounds, category-terms or artwork-types; defaults to all)")
+                        .value_delimiter(',')
+                        .value_parser(value_parser!(Model)),
+                )
+                .arg(
+                    Arg::new("cached")
+                        .long("cached")
+                        .help("check the cached responses instead of fetching new ones")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .help("number of records to fetch for each model")
+                        .default_value("10")
+                        .value_parser(value_parser!(u32)),
+                ),
+        )
         .get_matches();
 
     match matches.subcommand() {
@@ -260,6 +553,121 @@ async fn main() -> Result<(), Report> {
             let artwork: Cached = Api::new().fetch(request.to_string()).await?;
             println!("{}", artwork)
         }
+        Some(("artwork-cite", matches)) => {
+            let id = matches
+                .get_one::<u32>("id")
+                .copied()
+                .expect("clap ensures id is provided");
+            let artwork = Api::new().artworks().get(id).send().await?;
+            let mut citation = Citation::new(&artwork);
+            if let Some(accessed) = matches.get_one::<chrono::NaiveDate>("accessed") {
+                citation = citation.accessed(*accessed);
+            }
+            let style = matches
+                .get_one::<Style>("style")
+                .copied()
+                .expect("at least default set");
+            println!("{}", citation.format(style))
+        }
+        Some(("artwork-similar", matches)) => {
+            let id = matches
+                .get_one::<u32>("id")
+                .copied()
+                .expect("clap ensures id is provided");
+            let similar = Similar::new()
+                .size(
+                    matches
+                        .get_one::<u32>("size")
+                        .copied()
+                        .expect("at least default set"),
+                )
+                .weights(
+                    matches
+                        .get_one::<Weights>("weights")
+                        .copied()
+                        .unwrap_or_default(),
+                );
+            let related = similar.find(&Api::new(), id).await?;
+            println!("{}", related)
+        }
+        Some(("artwork-graph", matches)) => {
+            let mut explorer = Explorer::new()
+                .depth(
+                    matches
+                        .get_one::<usize>("depth")
+                        .copied()
+                        .expect("at least default set"),
+                )
+                .limit(
+                    matches
+                        .get_one::<u32>("limit")
+                        .copied()
+                        .expect("at least default set"),
+                );
+            if let Some(relations) = matches.get_many::<Relation>("relations") {
+                explorer = explorer.relations(relations.copied());
+            }
+            let ids = matches
+                .get_many::<u32>("ids")
+                .expect("clap ensures ids are provided")
+                .copied();
+            let graph = explorer.explore(&Api::new(), ids).await?;
+            let format = matches
+                .get_one::<acres::graph::Format>("format")
+                .copied()
+                .expect("at least default set");
+            println!("{}", graph.to_format(format).trim_end());
+        }
+        Some(("artwork-random", matches)) => {
+            let api = Api::new();
+            let mut random = if matches.get_flag("daily") {
+                let date = matches
+                    .get_one::<chrono::NaiveDate>("date")
+                    .copied()
+                    .unwrap_or_else(|| chrono::Utc::now().date_naive());
+                Random::artwork_of_the_day(date)
+            } else {
+                Random::new()
+                    .public_domain(matches.get_flag("public-domain"))
+                    .has_image(matches.get_flag("has-image") || matches.get_flag("ascii"))
+            };
+            random = random.department(matches.get_one::<String>("department").cloned());
+            if let Some(seed) = matches.get_one::<u64>("seed") {
+                random = random.seed(*seed);
+            }
+            let artwork = random
+                .pick(&api)
+                .await?
+                .ok_or_else(|| color_eyre::eyre::eyre!("no artworks match those filters"))?;
+            if !matches.get_flag("ascii") {
+                println!(
+                    "{}",
+                    serde_json::to_string(&artwork).context("failed to write artwork")?
+                );
+                return Ok(());
+            }
+            let options = ImageOptions::new().policy(Policy::from_config(&config.rights));
+            let image = api
+                .fetch_image(&ArtworkInfo::try_from(&artwork)?, &options)
+                .await?
+                .into_bytes();
+            let art = Ascii::builder()
+                .input(image)
+                .alphabet(parse_or_default(&ascii_defaults.alphabet))
+                .brightness_offset(parse_or_default(&ascii_defaults.brightness_offset))
+                .conversion_algorithm(parse_or_default(&ascii_defaults.conversion_algorithm))
+                .font(parse_or_default(&ascii_defaults.font))
+                .metric(parse_or_default(&ascii_defaults.metric))
+                .chars_wide(
+                    matches
+                        .get_one::<CharWidth>("width")
+                        .cloned()
+                        .expect("at least default set"),
+                )
+                .build()
+                .context("failed to build art")?;
+            println!("{}\n{}", art, Rights::of(&artwork).attribution());
+        }
         Some(("artwork-manifest", matches)) => {
             let api = Api::new();
             let id = matches
@@ -302,10 +710,43 @@ async fn main() -> Result<(), Report> {
         }
         Some(("artworks-search", matches)) => {
             let api = Api::new();
-            match artworks::Search::builder()
+            let color = matches.get_one::<color::Hsl>("color").map(|hsl| {
+                let query = color::Query::new(*hsl);
+                match matches.get_one::<f64>("tolerance") {
+                    Some(tolerance) => query.tolerance(color::Tolerance::uniform(*tolerance)),
+                    None => query,
+                }
+            });
+            let builder = artworks::Search::builder()
+                .query(matches.get_one::<String>("query").cloned())
+                .color(color);
+            let (builder, color) = match matches.get_one::<String>("find") {
+                Some(find) => match query::parse(find) {
+                    Ok(find) => {
+                        let find = match matches.get_one::<f64>("tolerance") {
+                            Some(_) if find.color().is_none() => {
+                                return Err(color_eyre::eyre::eyre!(
+                                    "--tolerance needs a color: term in --find to apply to"
+                                ));
+                            }
+                            Some(tolerance) => {
+                                find.tolerance(color::Tolerance::uniform(*tolerance))
+                            }
+                            None => find,
+                        };
+                        (find.apply(builder), find.color())
+                    }
+                    Err(error) => {
+                        let pointer = error.pointer(find);
+                        return Err(Report::new(AcresError::from(error)))
+                            .wrap_err(format!("We couldn't read that query:\n\n{pointer}\n"));
+                    }
+                },
+                None => (builder, color),
+            };
+            match builder
                 .base_uri(api.base_uri())
                 .q(matches.get_one::<String>("q").cloned())
-                .query(matches.get_one::<String>("query").cloned())
                 .sort(matches.get_one::<String>("sort").cloned())
                 .from(matches.get_one::<u32>("from").cloned())
                 .size(matches.get_one::<u32>("size").cloned())
@@ -314,15 +755,48 @@ async fn main() -> Result<(), Report> {
                         .get_many::<String>("facets")
                         .map(|facets| facets.cloned().collect()),
                 )
+                .dated(matches.get_one::<Span>("dated").cloned())
                 .build()
             {
                 Ok(request) => {
-                    let search: Cached = api.fetch(request.to_string()).await?;
+                    let mut search = api.send(&request).await?;
+                    if let Some(color) = color {
+                        color.rank(search.data_mut());
+                    }
                     println!("{}", search)
                 }
                 Err(error) => return Err(error).wrap_err("We couldn't complete that search ..."),
             }
         }
+        Some(("artworks-export", matches)) => {
+            let api = Api::new();
+            let mut exporter = Exporter::new(
+                matches
+                    .get_one::<Format>("format")
+                    .copied()
+                    .expect("at least default set"),
+                matches
+                    .get_many::<String>("columns")
+                    .expect("clap ensures columns are provided"),
+                io::BufWriter::new(io::stdout()),
+            )?;
+            let max = matches.get_one::<usize>("max").copied();
+            if let Some(dir) = matches.get_one::<std::path::PathBuf>("store") {
+                exporter.store(&JsonStore::open(dir)?)?;
+            } else if matches.contains_id("q") || matches.contains_id("query") {
+                let builder = artworks::Search::builder()
+                    .q(matches.get_one::<String>("q").cloned())
+                    .query(matches.get_one::<String>("query").cloned());
+                exporter.search(&api, builder, max).await?;
+            } else {
+                let builder = artworks::request::artworks::Request::builder();
+                exporter.listing(&api, builder, max).await?;
+            }
+            exporter
+                .finish()?
+                .flush()
+                .context("failed to write export")?;
+        }
         Some(("ascii-art", matches)) => {
             let image_reader = matches
                 .get_one::<FileOrStdin>("image")
@@ -382,9 +856,18 @@ async fn main() -> Result<(), Report> {
                     .into_reader()?,
             )
             .ok_or(AcresError::LoadArtworkInfo)?;
-            let base_uri: iiif::Uri = artwork.try_into()?;
-            let image_request = iiif::ImageRequest::builder()
-                .uri(base_uri)
+            let policy = Policy::from_config(&config.rights).or_else(|| {
+                matches.get_flag("enforce-rights").then(|| {
+                    Policy::new(
+                        config
+                            .rights
+                            .restricted_width
+                            .unwrap_or(Policy::DEFAULT_RESTRICTED_WIDTH),
+                    )
+                })
+            });
+            let options = ImageOptions::new()
+                .policy(policy)
                 .region(
                     matches
                         .get_one::<iiif::Region>("region")
@@ -414,15 +897,13 @@ async fn main() -> Result<(), Report> {
                         .get_one::<iiif::Format>("format")
                         .cloned()
                         .expect("at least default set"),
-                )
-                .build();
+                );
             match matches.get_one::<IiifTo>("to") {
-                Some(IiifTo::Url) => println!("{}", image_request),
+                Some(IiifTo::Url) => println!("{}", options.request(&artwork)?),
                 Some(IiifTo::Bytes) => {
-                    let response: bytes::Bytes =
-                        Api::new().fetch(image_request.to_string()).await?;
+                    let image = Api::new().fetch_image(&artwork, &options).await?;
                     io::stdout()
-                        .write_all(&response)
+                        .write_all(image.bytes())
                         .context("failed to write image bytes")?;
                 }
                 None => unreachable!("default value means we shouldn't get here"),
@@ -443,8 +924,45 @@ async fn main() -> Result<(), Report> {
                 .write_all(&response)
                 .context("failed to write json bytes")?;
         }
+        Some(("schema-check", matches)) => {
+            let models: Vec<Model> = match matches.get_many::<Model>("models") {
+                Some(models) => models.copied().collect(),
+                None => Model::ALL.to_vec(),
+            };
+            let limit = matches
+                .get_one::<u32>("limit")
+                .copied()
+                .expect("at least default set");
+            let api = Api::builder().use_cache(false).build();
+            let mut breaking = false;
+            for model in models {
+                let mut checker = Checker::new(model);
+                if matches.get_flag("cached") {
+                    checker.observe_dir(api.cache_dir())?;
+                } else {
+                    checker.sample(&api, limit).await?;
+                }
+                let report = checker.report();
+                breaking |= report.is_breaking();
+                print!("{report}");
+            }
+            if breaking {
+                return Err(color_eyre::eyre::eyre!(
+                    "responses have drifted from the models"
+                ));
+            }
+        }
         _ => unreachable!("clap should ensure we don't get here"),
     };
 
     Ok(())
 }
+
+/// Parses an ASCII art setting from the config, falling back to the default.
+#[doc(hidden)]
+fn parse_or_default<T: std::str::FromStr + Default>(setting: &Option<String>) -> T {
+    setting
+        .as_deref()
+        .and_then(|setting| setting.parse().ok())
+        .unwrap_or_default()
+}
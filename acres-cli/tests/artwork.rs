@@ -36,6 +36,52 @@ async fn artwork_command_outputs_json() -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
+#[tokio::test]
+async fn artwork_command_refetches_corrupt_cache_entries() -> Result<(), Box<dyn std::error::Error>>
+{
+    let id = 42;
+    let cache_dir = assert_fs::TempDir::new()?;
+
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::path(format!("/api/v1/artworks/{}", id)))
+        .respond_with(
+            wiremock::ResponseTemplate::new(200).set_body_json(json!({"data":{"id": id}})),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    let run = || -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("acres-cli")?;
+        cmd.env("ACRES_BASE_URI", &mock_uri)
+            .env("ACRES_CACHE_DIR", cache_dir.path())
+            .arg("artwork")
+            .arg(id.to_string());
+        Ok(serde_json::from_slice(&cmd.output()?.stdout)?)
+    };
+
+    // Given a cached response that was cut off
+    run()?;
+    let entry = std::fs::read_dir(cache_dir.path())?
+        .filter_map(|entry| entry.ok())
+        .find(|entry| entry.path().is_file())
+        .expect("response was cached");
+    std::fs::write(entry.path(), br#"{"data":{"i"#)?;
+
+    // When we ask for the artwork again
+    let value = run()?;
+
+    // Then it's fetched again and the corrupt entry is set aside
+    assert_eq!(value["data"]["id"].as_i64(), Some(id));
+    assert_eq!(
+        std::fs::read_dir(cache_dir.path().join("quarantine"))?.count(),
+        1
+    );
+    assert_eq!(std::fs::read(entry.path())?, br#"{"data":{"id":42}}"#);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn artwork_manifest_command_outputs_json() -> Result<(), Box<dyn std::error::Error>> {
     let id = 42;
@@ -132,3 +178,207 @@ async fn artwork_manifest_command_outputs_json() -> Result<(), Box<dyn std::erro
 //
 //    Ok(())
 //}
+
+#[tokio::test]
+async fn artwork_cite_command_outputs_citation() -> Result<(), Box<dyn std::error::Error>> {
+    let id = 4;
+    let body = json!({
+        "data": {
+            "id": id,
+            "title": "Priest and Boy",
+            "artist_display": "Lawrence Carmichael Earle\nAmerican, 1845-1921",
+            "date_display": "n.d.",
+            "medium_display": "Watercolor over graphite on cream wove paper"
+        },
+        "info": { "license_text": "", "license_links": [], "version": "1.13" },
+        "config": { "iiif_url": "https://www.artic.edu/iiif/2", "website_url": "https://www.artic.edu" }
+    });
+
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::path(format!("/api/v1/artworks/{}", id)))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(body))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // When we run the CLI to cite the artwork
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", mock_uri)
+        .env("ACRES_USE_CACHE", "false") // So it hits wiremock
+        .arg("artwork-cite")
+        .arg(id.to_string())
+        .args(["--style", "mla", "--accessed", "2024-05-01"]);
+
+    // Then stdout has the citation
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    assert_eq!(
+        stdout,
+        "Lawrence Carmichael Earle. Priest and Boy. n.d., Watercolor over graphite on cream wove \
+         paper. Art Institute of Chicago, www.artic.edu/artworks/4. Accessed 1 May 2024.\n"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn artwork_similar_command_outputs_related_artworks() -> Result<(), Box<dyn std::error::Error>>
+{
+    let id = 27992;
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::path(format!("/api/v1/artworks/{}", id)))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "id": id, "title": "A Sunday on La Grande Jatte — 1884", "artist_id": 40482 }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+        .and(wiremock::matchers::query_param_contains(
+            "query",
+            r#"{"term":{"artist_id":{"boost":5.0,"value":40482}}}"#,
+        ))
+        .and(wiremock::matchers::query_param("size", "2"))
+        .respond_with(
+            wiremock::ResponseTemplate::new(200)
+                .set_body_json(json!({ "data": [{ "id": 28067 }, { "id": 14574 }] })),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // When we ask for artworks like it, favoring the same artist
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", mock_uri)
+        .env("ACRES_USE_CACHE", "false") // So it hits wiremock
+        .arg("artwork-similar")
+        .arg(id.to_string())
+        .args(["--size", "2", "--weights", "artist=5"]);
+
+    // Then stdout has the related artworks
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    let value: serde_json::Value =
+        serde_json::from_str(&stdout).context(format!("failed to parse stdout: '{}'", &stdout))?;
+    assert_eq!(value["data"][0]["id"], 28067);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn artwork_random_daily_prints_ascii_banner() -> Result<(), Box<dyn std::error::Error>> {
+    let image = std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../acres-cli-ascii-art-77333.jpg"
+    ))?;
+
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+        .and(wiremock::matchers::query_param_contains(
+            "query",
+            r#""random_score":{"field":"id","seed":739907}"#,
+        ))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{
+                "id": 77333,
+                "title": "Water Lilies",
+                "artist_title": "Claude Monet",
+                "image_id": "3c27b499-af56-f0d5-93b5-a7f2f1ad5813",
+                "is_public_domain": true
+            }],
+            "info": { "license_text": "", "license_links": [], "version": "1.13" },
+            "config": { "iiif_url": format!("{}/iiif/2", mock_server.uri()), "website_url": "https://www.artic.edu" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    wiremock::Mock::given(wiremock::matchers::path(
+        "/iiif/2/3c27b499-af56-f0d5-93b5-a7f2f1ad5813/full/843,/0/default.jpg",
+    ))
+    .respond_with(wiremock::ResponseTemplate::new(200).set_body_bytes(image))
+    .expect(1)
+    .mount(&mock_server)
+    .await;
+
+    // When we ask for the artwork of the day as ASCII art
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", mock_uri)
+        .env("ACRES_USE_CACHE", "false") // So it hits wiremock
+        .arg("artwork-random")
+        .args([
+            "--daily",
+            "--date",
+            "2026-10-18",
+            "--ascii",
+            "--width",
+            "16",
+        ]);
+
+    // Then stdout has the art followed by its attribution
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    let mut lines = stdout.lines().rev();
+    assert_eq!(
+        lines.next(),
+        Some(
+            "Water Lilies, Claude Monet. CC0 Public Domain Designation. Art Institute of Chicago."
+        )
+    );
+    assert!(lines.all(|line| line.chars().count() <= 16));
+
+    Ok(())
+}
+
+#[test]
+fn artwork_random_daily_rejects_its_own_filters() -> Result<(), Box<dyn std::error::Error>> {
+    for filter in ["--public-domain", "--has-image"] {
+        // When the artwork of the day is asked for with a filter it always applies anyway
+        let mut cmd = Command::cargo_bin("acres-cli")?;
+        cmd.env("ACRES_USE_CACHE", "false")
+            .arg("artwork-random")
+            .args(["--daily", filter]);
+
+        // Then the combination is rejected rather than silently ignored
+        let output = cmd.output()?;
+        assert!(!output.status.success());
+        let stderr = String::from_utf8(output.stderr)?;
+        assert!(stderr.contains("cannot be used with"), "{stderr}");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn artwork_graph_command_outputs_dot() -> Result<(), Box<dyn std::error::Error>> {
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+        .and(wiremock::matchers::query_param("ids", "27992"))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{
+                "id": 27992,
+                "title": "A Sunday on La Grande Jatte — 1884",
+                "artist_id": 40482,
+                "artist_title": "Georges Seurat"
+            }]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // When we map an artwork's artist one hop out
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", mock_uri)
+        .env("ACRES_USE_CACHE", "false") // So it hits wiremock
+        .arg("artwork-graph")
+        .arg("27992")
+        .args(["--depth", "1", "--relations", "artist"]);
+
+    // Then stdout is a DOT graph linking the two
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    assert!(stdout.starts_with("digraph acres {"));
+    assert!(stdout.contains(r#""artist:40482" [label="Georges Seurat", shape=ellipse];"#));
+    assert!(stdout.contains(r#""artwork:27992" -> "artist:40482" [label="artist"];"#));
+
+    Ok(())
+}
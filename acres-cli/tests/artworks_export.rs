@@ -0,0 +1,64 @@
+use assert_cmd::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+#[tokio::test]
+async fn artworks_export_search_as_csv() -> Result<(), Box<dyn std::error::Error>> {
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+        .and(wiremock::matchers::query_param("q", "monet"))
+        .and(wiremock::matchers::query_param("fields", "id,title,color"))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+            "data": [
+                {"id": 16568, "title": "Water Lilies", "color": {"h": 196, "s": 27, "l": 46}},
+                {"id": 16571, "title": "Arrival of the Normandy Train, Gare Saint-Lazare", "color": null}
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // When we export search results
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", mock_uri)
+        .env("ACRES_USE_CACHE", "false") // So it hits wiremock
+        .arg("artworks-export")
+        .args(["--q", "monet"])
+        .args(["--columns", "id,title,color.h"]);
+
+    // Then stdout is a table with a header and one row per artwork
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    assert_eq!(
+        stdout,
+        "id,title,color.h\n\
+         16568,Water Lilies,196\n\
+         16571,\"Arrival of the Normandy Train, Gare Saint-Lazare\",\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn artworks_export_store_as_ndjson() -> Result<(), Box<dyn std::error::Error>> {
+    let store = assert_fs::TempDir::new()?;
+    std::fs::create_dir_all(store.path().join("records"))?;
+    std::fs::write(
+        store.path().join("records").join("4.json"),
+        json!({"id": 4, "title": "Priest and Boy", "thumbnail": {"alt_text": "A priest"}})
+            .to_string(),
+    )?;
+
+    // When we export a local store
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.arg("artworks-export")
+        .args(["--store", store.path().to_str().unwrap()])
+        .args(["--format", "ndjson"])
+        .args(["--columns", "id,thumbnail.alt_text"]);
+
+    // Then stdout has one JSON object per artwork
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    assert_eq!(stdout, "{\"id\":4,\"thumbnail.alt_text\":\"A priest\"}\n");
+
+    Ok(())
+}
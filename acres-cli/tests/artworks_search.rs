@@ -38,6 +38,32 @@ async fn artworks_search_with_q() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn artworks_search_replays_cassette() -> Result<(), Box<dyn std::error::Error>> {
+    let cassette_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("cassettes");
+
+    // When we run the CLI against a recorded cassette
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", "https://api.artic.edu/api/v1")
+        .env("ACRES_CASSETTE_DIR", cassette_dir)
+        .env("ACRES_CASSETTE_MODE", "replay") // So it never hits the network
+        .arg("artworks-search")
+        .args(["--q", "monet"]);
+
+    // Then stdout has the recorded list
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    let value: serde_json::Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["data"][0]["id"], 16568);
+    assert_eq!(
+        value["data"][1]["title"],
+        "Arrival of the Normandy Train, Gare Saint-Lazare"
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn artworks_search_with_query() -> Result<(), Box<dyn std::error::Error>> {
     let query = json!({
@@ -80,6 +106,204 @@ async fn artworks_search_with_query() -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+#[tokio::test]
+async fn artworks_search_dated() -> Result<(), Box<dyn std::error::Error>> {
+    let query = json!({
+        "bool": {
+            "must": [
+                { "range": { "date_start": { "lte": 1899 } } },
+                { "range": { "date_end": { "gte": 1800 } } }
+            ]
+        }
+    });
+
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::any())
+        .and(wiremock::matchers::path(
+            "/api/v1/artworks/search".to_string(),
+        ))
+        .and(wiremock::matchers::query_param("query", query.to_string()))
+        .respond_with(
+            wiremock::ResponseTemplate::new(200)
+                .set_body_json(json!({"data": [{"id": 28560, "title": "The Bedroom"}]})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // When we search by century
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", mock_uri)
+        .env("ACRES_USE_CACHE", "false") // So it hits wiremock
+        .arg("artworks-search")
+        .args(["--dated", "19th century"]);
+
+    // Then the span is sent as a range query
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    let value: serde_json::Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["data"][0]["id"], 28560);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn artworks_search_by_color_ranks_closest_first() -> Result<(), Box<dyn std::error::Error>> {
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::any())
+        .and(wiremock::matchers::path(
+            "/api/v1/artworks/search".to_string(),
+        ))
+        .and(wiremock::matchers::query_param_contains(
+            "query",
+            r#"{"range":{"color.h":{"gte":230.0,"lte":250.0}}}"#,
+        ))
+        .and(wiremock::matchers::query_param_contains("fields", "color"))
+        .respond_with(
+            wiremock::ResponseTemplate::new(200).set_body_json(json!({"data": [
+                {"id": 1, "color": {"h": 248, "s": 90, "l": 60}},
+                {"id": 2, "color": {"h": 240, "s": 100, "l": 48}}
+            ]})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // When we search for blue
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", mock_uri)
+        .env("ACRES_USE_CACHE", "false") // So it hits wiremock
+        .arg("artworks-search")
+        .args(["--color", "blue", "--tolerance", "10"]);
+
+    // Then the closest color comes first
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    let value: serde_json::Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["data"][0]["id"], 2);
+    assert_eq!(value["data"][1]["id"], 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn artworks_search_with_find() -> Result<(), Box<dyn std::error::Error>> {
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::any())
+        .and(wiremock::matchers::path(
+            "/api/v1/artworks/search".to_string(),
+        ))
+        .and(wiremock::matchers::query_param_contains(
+            "query",
+            r#"{"match_phrase":{"department_title":"Painting and Sculpture of Europe"}}"#,
+        ))
+        .and(wiremock::matchers::query_param_contains(
+            "query",
+            r#""must_not":[{"match":{"artwork_type_title":"print"}}]"#,
+        ))
+        .respond_with(
+            wiremock::ResponseTemplate::new(200)
+                .set_body_json(json!({"data": [{"id": 16568, "title": "Water Lilies"}]})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // When we search with the compact syntax
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", mock_uri)
+        .env("ACRES_USE_CACHE", "false") // So it hits wiremock
+        .arg("artworks-search")
+        .args([
+            "--find",
+            r#"artist:monet date:1870..1920 dept:"Painting and Sculpture of Europe" -type:print"#,
+        ]);
+
+    // Then it is compiled into the search query
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    let value: serde_json::Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["data"][0]["id"], 16568);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn artworks_search_with_find_takes_a_tolerance() -> Result<(), Box<dyn std::error::Error>> {
+    let mock_server = wiremock::MockServer::start().await;
+    let mock_uri = format!("{}/api/v1", mock_server.uri());
+    wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+        .and(wiremock::matchers::query_param_contains(
+            "query",
+            r#"{"range":{"color.h":{"gte":230.0,"lte":250.0}}}"#,
+        ))
+        .and(wiremock::matchers::query_param_contains(
+            "query",
+            r#"{"match":{"artist_title":"monet"}}"#,
+        ))
+        .respond_with(
+            wiremock::ResponseTemplate::new(200)
+                .set_body_json(json!({"data": [{"id": 16568, "title": "Water Lilies"}]})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // When we search for a color with the compact syntax and a tolerance
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_BASE_URI", mock_uri)
+        .env("ACRES_USE_CACHE", "false") // So it hits wiremock
+        .arg("artworks-search")
+        .args(["--find", "color:blue artist:monet", "--tolerance", "10"]);
+
+    // Then the tolerance applies to the color term
+    let stdout = String::from_utf8(cmd.output()?.stdout)?;
+    let value: serde_json::Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["data"][0]["id"], 16568);
+
+    Ok(())
+}
+
+#[test]
+fn artworks_search_tolerance_needs_a_color() -> Result<(), Box<dyn std::error::Error>> {
+    // When a tolerance is given with a compact query that has no color
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_USE_CACHE", "false")
+        .arg("artworks-search")
+        .args(["--find", "artist:monet", "--tolerance", "10"]);
+
+    // Then it is rejected rather than ignored
+    let output = cmd.output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("--tolerance needs a color: term"));
+
+    Ok(())
+}
+
+#[test]
+fn artworks_search_with_find_points_at_errors() -> Result<(), Box<dyn std::error::Error>> {
+    // When the compact query has an unknown key
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_USE_CACHE", "false")
+        .arg("artworks-search")
+        .args(["--find", "artist:monet when:1890"]);
+
+    // Then the error points at it
+    let output = cmd.output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    let mut lines = stderr
+        .lines()
+        .skip_while(|line| !line.contains("artist:monet"));
+    let query = lines.next().expect("query is shown");
+    let caret = lines.next().expect("caret is shown");
+    assert_eq!(caret.find('^'), Some(query.find("artist").unwrap() + 13));
+    assert!(stderr.contains("unknown key 'when' at column 14"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn artworks_search_with_query_sort() -> Result<(), Box<dyn std::error::Error>> {
     let query = json!({
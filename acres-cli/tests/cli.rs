@@ -22,3 +22,61 @@ fn unsupported_subcommand() -> Result<(), Box<dyn std::error::Error>> {
     ));
     Ok(())
 }
+
+#[test]
+fn ascii_defaults_come_from_config() -> Result<(), Box<dyn std::error::Error>> {
+    let config = assert_fs::NamedTempFile::new("config.toml")?;
+    std::fs::write(config.path(), "[ascii]\nalphabet = \"fast\"\n")?;
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_CONFIG", config.path())
+        .env("ACRES_ASCII__WIDTH", "64")
+        .args(["ascii-art", "--help"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("[default: fast]"))
+        .stdout(predicate::str::contains("[default: 64]"));
+    Ok(())
+}
+
+#[test]
+fn unknown_profile_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+    let config = assert_fs::NamedTempFile::new("config.toml")?;
+    std::fs::write(config.path(), "[profiles.staging]\nuse_cache = false\n")?;
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_CONFIG", config.path())
+        .env("ACRES_PROFILE", "nope")
+        .args(["artwork", "4"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("failed to load config"));
+    Ok(())
+}
+
+#[test]
+fn schema_check_reports_cached_drift() -> Result<(), Box<dyn std::error::Error>> {
+    let cache_dir = assert_fs::TempDir::new()?;
+    std::fs::write(
+        cache_dir.path().join("1"),
+        r#"{"data":[{"id":34,"title":"Claude Monet","birth_date":1840,"death_date":"1926","is_artist":true,"api_model":"agents"}],"info":{"version":"1.14"}}"#,
+    )?;
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.env("ACRES_CACHE_DIR", cache_dir.path())
+        .arg("schema-check")
+        .arg("agents")
+        .arg("--cached");
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "error: field death_date changed type (expected integer, found string)",
+        ))
+        .stdout(predicate::str::contains(
+            "warning: API version 1.14 differs from 1.13",
+        ))
+        .stderr(predicate::str::contains(
+            "responses have drifted from the models",
+        ));
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use assert_cmd::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn artwork(is_public_domain: bool) -> Result<assert_fs::NamedTempFile, Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("artwork.json")?;
+    let json = json!({
+        "config": { "iiif_url": "https://www.artic.edu/iiif/2" },
+        "data": {
+            "id": 151363,
+            "image_id": "0c9c1ed0-1bbd-d4ba-8f08-23b1d3b2d1b8",
+            "title": "Reclining Figure",
+            "is_public_domain": is_public_domain
+        }
+    });
+    std::fs::write(file.path(), json.to_string())?;
+    Ok(file)
+}
+
+#[test]
+fn iiif_enforce_rights_limits_restricted_works() -> Result<(), Box<dyn std::error::Error>> {
+    let artwork = artwork(false)?;
+
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.arg("iiif")
+        .arg(artwork.path())
+        .args(["--size", "full"])
+        .arg("--enforce-rights");
+
+    let output = cmd.output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert_eq!(
+        stdout.trim(),
+        "https://www.artic.edu/iiif/2/0c9c1ed0-1bbd-d4ba-8f08-23b1d3b2d1b8/full/!400,400/0/default.jpg"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn iiif_enforce_rights_keeps_public_domain_sizes() -> Result<(), Box<dyn std::error::Error>> {
+    let artwork = artwork(true)?;
+
+    let mut cmd = Command::cargo_bin("acres-cli")?;
+    cmd.arg("iiif")
+        .arg(artwork.path())
+        .args(["--size", "full"])
+        .arg("--enforce-rights");
+
+    let output = cmd.output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert_eq!(
+        stdout.trim(),
+        "https://www.artic.edu/iiif/2/0c9c1ed0-1bbd-d4ba-8f08-23b1d3b2d1b8/full/full/0/default.jpg"
+    );
+
+    Ok(())
+}
@@ -50,5 +50,7 @@ pub enum Action {
     ImageToAsciiBuilderUpdateFont(Font),
     ImageToAsciiBuilderUpdateMetric(Metric),
     ImageToAsciiBuilderUpdateImage(Bytes),
+    // Show a placeholder image while the full one downloads
+    ImageToAsciiBuilderPreviewImage(Bytes),
     ImageToAsciiBuilderBuildAscii,
 }
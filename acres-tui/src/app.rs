@@ -1,3 +1,4 @@
+use acres::rights::Policy;
 use color_eyre::Result;
 use crossterm::event::KeyEvent;
 use ratatui::prelude::Rect;
@@ -47,7 +48,10 @@ impl App {
             frame_rate,
             components: vec![
                 Box::new(Home::new()),
-                Box::new(Iiif::new(action_tx.clone())),
+                Box::new(Iiif::new(
+                    action_tx.clone(),
+                    Policy::from_config(&acres::Config::new()?.rights),
+                )),
                 Box::new(Artworks::new(artworks)),
                 Box::new(ImageToAsciiBuilder::new(action_tx.clone())),
             ],
@@ -1,30 +1,26 @@
-use acres::Api;
+use acres::{
+    Api,
+    artworks::{ArtworkInfo, Thumbnail},
+    images::{ArtworkImage, ImageOptions},
+    rights::Policy,
+};
 use color_eyre::eyre::{self};
-use iiif::{Format, ImageRequest, Quality, Region, Rotation, Size, Uri};
 use ratatui::{Frame, prelude::Rect};
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{action::Action, components::Component};
 
 pub struct Iiif {
-    base_uri: Option<Uri>,
-    region: Region,
-    size: Size,
-    rotation: Rotation,
-    quality: Quality,
-    format: Format,
+    artwork: Option<ArtworkInfo>,
+    options: ImageOptions,
     action_tx: UnboundedSender<Action>,
 }
 
 impl Iiif {
-    pub fn new(action_tx: UnboundedSender<Action>) -> Self {
+    pub fn new(action_tx: UnboundedSender<Action>, policy: Option<Policy>) -> Self {
         Self {
-            base_uri: None,
-            region: Region::Full,
-            size: Size::Width(843),
-            rotation: Rotation::Degrees(0.0.try_into().expect("0 degrees is a valid setting")),
-            quality: Quality::Default,
-            format: Format::Jpg,
+            artwork: None,
+            options: ImageOptions::new().policy(policy),
             action_tx,
         }
     }
@@ -39,30 +35,37 @@ impl Component for Iiif {
             Action::IiifUpdateBaseUri(artwork) => {
                 tracing::info!("updating base uri");
                 tracing::debug!(artwork = ?artwork);
-                self.base_uri = Some(artwork.try_into()?);
-                tracing::debug!(base_uri = ?self.base_uri);
+                let placeholder = artwork
+                    .data
+                    .thumbnail
+                    .as_ref()
+                    .map(Thumbnail::placeholder)
+                    .and_then(|placeholder| {
+                        placeholder
+                            .inspect_err(|e| tracing::warn!("failed to decode placeholder: {e}"))
+                            .ok()
+                            .flatten()
+                    });
+                if let Some(placeholder) = placeholder {
+                    let _ = self
+                        .action_tx
+                        .send(Action::ImageToAsciiBuilderPreviewImage(placeholder));
+                }
+                self.artwork = Some(artwork);
                 Ok(Some(Action::IiifRequestImage))
             }
             Action::IiifRequestImage => {
-                if let Some(uri) = &self.base_uri {
-                    tracing::debug!(uri = %uri, raw_uri = ?uri);
-                    let image_request = ImageRequest::builder()
-                        .uri(uri.clone())
-                        .region(self.region.clone())
-                        .size(self.size.clone())
-                        .rotation(self.rotation.clone())
-                        .quality(self.quality.clone())
-                        .format(self.format.clone())
-                        .build();
-                    tracing::debug!(image_request = %image_request, raw_image_request = ?image_request);
-
+                if let Some(artwork) = self.artwork.clone() {
+                    let options = self.options.clone();
                     let action_tx = self.action_tx.clone();
                     tokio::spawn(async move {
-                        let response: Option<bytes::Bytes> = Api::new()
-                            .fetch(image_request.to_string())
+                        let response = Api::new()
+                            .fetch_image(&artwork, &options)
                             .await
+                            .inspect(|image| tracing::debug!(image_request = %image.request()))
                             .inspect_err(|e| tracing::error!("failed to get image: {e}"))
-                            .ok();
+                            .ok()
+                            .map(ArtworkImage::into_bytes);
                         if let Some(response) = response {
                             let _ =
                                 action_tx.send(Action::ImageToAsciiBuilderUpdateImage(response));
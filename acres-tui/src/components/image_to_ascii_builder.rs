@@ -99,6 +99,16 @@ impl ImageToAsciiBuilder {
         Some(Action::ImageToAsciiBuilderBuildAscii)
     }
 
+    /// Show a placeholder image straight away.
+    fn preview_image(&self, image: Bytes) -> Option<Action> {
+        image_to_ascii_builder::Ascii::builder()
+            .input(image)
+            .build_preview()
+            .inspect_err(|e| tracing::warn!("failed to preview image: {e}"))
+            .ok()
+            .map(Action::UpdateAscii)
+    }
+
     /// Update alphabet.
     fn update_alphabet(&mut self, alphabet: Alphabet) -> Option<Action> {
         self.alphabet = alphabet;
@@ -201,6 +211,7 @@ impl Component for ImageToAsciiBuilder {
             },
             None => match action {
                 Action::ImageToAsciiBuilderUpdateImage(image) => self.update_image(image),
+                Action::ImageToAsciiBuilderPreviewImage(image) => self.preview_image(image),
                 Action::ImageToAsciiBuilderBuildAscii => self.build_ascii(),
                 Action::EnterBrowseAlphabetsMode => self.enter_browse_alphabets_mode(),
                 Action::EnterBrowseConversionAlgorithmsMode => {
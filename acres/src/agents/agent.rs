@@ -0,0 +1,176 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AcresError,
+    artworks::{CollectionQueryParams, Pagination},
+    endpoint::json_response,
+};
+
+/// An agent (a person or organization, such as an artist), as returned by [`GET /agents/{id}`].
+///
+/// [`GET /agents/{id}`]: https://api.artic.edu/docs/#get-agents-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Agent {
+    /// Data.
+    pub data: Data,
+}
+
+impl Display for Agent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} ({})", self.data.title, self.data.id))
+    }
+}
+
+json_response!(Agent, Agents);
+
+/// A page of agents, as returned by [`GET /agents`].
+///
+/// [`GET /agents`]: https://api.artic.edu/docs/#get-agents
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Agents {
+    /// Data.
+    pub data: Vec<Data>,
+    /// Pagination.
+    #[serde(default)]
+    pub pagination: Pagination,
+}
+
+/// Agent record.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Data {
+    /// ID.
+    pub id: u64,
+    /// Name.
+    #[serde(default)]
+    pub title: String,
+    /// Year of birth.
+    #[serde(default)]
+    pub birth_date: Option<i64>,
+    /// Year of death.
+    #[serde(default)]
+    pub death_date: Option<i64>,
+    /// Whether the agent is an artist.
+    #[serde(default)]
+    pub is_artist: Option<bool>,
+}
+
+/// A [`GET /agents/{id}`] request.
+///
+/// ```rust
+/// use acres::{Api, agents::request::agent::Request};
+///
+/// let request = Request::new(Api::new().base_uri(), 34);
+/// ```
+///
+/// [`GET /agents/{id}`]: https://api.artic.edu/docs/#get-agents-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Request {
+    base_uri: String,
+    id: u32,
+}
+
+impl Display for Request {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}/agents/{}", self.base_uri, self.id))
+    }
+}
+
+impl Request {
+    /// Constructs a new agent request.
+    pub fn new(base_uri: String, id: u32) -> Self {
+        Self { base_uri, id }
+    }
+}
+
+/// A [`GET /agents`] request.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::{Api, agents::request::agents};
+///
+/// # fn main() -> Result<()> {
+/// let request = agents::Builder::new()
+///     .base_uri(Api::new().base_uri())
+///     .ids(Some(vec![34, 35]))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`GET /agents`]: https://api.artic.edu/docs/#get-agents
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ListRequest(String);
+
+impl Display for ListRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+/// A [`GET /agents`] request builder.
+///
+/// [`GET /agents`]: https://api.artic.edu/docs/#get-agents
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    base_uri: String,
+    ids: Option<Vec<u32>>,
+    limit: Option<u32>,
+    page: Option<u32>,
+    fields: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Sets the base URI.
+    pub fn base_uri(mut self, base_uri: String) -> Self {
+        self.base_uri = base_uri;
+        self
+    }
+
+    /// Sets the agent ids to retrieve.
+    pub fn ids(mut self, ids: Option<Vec<u32>>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Sets limit on number of agents to return per page.
+    pub fn limit(mut self, limit: Option<u32>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets page number to return.
+    pub fn page(mut self, page: Option<u32>) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Sets the agent fields to retrieve.
+    pub fn fields(mut self, fields: Option<Vec<String>>) -> Self {
+        if let Some(fields) = fields {
+            self.fields = fields;
+        }
+        self
+    }
+
+    /// Builds request for the agents collection.
+    pub fn build(&self) -> Result<ListRequest, AcresError> {
+        let query_params = CollectionQueryParams {
+            ids: self.ids.clone(),
+            limit: self.limit,
+            page: self.page,
+            fields: self.fields.clone(),
+            include: vec![],
+        };
+        Ok(ListRequest(format!(
+            "{}/agents{}",
+            self.base_uri, query_params
+        )))
+    }
+}
@@ -0,0 +1,26 @@
+//! Modules and types for working with the [Agents Collection].
+//!
+//! [Agents Collection]: https://api.artic.edu/docs/#agents
+
+mod agent;
+
+pub use agent::{Agent, Agents, Data};
+
+/// Modules for requesting items from the [Agents Collection].
+///
+/// [Agents Collection]: https://api.artic.edu/docs/#agents
+pub mod request {
+    /// A [`GET /agents/{id}`] request.
+    ///
+    /// [`GET /agents/{id}`]: https://api.artic.edu/docs/#get-agents-id
+    pub mod agent {
+        pub use crate::agents::agent::Request;
+    }
+
+    /// A [`GET /agents`] request.
+    ///
+    /// [`GET /agents`]: https://api.artic.edu/docs/#get-agents
+    pub mod agents {
+        pub use crate::agents::agent::{Builder, ListRequest as Request};
+    }
+}
@@ -1,14 +1,27 @@
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-use crate::{AcresError, config::Config};
+use crate::{
+    AcresError, Cassette, Endpoint,
+    artworks::ArtworkInfo,
+    cache,
+    client::{AgentsClient, ArtworksClient, SoundsClient, ToursClient},
+    config::Config,
+    endpoint::json_response,
+    images::{ArtworkImage, Image, ImageOptions, ImageStore},
+    stream::{CacheWriter, DataStream, Source},
+};
 use anyhow::{Context, anyhow};
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 
 #[doc(hidden)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub struct Cached(serde_json::Value);
+pub struct Cached(pub(crate) serde_json::Value);
 
 impl Display for Cached {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -17,18 +30,13 @@ impl Display for Cached {
     }
 }
 
-impl TryFrom<Bytes> for Cached {
-    type Error = AcresError;
-
-    fn try_from(value: Bytes) -> Result<Self, Self::Error> {
-        let reader = value.reader();
-        serde_json::from_reader(reader).map_err(|e| AcresError::Unexpected(anyhow!(e.to_string())))
-    }
-}
+json_response!(Cached);
 
 /// A helper for fetching resources form the [AIC public APIs].
 ///
-/// Responses are cached by default, but this behavior can be turned off.
+/// Responses are cached by default, but this behavior can be turned off. The cache can be shared
+/// by several processes: entries are written atomically, and an entry that's corrupt anyway is
+/// moved into a `quarantine` directory and fetched again.
 ///
 /// ```rust
 /// let api_sans_caching = acres::Api::builder().use_cache(false).build();
@@ -39,6 +47,27 @@ impl TryFrom<Bytes> for Cached {
 pub struct Api {
     pub(crate) base_uri: String,
     pub(crate) use_cache: bool,
+    pub(crate) cache_dir: PathBuf,
+    pub(crate) cassette: Option<Cassette>,
+    pub(crate) http: Http,
+}
+
+/// Settings for how requests go out over the wire.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Http {
+    pub(crate) user_agent: String,
+    pub(crate) timeout: Duration,
+    pub(crate) rate_limit: Option<u32>,
+}
+
+impl Default for Http {
+    fn default() -> Self {
+        Self {
+            user_agent: format!("ACRES/{}", env!("CARGO_PKG_VERSION")),
+            timeout: Duration::from_secs(30),
+            rate_limit: None,
+        }
+    }
 }
 
 impl Api {
@@ -102,24 +131,329 @@ impl Api {
     pub fn use_cache(&self) -> bool {
         self.use_cache
     }
+
+    /// Returns where responses are cached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let api = acres::Api::builder().cache_dir("/tmp/acres").build();
+    /// assert_eq!(api.cache_dir(), std::path::Path::new("/tmp/acres"));
+    /// ```
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Returns the cassette, if requests are being recorded or replayed.
+    ///
+    /// # Examples
+    ///
+    /// There is no cassette by default.
+    ///
+    /// ```
+    /// let api = acres::Api::builder().cassette(None).build();
+    /// assert!(api.cassette().is_none());
+    /// ```
+    pub fn cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_ref()
+    }
+
+    /// Returns the request timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let api = acres::Api::builder().timeout(Duration::from_secs(5)).build();
+    /// assert_eq!(api.timeout(), Duration::from_secs(5));
+    /// ```
+    pub fn timeout(&self) -> Duration {
+        self.http.timeout
+    }
+
+    /// Returns the maximum number of requests per second, if limited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let api = acres::Api::builder().rate_limit(Some(2)).build();
+    /// assert_eq!(api.rate_limit(), Some(2));
+    /// ```
+    pub fn rate_limit(&self) -> Option<u32> {
+        self.http.rate_limit
+    }
+
+    /// Returns the value sent in the `user-agent` header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let api = acres::Api::builder().user_agent("my-museum-app/1.0").build();
+    /// assert_eq!(api.user_agent(), "my-museum-app/1.0");
+    /// ```
+    pub fn user_agent(&self) -> &str {
+        &self.http.user_agent
+    }
 }
 
 impl Api {
+    /// Starts a request against the [Artworks collection].
+    ///
+    /// The base URI is taken from this client, so you only need to describe what you want.
+    ///
+    /// ```rust
+    /// # use anyhow::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let mock_server = wiremock::MockServer::start().await;
+    /// # let mock_uri = format!("{}/api/v1", mock_server.uri());
+    /// # wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+    /// #     .and(wiremock::matchers::query_param("q", "monet"))
+    /// #     .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": []})))
+    /// #     .expect(1)
+    /// #     .mount(&mock_server)
+    /// #     .await;
+    /// let api = acres::Api::new();
+    /// # let api = acres::Api::builder().base_uri(&mock_uri).use_cache(false).build();
+    /// let search = api.artworks().search().q("monet").send().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Artworks collection]: https://api.artic.edu/docs/#artworks
+    pub fn artworks(&self) -> ArtworksClient<'_> {
+        ArtworksClient::new(self)
+    }
+
+    /// Returns the entrypoint for the [Agents collection].
+    ///
+    /// [Agents collection]: https://api.artic.edu/docs/#agents
+    pub fn agents(&self) -> AgentsClient<'_> {
+        AgentsClient::new(self)
+    }
+
+    /// Returns the entrypoint for the [Tours collection].
+    ///
+    /// [Tours collection]: https://api.artic.edu/docs/#tours
+    pub fn tours(&self) -> ToursClient<'_> {
+        ToursClient::new(self)
+    }
+
+    /// Returns the entrypoint for the [Mobile Sounds collection].
+    ///
+    /// [Mobile Sounds collection]: https://api.artic.edu/docs/#mobile-sounds
+    pub fn sounds(&self) -> SoundsClient<'_> {
+        SoundsClient::new(self)
+    }
+
+    /// Sends a request to a typed endpoint.
+    ///
+    /// This is like [`Api::fetch()`], except the response type comes from the endpoint.
+    ///
+    /// [`Api::fetch()`]: struct.Api.html#method.fetch
+    pub async fn send<E: Endpoint>(&self, endpoint: &E) -> Result<E::Response, AcresError> {
+        self.request(E::METHOD, endpoint.to_string()).await
+    }
+
     // TODO: Clean up optional query params handling. Passing usize here is a hack.
     /// Fetch
     pub async fn fetch<T>(&self, endpoint: String) -> Result<T, AcresError>
     where
         T: TryFrom<Bytes>,
+        T::Error: std::error::Error + Send + Sync + 'static,
     {
-        let cached: Option<Bytes> = self.load_from_cache(&endpoint)?;
-        let results: Bytes = match cached {
-            Some(results) => results,
-            None => fetch(&endpoint).await?,
+        self.request(reqwest::Method::GET, endpoint).await
+    }
+
+    async fn request<T>(&self, method: reqwest::Method, endpoint: String) -> Result<T, AcresError>
+    where
+        T: TryFrom<Bytes>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        // Cassettes stand in for the network and the cache alike.
+        if let Some(cassette) = &self.cassette {
+            let results = cassette.play(&self.http, method, &endpoint).await?;
+            return parse(results, &endpoint);
+        }
+        // Only idempotent reads are safe to serve from the cache.
+        if method != reqwest::Method::GET {
+            let results = request(&self.http, method, &endpoint).await?;
+            return parse(results, &endpoint);
+        }
+        if let Some(cached) = self.load_from_cache(&endpoint)? {
+            match T::try_from(cached.clone()) {
+                Ok(results) => return Ok(results),
+                // A corrupt entry is set aside and fetched again.
+                Err(_) if serde_json::from_slice::<serde::de::IgnoredAny>(&cached).is_err() => {
+                    self.quarantine_cached(&endpoint)?
+                }
+                // Valid JSON that doesn't fit the type would fail again if fetched, so keep it.
+                Err(_) => {
+                    return Err(AcresError::from(anyhow!(
+                        "failed to parse cached response for {endpoint}"
+                    )));
+                }
+            }
+        }
+        let results = request(&self.http, method, &endpoint).await?;
+        let parsed = parse(results.clone(), &endpoint)?;
+        self.store_in_cache(&endpoint, results)?;
+        Ok(parsed)
+    }
+
+    /// Returns a copy of this API that neither reads nor writes the response cache.
+    pub(crate) fn uncached(&self) -> Api {
+        Api {
+            use_cache: false,
+            ..self.clone()
+        }
+    }
+
+    /// Moves a corrupt cache entry out of the way.
+    pub(crate) fn quarantine_cached(&self, endpoint: &String) -> Result<(), AcresError> {
+        if let Some(path) = self.cache_path(endpoint).filter(|path| path.is_file()) {
+            cache::quarantine(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Streams the items in a response's `data` as the body arrives.
+    ///
+    /// Unlike [`Api::fetch()`], only one item is held in memory at a time, which keeps large
+    /// pages and data dumps cheap. The response is still cached, and served from the cache when
+    /// it's there. See [`stream`] for more.
+    ///
+    /// [`Api::fetch()`]: struct.Api.html#method.fetch
+    /// [`stream`]: stream/index.html
+    pub async fn stream(&self, endpoint: String) -> Result<DataStream, AcresError> {
+        if let Some(cassette) = &self.cassette {
+            let results = cassette
+                .play(&self.http, reqwest::Method::GET, &endpoint)
+                .await?;
+            return Ok(DataStream::new(Source::Bytes(Some(results))));
+        }
+        let cache_file_path = self.cache_path(&endpoint);
+        if let Some(path) = cache_file_path.as_ref().filter(|path| path.is_file()) {
+            let file = std::fs::File::open(path)
+                .with_context(|| format!("failed to open cached file {}", path.display()))?;
+            tracing::info!("Streaming '{}' from cache", path.display());
+            return Ok(DataStream::new(Source::File(file, path.clone())));
+        }
+        let method = reqwest::Method::GET;
+        let response = send_streaming(&self.http, method.clone(), &endpoint).await?;
+        if response.status() != StatusCode::OK {
+            let error = read_raw(response, &method, &endpoint)
+                .await?
+                .into_body(&method, &endpoint)
+                .err();
+            return Err(error.unwrap_or_else(|| anyhow!("{method} {endpoint} failed").into()));
+        }
+        let cache = cache_file_path.map(CacheWriter::create).transpose()?;
+        Ok(DataStream::new(Source::Response(response, cache)))
+    }
+
+    /// Fetches an IIIF image.
+    ///
+    /// When caching is on, images go through the [image store] in the cache directory, which may
+    /// scale down a larger image it already has rather than fetch a new one.
+    ///
+    /// Responses that aren't images, such as error pages, are rejected rather than stored, and a
+    /// server that refuses the request fails with [`AcresError::ImageRefused`]. Responses replayed
+    /// from a cassette are checked the same way, status and content type included.
+    ///
+    /// [image store]: images/index.html
+    pub async fn image(
+        &self,
+        request: &iiif::ImageRequest,
+        artwork_id: Option<u32>,
+    ) -> Result<Image, AcresError> {
+        let endpoint = request.to_string();
+        let method = reqwest::Method::GET;
+        if let Some(cassette) = &self.cassette {
+            let response = cassette.play_raw(&self.http, method, &endpoint).await?;
+            return image_from(request, endpoint, response, artwork_id);
+        }
+        let store = if self.use_cache {
+            ImageStore::in_cache(self).ok()
+        } else {
+            None
         };
-        let results = self.store_in_cache(&endpoint, results)?;
-        T::try_from(results)
-            .map_err(|_| anyhow!("failed to store in cache"))
-            .map_err(AcresError::from)
+        if let Some(image) = store
+            .as_ref()
+            .map(|store| store.get(request))
+            .transpose()?
+            .flatten()
+        {
+            return Ok(image);
+        }
+        let response = send_raw(&self.http, method, &endpoint).await?;
+        let image = image_from(request, endpoint, response, artwork_id)?;
+        if let Some(store) = store {
+            store.save(&image)?;
+        }
+        Ok(image)
+    }
+
+    /// Fetches an artwork's image.
+    ///
+    /// The request is built from the artwork and [`ImageOptions`], with the options' rights
+    /// policy applied. If the server refuses the size, as IIIF servers do for sizes beyond what
+    /// they allow, smaller sizes are tried in turn before giving up.
+    ///
+    /// ```rust,no_run
+    /// # use anyhow::Result;
+    /// use acres::{Api, artworks::ArtworkInfo, images::ImageOptions};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let artwork: ArtworkInfo = serde_json::from_str(r#"{
+    /// #     "config": { "iiif_url": "https://www.artic.edu/iiif/2" },
+    /// #     "data": { "id": 77333, "image_id": "3eec80ab-bad4-8b35-1adb-57b7e7cb7adb", "title": "Nocturne" }
+    /// # }"#)?;
+    /// let image = Api::new().fetch_image(&artwork, &ImageOptions::new()).await?;
+    /// println!("{} {:?}", image.content_type(), image.dimensions());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_image(
+        &self,
+        artwork: &ArtworkInfo,
+        options: &ImageOptions,
+    ) -> Result<ArtworkImage, AcresError> {
+        let requested = options.request(artwork)?;
+        let mut fallbacks = options.fallbacks(requested.size()).into_iter();
+        let mut request = requested.clone();
+        loop {
+            let error = match self.image(&request, Some(artwork.data.id)).await {
+                Ok(image) => {
+                    return Ok(ArtworkImage::new(
+                        artwork.clone(),
+                        requested,
+                        request,
+                        image,
+                    ));
+                }
+                Err(error) => error,
+            };
+            let refused = matches!(error, AcresError::ImageRefused(status, _) if REFUSED_SIZE_STATUSES.contains(&status));
+            match fallbacks.next() {
+                Some(size) if refused => {
+                    tracing::info!("'{request}' was refused; trying {size}");
+                    request = requested.with_size(size);
+                }
+                _ => return Err(error),
+            }
+        }
+    }
+
+    /// Returns where a response would be cached, if caching is on.
+    fn cache_path(&self, endpoint: &String) -> Option<std::path::PathBuf> {
+        if !self.use_cache {
+            return None;
+        }
+        let id = xxhash_rust::xxh3::xxh3_64(format!("{:?}", endpoint).as_bytes()).to_string();
+        Some(self.cache_dir.join(id))
     }
 
     /// Stores an item in cache.
@@ -129,15 +463,12 @@ impl Api {
         }
         let id = xxhash_rust::xxh3::xxh3_64(format!("{:?}", endpoint).as_bytes()).to_string();
         tracing::debug!("Looking to store id '{}' to cache", &id);
-        let cache_file_path = match Config::new() {
-            Ok(config) => config.cache_dir.join(&id),
-            Err(_) => return Ok(data),
-        };
+        let cache_file_path = self.cache_dir.join(&id);
         tracing::debug!(?cache_file_path);
         if cache_file_path.is_file() {
             return Ok(data);
         }
-        std::fs::write(&cache_file_path, data.clone()).with_context(|| "writing data to file")?;
+        cache::write_atomic(&cache_file_path, &data)?;
         tracing::info!(
             "Wrote '{}' to cache at '{}'",
             id,
@@ -153,10 +484,7 @@ impl Api {
         }
         let id = xxhash_rust::xxh3::xxh3_64(format!("{:?}", endpoint).as_bytes()).to_string();
         tracing::debug!("Looking to load id '{}' from cache", id);
-        let cache_file_path = match Config::new() {
-            Ok(config) => config.cache_dir.join(&id),
-            Err(_) => return Ok(None),
-        };
+        let cache_file_path = self.cache_dir.join(&id);
         if !cache_file_path.is_file() {
             return Ok(None);
         }
@@ -204,6 +532,9 @@ impl Default for Api {
 pub struct ApiBuilder {
     base_uri: String,
     use_cache: bool,
+    cache_dir: PathBuf,
+    cassette: Option<Cassette>,
+    http: Http,
 }
 
 impl ApiBuilder {
@@ -238,22 +569,151 @@ impl ApiBuilder {
         self
     }
 
+    /// Sets where to cache responses.
+    ///
+    /// The default is the platform's local data directory (e.g., `~/.local/share/acres` on Linux).
+    ///
+    /// ```
+    /// let api = acres::Api::builder()
+    ///     .cache_dir("/tmp/acres")
+    ///     .build();
+    /// assert_eq!(api.cache_dir(), std::path::Path::new("/tmp/acres"));
+    /// ```
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    /// Sets a cassette to record or replay requests.
+    ///
+    /// While a cassette is set, the response cache is bypassed. Recording
+    /// writes every interaction to the cassette's directory and replaying
+    /// serves responses from it without touching the network.
+    ///
+    /// ```
+    /// use acres::{Cassette, CassetteMode};
+    ///
+    /// let api = acres::Api::builder()
+    ///     .cassette(Some(Cassette::new(CassetteMode::Replay, "tests/cassettes")))
+    ///     .build();
+    /// assert_eq!(api.cassette().map(|c| c.mode()), Some(CassetteMode::Replay));
+    /// ```
+    pub fn cassette(mut self, cassette: Option<Cassette>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    /// Sets the request timeout.
+    ///
+    /// The default is 30 seconds.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let api = acres::Api::builder()
+    ///     .timeout(Duration::from_secs(5))
+    ///     .build();
+    /// assert_eq!(api.timeout(), Duration::from_secs(5));
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.http.timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum number of requests per second.
+    ///
+    /// The limit is shared by every client in the process. The default is no limit.
+    ///
+    /// ```
+    /// let api = acres::Api::builder()
+    ///     .rate_limit(Some(1))
+    ///     .build();
+    /// assert_eq!(api.rate_limit(), Some(1));
+    /// ```
+    pub fn rate_limit(mut self, rate_limit: Option<u32>) -> Self {
+        self.http.rate_limit = rate_limit;
+        self
+    }
+
+    /// Sets the value sent in the `user-agent` header.
+    ///
+    /// ```
+    /// let api = acres::Api::builder()
+    ///     .user_agent("my-museum-app/1.0")
+    ///     .build();
+    /// assert_eq!(api.user_agent(), "my-museum-app/1.0");
+    /// ```
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.http.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Creates a builder from the named profile in the [config file].
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # fn main() -> Result<()> {
+    /// let api = acres::ApiBuilder::from_profile("offline")?.build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [config file]: struct.Config.html
+    pub fn from_profile(profile: &str) -> Result<Self, AcresError> {
+        Ok(Config::with_profile(profile)?.into())
+    }
+
     /// Builds the actual API client.
     pub fn build(self) -> Api {
         Api {
             base_uri: self.base_uri,
             use_cache: self.use_cache,
+            cache_dir: self.cache_dir,
+            cassette: self.cassette,
+            http: self.http,
         }
     }
 }
 
-impl Default for ApiBuilder {
-    fn default() -> Self {
-        // TODO: Check ACRES_* for overrides
-        let config = Config::new().unwrap_or_default();
+impl From<Config> for ApiBuilder {
+    fn from(config: Config) -> Self {
+        let cassette = config
+            .cassette_dir
+            .map(|dir| Cassette::new(config.cassette_mode.unwrap_or_default(), dir));
+        let defaults = Http::default();
+        let http = Http {
+            user_agent: config.user_agent.unwrap_or(defaults.user_agent),
+            timeout: config
+                .timeout
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.timeout),
+            rate_limit: config.rate_limit,
+        };
         ApiBuilder {
             base_uri: config.base_uri,
             use_cache: config.use_cache,
+            cache_dir: config.cache_dir,
+            cassette,
+            http,
+        }
+    }
+}
+
+impl Default for ApiBuilder {
+    /// Creates a builder from the [config file], or from the built-in defaults if it can't be
+    /// loaded.
+    ///
+    /// A warning is logged when that happens. Load a [`Config`] yourself to handle the error.
+    ///
+    /// [config file]: struct.Config.html
+    /// [`Config`]: struct.Config.html
+    fn default() -> Self {
+        match Config::new() {
+            Ok(config) => config.into(),
+            Err(error) => {
+                tracing::warn!(%error, "Unable to load config; using built-in defaults");
+                Config::default().into()
+            }
         }
     }
 }
@@ -265,11 +725,124 @@ impl Default for ApiBuilder {
 ///
 /// [`Api::fetch()`]: struct.Api.html#method.fetch
 pub async fn fetch(endpoint: &String) -> Result<Bytes, AcresError> {
-    let client = reqwest::Client::new();
+    request(&Http::default(), reqwest::Method::GET, endpoint).await
+}
+
+async fn request(
+    http: &Http,
+    method: reqwest::Method,
+    endpoint: &String,
+) -> Result<Bytes, AcresError> {
+    send_raw(http, method.clone(), endpoint)
+        .await?
+        .into_body(&method, endpoint)
+}
+
+/// Statuses IIIF servers answer with when a size is larger than they allow.
+const REFUSED_SIZE_STATUSES: [u16; 4] = [400, 403, 413, 501];
+
+/// When the next rate-limited request may go out, across all clients.
+static NEXT_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Waits for a slot under the rate limit, if there is one.
+async fn throttle(rate_limit: Option<u32>) {
+    let Some(rate_limit) = rate_limit.filter(|limit| *limit > 0) else {
+        return;
+    };
+    let interval = Duration::from_secs(1) / rate_limit;
+    let wait = {
+        let mut next = NEXT_REQUEST
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        let slot = next.map_or(now, |next| next.max(now));
+        *next = Some(slot + interval);
+        slot - now
+    };
+    if !wait.is_zero() {
+        tracing::debug!(?wait, "Waiting on rate limit");
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Parses a response, keeping the reason it didn't parse.
+fn parse<T>(results: Bytes, endpoint: &str) -> Result<T, AcresError>
+where
+    T: TryFrom<Bytes>,
+    T::Error: std::error::Error + Send + Sync + 'static,
+{
+    T::try_from(results)
+        .map_err(|e| {
+            anyhow::Error::new(e).context(format!("failed to parse response from {endpoint}"))
+        })
+        .map_err(AcresError::from)
+}
+
+/// Describes an image response, refusing error statuses and bodies that aren't images.
+fn image_from(
+    request: &iiif::ImageRequest,
+    endpoint: String,
+    response: RawResponse,
+    artwork_id: Option<u32>,
+) -> Result<Image, AcresError> {
+    if response.status != StatusCode::OK.as_u16() {
+        return Err(AcresError::ImageRefused(response.status, endpoint));
+    }
+    let content_type = response.headers.get("content-type").cloned();
+    let image = Image::new(request, response.body, content_type.as_deref(), artwork_id);
+    image.validate(request.format())?;
+    Ok(image)
+}
+
+/// A response as it came off the wire.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RawResponse {
+    pub(crate) status: u16,
+    pub(crate) headers: BTreeMap<String, String>,
+    pub(crate) body: Bytes,
+}
+
+impl RawResponse {
+    /// Returns the body for successful responses and the API's error otherwise.
+    pub(crate) fn into_body(
+        self,
+        method: &reqwest::Method,
+        endpoint: &String,
+    ) -> Result<Bytes, AcresError> {
+        if self.status == StatusCode::OK.as_u16() {
+            return Ok(self.body);
+        }
+        let error = serde_json::from_slice::<serde_json::Value>(&self.body)
+            .map(|value| anyhow!("{}: {}", value["error"], value["detail"]))
+            .with_context(|| format!("awaiting errror from {} {}", method, endpoint))?;
+        Err(error.into())
+    }
+}
+
+pub(crate) async fn send_raw(
+    http: &Http,
+    method: reqwest::Method,
+    endpoint: &String,
+) -> Result<RawResponse, AcresError> {
+    let response = send_streaming(http, method.clone(), endpoint).await?;
+    read_raw(response, &method, endpoint).await
+}
+
+/// Sends a request, returning as soon as the headers arrive.
+async fn send_streaming(
+    http: &Http,
+    method: reqwest::Method,
+    endpoint: &String,
+) -> Result<reqwest::Response, AcresError> {
+    throttle(http.rate_limit).await;
+    let client = reqwest::Client::builder()
+        .timeout(http.timeout)
+        .build()
+        .context("failed constructing HTTP client")?;
     let mut headers = reqwest::header::HeaderMap::new();
     headers.insert(
         "user-agent",
-        format!("ACRES/{}", env!("CARGO_PKG_VERSION"),)
+        http.user_agent
             .parse()
             .context("failed constructing user-agent header")?,
     );
@@ -279,28 +852,47 @@ pub async fn fetch(endpoint: &String) -> Result<Bytes, AcresError> {
             .parse()
             .context("failed constructing ACRES-User-Agent header")?,
     );
-    let request = client.get(endpoint).headers(headers);
+    let request = client.request(method.clone(), endpoint).headers(headers);
     let response = request
         .send()
         .await
-        .with_context(|| format!("GET {}", endpoint))?;
-    let value = match response.status() {
-        StatusCode::OK => Ok(response
-            .bytes()
-            //.json::<serde_json::Value>()
-            .await
-            .with_context(|| format!("awaiting JSON from GET {}", endpoint))?),
-        _ => Err(response
-            .json::<serde_json::Value>()
-            .await
-            .map(|value| anyhow!("{}: {}", value["error"], value["detail"]))
-            .with_context(|| format!("awaiting errror from GET {}", endpoint))?),
-    };
-    Ok(value?)
+        .with_context(|| format!("{} {}", method, endpoint))?;
+    Ok(response)
 }
+
+/// Reads a whole response.
+async fn read_raw(
+    response: reqwest::Response,
+    method: &reqwest::Method,
+    endpoint: &String,
+) -> Result<RawResponse, AcresError> {
+    let status = response.status().as_u16();
+    let mut headers: BTreeMap<String, String> = BTreeMap::new();
+    for (name, value) in response.headers() {
+        let value = String::from_utf8_lossy(value.as_bytes()).to_string();
+        headers
+            .entry(name.to_string())
+            .and_modify(|existing| {
+                existing.push_str(", ");
+                existing.push_str(&value);
+            })
+            .or_insert(value);
+    }
+    let body = response
+        .bytes()
+        .await
+        .with_context(|| format!("awaiting body from {} {}", method, endpoint))?;
+    Ok(RawResponse {
+        status,
+        headers,
+        body,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{CassetteMode, fixtures::page};
 
     #[test]
     fn base_uri_default() {
@@ -318,4 +910,367 @@ mod tests {
         let api = Api::builder().base_uri(custom_uri).build();
         assert_eq!(api.base_uri, custom_uri);
     }
+
+    #[tokio::test]
+    async fn rate_limit_spaces_out_requests() {
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            throttle(Some(20)).await;
+        }
+
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn sends_configured_user_agent() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::header("user-agent", "tester/1.0"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .use_cache(false)
+            .user_agent("tester/1.0")
+            .build();
+
+        let result: Result<Cached, AcresError> = api.fetch(mock_server.uri()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn send_returns_typed_response() {
+        let body = page(
+            serde_json::json!([ { "id": 999, "title": "Emergency!", "image_id": null } ]),
+            1,
+            1,
+            1,
+        );
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+            .and(wiremock::matchers::query_param("limit", "1"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .use_cache(false)
+            .build();
+
+        let artworks = api.artworks().list().limit(1).send().await.unwrap();
+
+        assert_eq!(artworks.data[0].title, "Emergency!");
+    }
+
+    #[tokio::test]
+    async fn send_reports_garbage_search_responses() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_string("{\"data\": ["))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .use_cache(false)
+            .build();
+        let request = crate::artworks::request::search::Builder::default()
+            .base_uri(api.base_uri())
+            .q(Some("monet".to_string()))
+            .build()
+            .unwrap();
+
+        let error = api.send(&request).await.unwrap_err();
+
+        assert!(
+            format!("{error:#}").contains("EOF while parsing"),
+            "{error:#}"
+        );
+    }
+
+    #[tokio::test]
+    async fn caches_responses_in_builder_cache_dir() {
+        let cache_dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/4"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": { "id": 4 } })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .use_cache(true)
+            .cache_dir(cache_dir.path())
+            .build();
+        let endpoint = format!("{}/api/v1/artworks/4", mock_server.uri());
+
+        let first: Cached = api.fetch(endpoint.clone()).await.unwrap();
+        let second: Cached = api.fetch(endpoint).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn keeps_cached_json_that_does_not_fit_the_type() {
+        let cache_dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .use_cache(true)
+            .cache_dir(cache_dir.path())
+            .build();
+        let request = crate::artworks::request::artwork::Request::new(api.base_uri(), 4);
+        api.store_in_cache(&request.to_string(), Bytes::from_static(br#"{"data": []}"#))
+            .unwrap();
+
+        let result = api.send(&request).await;
+
+        assert!(result.is_err());
+        assert!(api.load_from_cache(&request.to_string()).unwrap().is_some());
+        assert!(!cache_dir.path().join("quarantine").exists());
+    }
+
+    #[tokio::test]
+    async fn refetches_corrupt_cached_searches() {
+        let cache_dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": [{ "id": 4 }] })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .use_cache(true)
+            .cache_dir(cache_dir.path())
+            .build();
+        let request = crate::artworks::request::search::Builder::default()
+            .base_uri(api.base_uri())
+            .q(Some("monet".to_string()))
+            .build()
+            .unwrap();
+        api.store_in_cache(&request.to_string(), Bytes::from_static(br#"{"data": ["#))
+            .unwrap();
+
+        let search = api.send(&request).await.unwrap();
+
+        assert_eq!(search.data()[0]["id"], 4);
+        assert!(cache_dir.path().join("quarantine").is_dir());
+    }
+
+    #[tokio::test]
+    async fn stream_yields_items_then_envelope() {
+        let body = serde_json::json!({
+            "pagination": { "total": 2, "total_pages": 1 },
+            "data": [ { "id": 1 }, { "id": 2 } ],
+            "info": { "version": "1.13" }
+        });
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().use_cache(false).build();
+
+        let mut stream = api
+            .stream(format!("{}/api/v1/artworks", mock_server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(stream.next_item().await.unwrap().unwrap()["id"], 1);
+        assert_eq!(stream.next_item().await.unwrap().unwrap()["id"], 2);
+        assert!(stream.next_item().await.unwrap().is_none());
+        assert_eq!(stream.envelope()["info"]["version"], "1.13");
+        assert_eq!(stream.envelope()["pagination"]["total"], 2);
+    }
+
+    #[tokio::test]
+    async fn image_describes_fetched_bytes() {
+        let jpeg = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../acres-cli-ascii-art-77333.jpg"
+        ))
+        .unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path(
+            "/iiif/2/abc/full/843,/0/default.jpg",
+        ))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(jpeg.clone(), "image/jpeg"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+        let api = Api::builder().use_cache(false).build();
+        let request: iiif::ImageRequest =
+            format!("{}/iiif/2/abc/full/843,/0/default.jpg", mock_server.uri())
+                .parse()
+                .unwrap();
+
+        let image = api.image(&request, Some(77333)).await.unwrap();
+
+        assert_eq!(image.bytes().as_ref(), jpeg.as_slice());
+        assert_eq!(image.sidecar().content_type, "image/jpeg");
+        assert_eq!(image.sidecar().width, Some(1690));
+        assert_eq!(image.sidecar().artwork_id, Some(77333));
+    }
+
+    fn artwork_at(uri: &str) -> ArtworkInfo {
+        serde_json::from_value(serde_json::json!({
+            "config": { "iiif_url": format!("{uri}/iiif/2") },
+            "data": { "id": 77333, "image_id": "abc", "title": "Nocturne" }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn fetch_image_falls_back_to_smaller_sizes() {
+        let jpeg = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../acres-cli-ascii-art-77333.jpg"
+        ))
+        .unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        for size in ["1200,", "843,"] {
+            wiremock::Mock::given(wiremock::matchers::path(format!(
+                "/iiif/2/abc/full/{size}/0/default.jpg"
+            )))
+            .respond_with(wiremock::ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        }
+        wiremock::Mock::given(wiremock::matchers::path(
+            "/iiif/2/abc/full/600,/0/default.jpg",
+        ))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(jpeg, "image/jpeg"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+        let api = Api::builder().use_cache(false).build();
+
+        let image = api
+            .fetch_image(
+                &artwork_at(&mock_server.uri()),
+                &ImageOptions::new().size(iiif::Size::Width(1200)),
+            )
+            .await
+            .unwrap();
+
+        assert!(image.is_fallback());
+        assert_eq!(image.request().size(), &iiif::Size::Width(600));
+        assert_eq!(image.requested().size(), &iiif::Size::Width(1200));
+        assert_eq!(image.content_type(), "image/jpeg");
+        assert_eq!(image.dimensions().map(|(width, _)| width), Some(1690));
+        assert_eq!(image.artwork().data.id, 77333);
+    }
+
+    #[tokio::test]
+    async fn fetch_image_rejects_responses_that_are_not_images() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_raw("<html>Service unavailable</html>", "text/html"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().use_cache(false).build();
+
+        let error = api
+            .fetch_image(&artwork_at(&mock_server.uri()), &ImageOptions::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, AcresError::InvalidImage(_)), "{error}");
+    }
+
+    #[tokio::test]
+    async fn fetch_image_gives_up_on_missing_images() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(wiremock::ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().use_cache(false).build();
+
+        let error = api
+            .fetch_image(&artwork_at(&mock_server.uri()), &ImageOptions::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, AcresError::ImageRefused(404, _)), "{error}");
+    }
+
+    #[tokio::test]
+    async fn fetch_image_replays_like_a_live_request() {
+        let jpeg = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../acres-cli-ascii-art-77333.jpg"
+        ))
+        .unwrap();
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path(
+            "/iiif/2/abc/full/1200,/0/default.jpg",
+        ))
+        .respond_with(wiremock::ResponseTemplate::new(403))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+        wiremock::Mock::given(wiremock::matchers::path(
+            "/iiif/2/abc/full/843,/0/default.jpg",
+        ))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(jpeg, "image/pjpeg"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+        let artwork = artwork_at(&mock_server.uri());
+        let options = ImageOptions::new().size(iiif::Size::Width(1200));
+        let recorder = Api::builder()
+            .cassette(Some(Cassette::new(CassetteMode::Record, dir.path())))
+            .build();
+        let recorded = recorder.fetch_image(&artwork, &options).await.unwrap();
+        drop(mock_server);
+        let player = Api::builder()
+            .cassette(Some(Cassette::new(CassetteMode::Replay, dir.path())))
+            .build();
+
+        let replayed = player.fetch_image(&artwork, &options).await.unwrap();
+
+        assert_eq!(replayed.request().size(), &iiif::Size::Width(843));
+        assert_eq!(replayed.content_type(), "image/pjpeg");
+        assert_eq!(replayed.content_type(), recorded.content_type());
+    }
+
+    #[tokio::test]
+    async fn stream_returns_api_errors() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(wiremock::ResponseTemplate::new(404).set_body_json(
+                serde_json::json!({ "error": "Not found", "detail": "no such artwork" }),
+            ))
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().use_cache(false).build();
+
+        let error = api.stream(mock_server.uri()).await.unwrap_err();
+
+        assert!(error.to_string().contains("no such artwork"));
+    }
 }
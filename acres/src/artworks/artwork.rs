@@ -3,7 +3,35 @@ use std::fmt::Display;
 use iiif::IiifError;
 use serde::{Deserialize, Serialize};
 
-use crate::{AcresError, artworks::Artworks};
+use crate::{
+    AcresError,
+    artworks::{
+        Artworks,
+        collection::{Config, Data, Info, Thumbnail},
+    },
+    endpoint::json_response,
+};
+
+/// An artwork, as returned by [`GET /artworks/{id}`].
+///
+/// [`GET /artworks/{id}`]: https://api.artic.edu/docs/#get-artworks-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Artwork {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Data,
+    /// Info.
+    pub info: Info,
+}
+
+impl Display for Artwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} ({})", self.data.title, self.data.id))
+    }
+}
+
+json_response!(Artwork);
 
 /// Artwork config.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
@@ -21,6 +49,12 @@ pub struct ArtworkInfoData {
     pub image_id: String,
     /// Title.
     pub title: String,
+    /// Whether the artwork is in the public domain, if known.
+    #[serde(default)]
+    pub is_public_domain: Option<bool>,
+    /// Thumbnail details, including a placeholder to show while the image loads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<Thumbnail>,
 }
 
 // TODO: Finish out the implementation of this type and document.
@@ -47,14 +81,17 @@ impl From<Artworks> for Vec<ArtworkInfo> {
             .iter()
             // ArtworkInfos must have IIIF URIs, so they must have image IDs
             .filter_map(|data| {
+                let id = u32::try_from(data.id).ok()?;
                 data.image_id.clone().map(|image_id| ArtworkInfo {
                     config: ArtworkInfoConfig {
                         iiif_url: iiif_url.clone(),
                     },
                     data: ArtworkInfoData {
-                        id: data.id as u32,
+                        id,
                         image_id: image_id.clone(),
                         title: data.title.clone(),
+                        is_public_domain: data.is_public_domain,
+                        thumbnail: data.thumbnail.clone(),
                     },
                 })
             })
@@ -62,31 +99,79 @@ impl From<Artworks> for Vec<ArtworkInfo> {
     }
 }
 
+impl TryFrom<&Artwork> for ArtworkInfo {
+    type Error = AcresError;
+
+    fn try_from(artwork: &Artwork) -> std::result::Result<Self, Self::Error> {
+        let image_id = artwork.data.image_id.clone().ok_or_else(|| {
+            AcresError::Unexpected(anyhow::anyhow!("artwork {} has no image", artwork.data.id))
+        })?;
+        let id = u32::try_from(artwork.data.id).map_err(|_| {
+            AcresError::Unexpected(anyhow::anyhow!(
+                "artwork id {} is out of range",
+                artwork.data.id
+            ))
+        })?;
+        let iiif_url = url::Url::parse(&artwork.config.iiif_url)
+            .map_err(IiifError::InvalidUri)
+            .map_err(AcresError::Iiif)?;
+        Ok(ArtworkInfo {
+            config: ArtworkInfoConfig { iiif_url },
+            data: ArtworkInfoData {
+                id,
+                image_id,
+                title: artwork.data.title.clone(),
+                is_public_domain: artwork.data.is_public_domain,
+                thumbnail: artwork.data.thumbnail.clone(),
+            },
+        })
+    }
+}
+
 impl TryFrom<ArtworkInfo> for iiif::Uri {
     type Error = AcresError;
 
     fn try_from(artwork: ArtworkInfo) -> std::result::Result<Self, Self::Error> {
         tracing::debug!(artwork = ?artwork);
-        let url = if artwork.config.iiif_url.as_str().ends_with("/") {
-            artwork.config.iiif_url.clone()
-        } else {
-            let mut path = artwork.config.iiif_url.path().to_string();
-            path.push('/');
-            let mut url = artwork.config.iiif_url.clone();
-            url.set_path(path.as_str());
-            url
-        };
-        tracing::debug!(url = ?url);
-        let url = url
-            .join(&artwork.data.image_id)
+        iiif_uri(&artwork.config.iiif_url, &artwork.data.image_id)
+    }
+}
+
+impl TryFrom<&Artwork> for iiif::Uri {
+    type Error = AcresError;
+
+    fn try_from(artwork: &Artwork) -> std::result::Result<Self, Self::Error> {
+        tracing::debug!(artwork = ?artwork);
+        let image_id = artwork.data.image_id.as_ref().ok_or_else(|| {
+            AcresError::Unexpected(anyhow::anyhow!("artwork {} has no image", artwork.data.id))
+        })?;
+        let iiif_url = url::Url::parse(&artwork.config.iiif_url)
             .map_err(IiifError::InvalidUri)
             .map_err(AcresError::Iiif)?;
-        tracing::debug!(url = %url);
-        tracing::trace!(url = ?url);
-        url.as_str().parse::<iiif::Uri>().map_err(AcresError::Iiif)
+        iiif_uri(&iiif_url, image_id)
     }
 }
 
+pub(crate) fn iiif_uri(iiif_url: &url::Url, image_id: &str) -> Result<iiif::Uri, AcresError> {
+    let url = if iiif_url.as_str().ends_with("/") {
+        iiif_url.clone()
+    } else {
+        let mut path = iiif_url.path().to_string();
+        path.push('/');
+        let mut url = iiif_url.clone();
+        url.set_path(path.as_str());
+        url
+    };
+    tracing::debug!(url = ?url);
+    let url = url
+        .join(image_id)
+        .map_err(IiifError::InvalidUri)
+        .map_err(AcresError::Iiif)?;
+    tracing::debug!(url = %url);
+    tracing::trace!(url = ?url);
+    url.as_str().parse::<iiif::Uri>().map_err(AcresError::Iiif)
+}
+
 impl ArtworkInfo {
     /// Load from reader.
     pub fn load<R: std::io::Read>(reader: R) -> Option<Self> {
@@ -121,3 +206,25 @@ impl Request {
         Self { base_uri, id }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::fixtures::envelope;
+
+    #[test]
+    fn out_of_range_ids_are_errors() {
+        let artwork: Artwork = serde_json::from_value(envelope(json!({
+            "id": u64::from(u32::MAX) + 1,
+            "title": "Too far",
+            "image_id": "25c31d8d-21a4-9ea1-1d73-6a2eca4dda7e"
+        })))
+        .unwrap();
+
+        let error = ArtworkInfo::try_from(&artwork).unwrap_err();
+
+        assert!(error.to_string().contains("out of range"));
+    }
+}
@@ -3,10 +3,16 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+use base64::Engine;
+use bytes::Bytes;
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Serialize};
 
-use crate::AcresError;
+use crate::{
+    AcresError,
+    endpoint::json_response,
+    history::{self, Exhibition, Ownership, Publication, Timeline},
+};
 
 // TODO: Finish out the implementation of this type and document.
 #[doc(hidden)]
@@ -30,19 +36,206 @@ impl FromStr for Artworks {
     }
 }
 
+json_response!(Artworks);
+
+/// Response config.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Config {
+    /// IIIF URL.
     pub iiif_url: String,
     website_url: String,
 }
 
+impl Config {
+    /// Returns the public website URL, e.g. `https://www.artic.edu`.
+    pub fn website_url(&self) -> &str {
+        &self.website_url
+    }
+
+    /// Returns the URL of an artwork's page on the public website.
+    pub fn artwork_url(&self, id: u64) -> String {
+        format!("{}/artworks/{id}", self.website())
+    }
+
+    /// Returns the URL of an artist's page on the public website.
+    pub fn artist_url(&self, id: u64) -> String {
+        format!("{}/artists/{id}", self.website())
+    }
+
+    fn website(&self) -> &str {
+        match self.website_url.trim_end_matches('/') {
+            "" => "https://www.artic.edu",
+            url => url,
+        }
+    }
+}
+
+/// Artwork record.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Data {
+    /// ID.
     pub id: u64,
+    /// Image ID.
+    #[serde(default)]
     pub image_id: Option<String>,
+    /// Title.
+    #[serde(default)]
     pub title: String,
+    /// Artist, as it's displayed on the label (name on the first line, then nationality and
+    /// dates).
+    #[serde(default)]
+    pub artist_display: Option<String>,
+    /// Date, as it's displayed on the label.
+    #[serde(default)]
+    pub date_display: Option<String>,
+    /// Earliest year the artwork may have been made.
+    #[serde(default)]
+    pub date_start: Option<i64>,
+    /// Latest year the artwork may have been made.
+    #[serde(default)]
+    pub date_end: Option<i64>,
+    /// Medium, as it's displayed on the label.
+    #[serde(default)]
+    pub medium_display: Option<String>,
+    /// How the artwork came into the collection.
+    #[serde(default)]
+    pub credit_line: Option<String>,
+    /// Preferred artist id.
+    #[serde(default)]
+    pub artist_id: Option<u64>,
+    /// Preferred artist name.
+    #[serde(default)]
+    pub artist_title: Option<String>,
+    /// Accession number.
+    #[serde(default)]
+    pub main_reference_number: Option<String>,
+    /// Dimensions, as they're displayed on the label.
+    #[serde(default)]
+    pub dimensions: Option<String>,
+    /// Classification (e.g., "painting").
+    #[serde(default)]
+    pub classification_title: Option<String>,
+    /// Department id (e.g., "PC-10").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department_id: Option<String>,
+    /// Artwork type id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artwork_type_id: Option<u64>,
+    /// Preferred style id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_id: Option<String>,
+    /// Style ids.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub style_ids: Vec<String>,
+    /// Preferred classification id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification_id: Option<String>,
+    /// Classification ids.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub classification_ids: Vec<String>,
+    /// Subject ids.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subject_ids: Vec<String>,
+    /// Material ids.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub material_ids: Vec<String>,
+    /// Technique ids.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub technique_ids: Vec<String>,
+    /// Thumbnail details for the preferred image.
+    #[serde(default)]
+    pub thumbnail: Option<Thumbnail>,
+    /// Whether the artwork is in the public domain.
+    #[serde(default)]
+    pub is_public_domain: Option<bool>,
+    /// Copyright notice for works that are not in the public domain.
+    #[serde(default)]
+    pub copyright_notice: Option<String>,
+    /// Ownership history, as written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance_text: Option<String>,
+    /// Exhibitions the artwork was shown in, as written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exhibition_history: Option<String>,
+    /// Publications the artwork appears in, as written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_history: Option<String>,
+}
+
+impl Data {
+    /// Returns who owned the artwork and how it changed hands, from `provenance_text`.
+    pub fn provenance(&self) -> Timeline<Ownership> {
+        history::provenance(self.provenance_text.as_deref().unwrap_or_default())
+    }
+
+    /// Returns the exhibitions the artwork was shown in, from `exhibition_history`.
+    pub fn exhibitions(&self) -> Timeline<Exhibition> {
+        history::exhibitions(self.exhibition_history.as_deref().unwrap_or_default())
+    }
+
+    /// Returns the publications the artwork appears in, from `publication_history`.
+    pub fn publications(&self) -> Timeline<Publication> {
+        history::publications(self.publication_history.as_deref().unwrap_or_default())
+    }
+}
+
+/// Thumbnail details for an artwork's preferred image.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Thumbnail {
+    /// Low-quality image placeholder, as a base64 data URL.
+    #[serde(default)]
+    pub lqip: Option<String>,
+    /// Width of the full image, in pixels.
+    #[serde(default)]
+    pub width: Option<u32>,
+    /// Height of the full image, in pixels.
+    #[serde(default)]
+    pub height: Option<u32>,
+    /// Text description of the image.
+    #[serde(default)]
+    pub alt_text: Option<String>,
+}
+
+impl Thumbnail {
+    /// Decodes the low-quality image placeholder, if there is one.
+    ///
+    /// The placeholder is a tiny image, usually a GIF a few pixels across, with roughly the
+    /// colors and proportions of the full image. It's embedded in the response, so you can show it
+    /// straight away while the full image is still downloading.
+    ///
+    /// ```rust
+    /// # use anyhow::Result;
+    /// use acres::artworks::Thumbnail;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let thumbnail = Thumbnail {
+    ///     lqip: Some("data:image/gif;base64,R0lGODlhAQABAIAAAP///wAAACH5BAEAAAAALAAAAAABAAEAAAICRAEAOw==".into()),
+    ///     ..Default::default()
+    /// };
+    /// let gif = thumbnail.placeholder()?.expect("has a placeholder");
+    /// assert!(gif.starts_with(b"GIF89a"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn placeholder(&self) -> Result<Option<Bytes>, AcresError> {
+        let Some(lqip) = self.lqip.as_deref().filter(|lqip| !lqip.is_empty()) else {
+            return Ok(None);
+        };
+        let data = lqip
+            .strip_prefix("data:")
+            .and_then(|url| url.split_once(','))
+            .and_then(|(header, data)| header.ends_with(";base64").then_some(data))
+            .ok_or_else(|| {
+                AcresError::InvalidImage("placeholder isn't a base64 data URL".into())
+            })?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data.trim())
+            .map_err(|e| AcresError::InvalidImage(format!("placeholder isn't base64: {e}")))?;
+        Ok(Some(bytes.into()))
+    }
 }
 
+/// Response info.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Info {
     license_text: String,
@@ -50,13 +243,36 @@ pub struct Info {
     version: String,
 }
 
+impl Info {
+    /// Returns the license covering the response data.
+    pub fn license_text(&self) -> &str {
+        &self.license_text
+    }
+
+    /// Returns links to the licenses and terms covering the response data.
+    pub fn license_links(&self) -> &[String] {
+        &self.license_links
+    }
+
+    /// Returns the API version that produced the response.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+/// Response pagination.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Pagination {
-    total: u64,
-    limit: u64,
-    offset: u64,
-    total_pages: u64,
-    current_page: u64,
+    /// Total number of records.
+    pub total: u64,
+    /// Records per page.
+    pub limit: u64,
+    /// Offset of the first record on this page.
+    pub offset: u64,
+    /// Total number of pages.
+    pub total_pages: u64,
+    /// This page.
+    pub current_page: u64,
 }
 
 /// A [`GET /artworks`] request.
@@ -253,12 +469,12 @@ impl Builder {
 }
 
 #[derive(Debug)]
-pub(super) struct CollectionQueryParams {
-    pub(super) ids: Option<Vec<u32>>,
-    pub(super) limit: Option<u32>,
-    pub(super) page: Option<u32>,
-    pub(super) fields: Vec<String>,
-    pub(super) include: Vec<String>,
+pub(crate) struct CollectionQueryParams {
+    pub(crate) ids: Option<Vec<u32>>,
+    pub(crate) limit: Option<u32>,
+    pub(crate) page: Option<u32>,
+    pub(crate) fields: Vec<String>,
+    pub(crate) include: Vec<String>,
 }
 
 impl Display for CollectionQueryParams {
@@ -392,6 +608,35 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn thumbnail_placeholders_decode() {
+        let mut gif = std::io::Cursor::new(vec![]);
+        image::DynamicImage::new_rgb8(5, 4)
+            .write_to(&mut gif, image::ImageOutputFormat::Gif)
+            .unwrap();
+        let gif = gif.into_inner();
+        let thumbnail = |lqip: &str| Thumbnail {
+            lqip: Some(lqip.into()),
+            ..Default::default()
+        };
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&gif);
+
+        let placeholder = thumbnail(&format!("data:image/gif;base64,{encoded}"))
+            .placeholder()
+            .unwrap()
+            .unwrap();
+        assert_eq!(placeholder.as_ref(), gif.as_slice());
+        assert_eq!(image::load_from_memory(&placeholder).unwrap().width(), 5);
+
+        assert!(Thumbnail::default().placeholder().unwrap().is_none());
+        assert!(thumbnail(&encoded).placeholder().is_err());
+        assert!(
+            thumbnail("data:image/gif;base64,!!!")
+                .placeholder()
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn api_artworks_collection_with_include() {
         let base_uri = String::from("https://example.org/api/v1");
@@ -1,8 +1,9 @@
 use std::fmt::Display;
 
-use bytes::{Buf, Bytes};
 use serde::{Deserialize, Serialize};
 
+use crate::endpoint::json_response;
+
 // TODO: Finish out the implementation of this type and document.
 #[doc(hidden)]
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
@@ -15,12 +16,7 @@ impl Display for Manifest {
     }
 }
 
-impl From<Bytes> for Manifest {
-    fn from(value: Bytes) -> Self {
-        let reader = value.reader();
-        serde_json::from_reader(reader).unwrap()
-    }
-}
+json_response!(Manifest);
 
 impl Manifest {
     #[doc(hidden)]
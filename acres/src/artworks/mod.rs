@@ -7,10 +7,13 @@ mod collection;
 mod manifest;
 mod search;
 
-pub use artwork::ArtworkInfo;
-pub use collection::Artworks;
+pub(crate) use artwork::iiif_uri;
+pub use artwork::{Artwork, ArtworkInfo};
+pub(crate) use collection::CollectionQueryParams;
+pub use collection::{Artworks, Config, Data, Info, Pagination, Thumbnail};
 pub use manifest::Manifest;
 pub use search::Search;
+pub(crate) use search::SearchQueryParams;
 
 /// Modules for requesting items from the [Artworks Collection].
 ///
@@ -3,16 +3,15 @@
 use std::fmt::Display;
 
 use anyhow::Context;
-use bytes::{Buf, Bytes};
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Serialize};
 
-use crate::AcresError;
+use crate::{AcresError, color, dating::Span, endpoint::json_response};
 
 // TODO: Finish out the implementation of this type and document.
 #[doc(hidden)]
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
-pub struct Search(serde_json::Value);
+pub struct Search(pub(crate) serde_json::Value);
 
 impl Display for Search {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -21,12 +20,7 @@ impl Display for Search {
     }
 }
 
-impl From<Bytes> for Search {
-    fn from(value: Bytes) -> Self {
-        let reader = value.reader();
-        serde_json::from_reader(reader).unwrap()
-    }
-}
+json_response!(Search);
 
 impl TryFrom<Vec<u8>> for Search {
     type Error = AcresError;
@@ -54,8 +48,31 @@ impl Search {
     pub fn builder() -> Builder {
         Builder::default()
     }
+
+    /// Returns the matching records.
+    pub fn data(&self) -> &[serde_json::Value] {
+        self.0["data"].as_array().map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the matching records, for reordering or editing in place.
+    pub fn data_mut(&mut self) -> &mut [serde_json::Value] {
+        self.0["data"]
+            .as_array_mut()
+            .map_or(&mut [], Vec::as_mut_slice)
+    }
 }
 
+/// Fields the API returns for each result when none are asked for.
+const DEFAULT_FIELDS: [&str; 7] = [
+    "id",
+    "api_model",
+    "api_link",
+    "is_boosted",
+    "title",
+    "thumbnail",
+    "timestamp",
+];
+
 /// A [`GET /artworks/search`] request.
 ///
 /// ```rust
@@ -118,6 +135,9 @@ pub struct Builder {
     from: Option<u32>,
     size: Option<u32>,
     facets: Option<Vec<String>>,
+    fields: Vec<String>,
+    dated: Option<Span>,
+    color: Option<color::Query>,
 }
 
 impl Builder {
@@ -195,43 +215,142 @@ impl Builder {
         self
     }
 
+    /// Sets the artwork fields to retrieve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acres::artworks::request::search::Builder;
+    ///
+    /// Builder::new().fields(Some(vec!["id".into(), "timestamp".into()]));
+    /// ```
+    pub fn fields(mut self, fields: Option<Vec<String>>) -> Self {
+        tracing::info!(msg = "Setting fields", ?fields);
+        if let Some(fields) = fields {
+            self.fields = fields;
+        }
+        self
+    }
+
+    /// Limits results to artworks dated within a span of years.
+    ///
+    /// This is combined with any [`query`](Self::query) so that both must match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use acres::artworks::request::search::Builder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// Builder::new().dated(Some("19th century".parse()?));
+    /// Builder::new().dated(Some("1870 to 1890".parse()?));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dated(mut self, dated: Option<Span>) -> Self {
+        tracing::info!(msg = "Setting dated", ?dated);
+        self.dated = dated;
+        self
+    }
+
+    /// Limits results to artworks whose dominant color is close to a color.
+    ///
+    /// This is combined with any [`query`](Self::query) so that both must match. The `color`
+    /// field is added to the [`fields`](Self::fields) retrieved, so that the results can be
+    /// [ranked](crate::color::Query::rank).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use acres::{artworks::request::search::Builder, color};
+    ///
+    /// # fn main() -> Result<()> {
+    /// Builder::new().color(Some(color::Query::new("#336699".parse()?)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn color(mut self, color: Option<color::Query>) -> Self {
+        tracing::info!(msg = "Setting color", ?color);
+        self.color = color;
+        self
+    }
+
     /// Builds artworks search.
     pub fn build(&self) -> Result<Request, AcresError> {
         let query_params = SearchQueryParams {
             q: self.q.clone(),
-            query: self.query.clone(),
+            query: self.combined_query()?,
             sort: self.sort.clone(),
             from: self.from,
             size: self.size,
             facets: self.facets.clone(),
+            fields: self.request_fields(),
         };
         query_params.valid()?;
         let request = format!("{}/artworks/search{}", self.base_uri, query_params);
         Ok(Request(request))
     }
+
+    fn request_fields(&self) -> Vec<String> {
+        let mut fields = self.fields.clone();
+        if self.color.is_some() && !fields.iter().any(|f| f == "color") {
+            if fields.is_empty() {
+                fields = DEFAULT_FIELDS.iter().map(|f| f.to_string()).collect();
+            }
+            fields.push("color".to_string());
+        }
+        fields
+    }
+
+    fn combined_query(&self) -> Result<Option<String>, AcresError> {
+        let mut clauses = vec![];
+        if let Some(dated) = &self.dated {
+            clauses.push(dated.query());
+        }
+        if let Some(color) = &self.color {
+            clauses.push(color.query());
+        }
+        if clauses.is_empty() {
+            return Ok(self.query.clone());
+        }
+        if let Some(query) = &self.query {
+            let query: serde_json::Value = serde_json::from_str(query).map_err(|e| {
+                AcresError::InvalidSearchQueryParams(format!("query is not valid JSON: {e}"))
+            })?;
+            clauses.insert(0, query);
+        }
+        let query = match clauses.len() {
+            1 => clauses.remove(0),
+            _ => serde_json::json!({ "bool": { "must": clauses } }),
+        };
+        Ok(Some(query.to_string()))
+    }
 }
 
 #[derive(Debug)]
-pub(super) struct SearchQueryParams {
-    pub(super) q: Option<String>,
-    pub(super) query: Option<String>,
-    pub(super) sort: Option<String>,
-    pub(super) from: Option<u32>,
-    pub(super) size: Option<u32>,
-    pub(super) facets: Option<Vec<String>>,
+pub(crate) struct SearchQueryParams {
+    pub(crate) q: Option<String>,
+    pub(crate) query: Option<String>,
+    pub(crate) sort: Option<String>,
+    pub(crate) from: Option<u32>,
+    pub(crate) size: Option<u32>,
+    pub(crate) facets: Option<Vec<String>>,
+    pub(crate) fields: Vec<String>,
 }
 
 impl Display for SearchQueryParams {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut params: Vec<String> = vec![];
         if let Some(q) = &self.q {
-            params.push(format!("q={q}"));
+            params.push(format!("q={}", encode(q)));
         }
         if let Some(query) = &self.query {
-            params.push(format!("query={query}"));
+            params.push(format!("query={}", encode(query)));
         }
         if let Some(sort) = &self.sort {
-            params.push(format!("sort={sort}"));
+            params.push(format!("sort={}", encode(sort)));
         }
         if let Some(from) = &self.from {
             params.push(format!("from={from}"));
@@ -242,8 +361,10 @@ impl Display for SearchQueryParams {
         if let Some(facets) = &self.facets
             && !facets.is_empty()
         {
-            let facets = facets.join(",");
-            params.push(format!("facets={facets}"));
+            params.push(format!("facets={}", encode_list(facets)));
+        }
+        if !self.fields.is_empty() {
+            params.push(format!("fields={}", encode_list(&self.fields)));
         }
         if params.is_empty() {
             Ok(())
@@ -253,6 +374,20 @@ impl Display for SearchQueryParams {
     }
 }
 
+/// Percent-encodes a query parameter value, so text like "Mother & Child" stays in one piece.
+fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Percent-encodes each item of a comma-separated parameter value.
+fn encode_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| encode(value))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 impl Serialize for SearchQueryParams {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -277,6 +412,9 @@ impl Serialize for SearchQueryParams {
         if let Some(facets) = &self.facets {
             seq.serialize_element(&("facets", facets.join(",")))?
         }
+        if !self.fields.is_empty() {
+            seq.serialize_element(&("fields", self.fields.join(",")))?;
+        }
         seq.end()
     }
 }
@@ -297,6 +435,15 @@ mod tests {
     use super::*;
     use crate::AcresError;
 
+    /// Returns a decoded query parameter of a request.
+    fn param(request: &impl Display, name: &str) -> String {
+        let url = url::Url::parse(&request.to_string()).unwrap();
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .unwrap_or_default()
+    }
+
     #[test]
     fn sort_requires_query() {
         let params = SearchQueryParams {
@@ -306,6 +453,7 @@ mod tests {
             from: None,
             size: None,
             facets: None,
+            fields: vec![],
         };
 
         let result = params.valid();
@@ -315,4 +463,69 @@ mod tests {
             Err(AcresError::InvalidSearchQueryParams(_))
         ));
     }
+
+    #[test]
+    fn dated_is_combined_with_query() {
+        let request = Builder::new()
+            .base_uri("https://example.org/api/v1".into())
+            .query(Some(r#"{"term":{"is_public_domain":true}}"#.into()))
+            .dated(Some(Span::new(1870, 1890)))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            param(&request, "query"),
+            "{\"bool\":{\"must\":[{\"term\":{\"is_public_domain\":true}},\
+             {\"bool\":{\"must\":[{\"range\":{\"date_start\":{\"lte\":1890}}},\
+             {\"range\":{\"date_end\":{\"gte\":1870}}}]}}]}}"
+        );
+    }
+
+    #[test]
+    fn color_adds_color_field() {
+        let color = color::Query::new(color::Hsl::new(210.0, 50.0, 40.0));
+
+        let request = Builder::new()
+            .base_uri("https://example.org/api/v1".into())
+            .fields(Some(vec!["id".into()]))
+            .color(Some(color))
+            .build()
+            .unwrap();
+
+        assert!(request.to_string().ends_with("&fields=id,color"));
+        assert!(param(&request, "query").contains(r#""color.h":{"gte":195.0,"lte":225.0}"#));
+    }
+
+    #[test]
+    fn free_text_is_percent_encoded() {
+        let request = Builder::new()
+            .base_uri("https://example.org/api/v1".into())
+            .q(Some("Mother & Child #2 + 1".into()))
+            .build()
+            .unwrap();
+
+        assert_eq!(param(&request, "q"), "Mother & Child #2 + 1");
+        assert!(
+            request
+                .to_string()
+                .ends_with("?q=Mother+%26+Child+%232+%2B+1")
+        );
+    }
+
+    #[test]
+    fn api_artworks_search_with_fields() {
+        let base_uri = String::from("https://example.org/api/v1");
+
+        let request = Builder::new()
+            .base_uri(base_uri.clone())
+            .q(Some("monet".into()))
+            .fields(Some(vec!["id".into(), "timestamp".into()]))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.to_string(),
+            format!("{base_uri}/artworks/search?q=monet&fields=id,timestamp")
+        );
+    }
 }
@@ -0,0 +1,155 @@
+//! Safe writes to the cache and other shared directories.
+//!
+//! The CLI, the TUI and background jobs can all share a cache directory, so a file must never be
+//! seen half-written. Files are written to a temporary file next to their final path and then
+//! renamed into place, which is atomic on the same filesystem. Updates that span several files,
+//! like a store's records and cursor, take an advisory [`DirLock`] first.
+//!
+//! Entries that are corrupt anyway, say from a crash in an older version, are moved aside into a
+//! `quarantine` directory when they're found rather than failing every read after.
+
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, anyhow};
+
+use crate::AcresError;
+
+/// Name of the lock file taken by [`DirLock`].
+const LOCK_FILE: &str = ".lock";
+
+/// Name of the directory corrupt entries are moved into.
+const QUARANTINE_DIR: &str = "quarantine";
+
+/// Tells apart temporary files made by the same process.
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a temporary path next to `path` that no other writer will use.
+pub(crate) fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let count = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{name}.{}.{count}.tmp", std::process::id()))
+}
+
+/// Writes a file so that readers see either the old contents or the new, never a mix.
+pub(crate) fn write_atomic(path: &Path, data: &[u8]) -> Result<(), AcresError> {
+    let temp = temp_path(path);
+    std::fs::write(&temp, data).with_context(|| format!("writing {}", temp.display()))?;
+    if let Err(error) = std::fs::rename(&temp, path) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(anyhow!(error)
+            .context(format!("moving {} into place", path.display()))
+            .into());
+    }
+    Ok(())
+}
+
+/// Moves a corrupt entry aside so that it's fetched or rebuilt next time.
+///
+/// Returns where it went.
+pub(crate) fn quarantine(path: &Path) -> Result<PathBuf, AcresError> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| anyhow!("{} has no parent directory", path.display()))?
+        .join(QUARANTINE_DIR);
+    std::fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let destination = dir.join(format!("{name}.{stamp}"));
+    std::fs::rename(path, &destination)
+        .with_context(|| format!("quarantining {}", path.display()))?;
+    tracing::warn!(
+        "Quarantined corrupt entry '{}' at '{}'",
+        path.display(),
+        destination.display()
+    );
+    Ok(destination)
+}
+
+/// An advisory lock on a directory, held until dropped.
+///
+/// Only other `DirLock`s wait on it; plain readers don't, which is fine since every file is
+/// written atomically.
+#[derive(Debug)]
+pub(crate) struct DirLock {
+    file: File,
+}
+
+impl DirLock {
+    /// Waits for and takes the lock on `dir`.
+    pub(crate) fn exclusive(dir: &Path) -> Result<Self, AcresError> {
+        let path = dir.join(LOCK_FILE);
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("opening {}", path.display()))?;
+        file.lock()
+            .with_context(|| format!("locking {}", dir.display()))?;
+        Ok(DirLock { file })
+    }
+}
+
+impl Drop for DirLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::TryLockError;
+
+    use super::*;
+
+    #[test]
+    fn write_atomic_replaces_without_leftovers() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("entry");
+
+        write_atomic(&path, b"old").unwrap();
+        write_atomic(&path, b"new").unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_ne!(temp_path(&path), temp_path(&path));
+    }
+
+    #[test]
+    fn quarantine_moves_entries_aside() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("entry");
+        std::fs::write(&path, b"{\"data\": [").unwrap();
+
+        let destination = quarantine(&path).unwrap();
+
+        assert!(!path.exists());
+        assert!(destination.starts_with(dir.path().join(QUARANTINE_DIR)));
+        assert_eq!(std::fs::read(destination).unwrap(), b"{\"data\": [");
+    }
+
+    #[test]
+    fn dir_lock_excludes_other_lockers() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let other = || File::open(dir.path().join(LOCK_FILE)).unwrap();
+
+        let lock = DirLock::exclusive(dir.path()).unwrap();
+        assert!(matches!(other().try_lock(), Err(TryLockError::WouldBlock)));
+
+        drop(lock);
+        assert!(other().try_lock().is_ok());
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::Context;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AcresError,
+    api::{Http, RawResponse, send_raw},
+    cache,
+};
+
+/// Whether a [`Cassette`] records new interactions or replays old ones.
+///
+/// You can create one from a string.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::CassetteMode;
+///
+/// # fn main() -> Result<()> {
+/// let mode: CassetteMode = "record".parse()?;
+/// assert_eq!(mode, CassetteMode::Record);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Cassette`]: struct.Cassette.html
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CassetteMode {
+    /// Send requests over the network and write every interaction to disk.
+    Record,
+    /// Serve responses from disk and never touch the network.
+    #[default]
+    Replay,
+}
+
+impl Display for CassetteMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CassetteMode::Record => f.write_str("record"),
+            CassetteMode::Replay => f.write_str("replay"),
+        }
+    }
+}
+
+impl FromStr for CassetteMode {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "record" => Ok(CassetteMode::Record),
+            "replay" => Ok(CassetteMode::Replay),
+            _ => Err(AcresError::Unexpected(anyhow::anyhow!(
+                "{} is not a supported cassette mode",
+                s
+            ))),
+        }
+    }
+}
+
+/// A directory of recorded HTTP interactions.
+///
+/// Each interaction is stored as a JSON file with the request (method and URL) and response
+/// (status and headers), next to a file with the raw response body. Files are named after a
+/// hash of the method and URL, so the same request always maps to the same recording.
+///
+/// Set one on the API client with [`ApiBuilder::cassette()`], or with the `ACRES_CASSETTE_DIR`
+/// and `ACRES_CASSETTE_MODE` environment variables.
+///
+/// ```rust
+/// use acres::{Api, Cassette, CassetteMode};
+///
+/// let api = Api::builder()
+///     .cassette(Some(Cassette::new(CassetteMode::Record, "tests/cassettes")))
+///     .build();
+/// ```
+///
+/// [`ApiBuilder::cassette()`]: struct.ApiBuilder.html#method.cassette
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cassette {
+    mode: CassetteMode,
+    dir: PathBuf,
+}
+
+/// A recorded interaction, minus the body.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    url: String,
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+impl Cassette {
+    /// Creates a new cassette backed by `dir`.
+    pub fn new(mode: CassetteMode, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mode,
+            dir: dir.into(),
+        }
+    }
+
+    /// Returns the mode.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Returns the directory interactions are kept in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Records or replays a request, depending on the mode.
+    pub(crate) async fn play(
+        &self,
+        http: &Http,
+        method: reqwest::Method,
+        endpoint: &String,
+    ) -> Result<Bytes, AcresError> {
+        self.play_raw(http, method.clone(), endpoint)
+            .await?
+            .into_body(&method, endpoint)
+    }
+
+    /// Records or replays a request, keeping the status and headers of the response.
+    pub(crate) async fn play_raw(
+        &self,
+        http: &Http,
+        method: reqwest::Method,
+        endpoint: &String,
+    ) -> Result<RawResponse, AcresError> {
+        Ok(match self.mode {
+            CassetteMode::Record => {
+                let response = send_raw(http, method.clone(), endpoint).await?;
+                self.record(&method, endpoint, &response)?;
+                response
+            }
+            CassetteMode::Replay => self
+                .replay(&method, endpoint)?
+                .ok_or_else(|| AcresError::MissingInteraction(format!("{method} {endpoint}")))?,
+        })
+    }
+
+    fn key(method: &reqwest::Method, endpoint: &str) -> String {
+        format!(
+            "{:016x}",
+            xxhash_rust::xxh3::xxh3_64(format!("{} {}", method, endpoint).as_bytes())
+        )
+    }
+
+    fn record(
+        &self,
+        method: &reqwest::Method,
+        endpoint: &str,
+        response: &RawResponse,
+    ) -> Result<(), AcresError> {
+        let key = Cassette::key(method, endpoint);
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("creating cassette dir {}", self.dir.display()))?;
+        let interaction = Interaction {
+            method: method.to_string(),
+            url: endpoint.to_string(),
+            status: response.status,
+            headers: response.headers.clone(),
+            body: format!("{key}.body"),
+        };
+        cache::write_atomic(&self.dir.join(&interaction.body), &response.body)?;
+        let json = serde_json::to_vec_pretty(&interaction)
+            .with_context(|| "serializing recorded interaction")?;
+        cache::write_atomic(&self.dir.join(format!("{key}.json")), &json)?;
+        tracing::info!("Recorded '{} {}' as '{}'", method, endpoint, key);
+        Ok(())
+    }
+
+    fn replay(
+        &self,
+        method: &reqwest::Method,
+        endpoint: &str,
+    ) -> Result<Option<RawResponse>, AcresError> {
+        let key = Cassette::key(method, endpoint);
+        let path = self.dir.join(format!("{key}.json"));
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let json = std::fs::read(&path)
+            .with_context(|| format!("reading recorded interaction {}", path.display()))?;
+        let interaction: Interaction = serde_json::from_slice(&json)
+            .with_context(|| format!("parsing recorded interaction {}", path.display()))?;
+        let body = std::fs::read(self.dir.join(&interaction.body))
+            .with_context(|| format!("reading recorded body {}", interaction.body))?;
+        tracing::info!("Replayed '{} {}' from '{}'", method, endpoint, key);
+        Ok(Some(RawResponse {
+            status: interaction.status,
+            headers: interaction.headers,
+            body: body.into(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Api, Cached};
+
+    #[tokio::test]
+    async fn replays_what_was_recorded() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        let base_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/4"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(json!({"data": {"id": 4}})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let endpoint = format!("{base_uri}/artworks/4");
+
+        let recorder = Api::builder()
+            .base_uri(&base_uri)
+            .cassette(Some(Cassette::new(CassetteMode::Record, dir.path())))
+            .build();
+        let recorded: Cached = recorder.fetch(endpoint.clone()).await.unwrap();
+        drop(mock_server);
+        let player = Api::builder()
+            .base_uri(&base_uri)
+            .cassette(Some(Cassette::new(CassetteMode::Replay, dir.path())))
+            .build();
+        let replayed: Cached = player.fetch(endpoint).await.unwrap();
+
+        assert_eq!(recorded, replayed);
+    }
+
+    #[tokio::test]
+    async fn replays_errors_too() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(
+                wiremock::ResponseTemplate::new(404)
+                    .set_body_json(json!({"status": 404, "error": "Not found", "detail": "Gone"})),
+            )
+            .mount(&mock_server)
+            .await;
+        let endpoint = format!("{}/api/v1/artworks/0", mock_server.uri());
+        let cassette = Cassette::new(CassetteMode::Record, dir.path());
+        let _ = cassette
+            .play(&Http::default(), reqwest::Method::GET, &endpoint)
+            .await;
+
+        let cassette = Cassette::new(CassetteMode::Replay, dir.path());
+        let error = cassette
+            .play(&Http::default(), reqwest::Method::GET, &endpoint)
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("Not found"));
+    }
+
+    #[tokio::test]
+    async fn missing_interaction_is_an_error() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cassette = Cassette::new(CassetteMode::Replay, dir.path());
+
+        let result = cassette
+            .play(
+                &Http::default(),
+                reqwest::Method::GET,
+                &"https://api.artic.edu/api/v1/artworks/4".to_string(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AcresError::MissingInteraction(_))));
+    }
+}
@@ -0,0 +1,509 @@
+//! Citations for artworks.
+//!
+//! A [`Citation`] turns an [`Artwork`] into a reference in one of the common [`Style`]s, drawing
+//! on the artist, title, date, medium and credit line from the label, the artwork's page on the
+//! public website and the date it was accessed.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use acres::{
+//!     artworks::Artwork,
+//!     citation::{Citation, Style},
+//! };
+//! use chrono::NaiveDate;
+//!
+//! # fn main() -> Result<()> {
+//! let artwork: Artwork = serde_json::from_str(r#"{
+//!     "data": {
+//!         "id": 4,
+//!         "title": "Priest and Boy",
+//!         "artist_display": "Lawrence Carmichael Earle\nAmerican, 1845-1921",
+//!         "date_display": "n.d.",
+//!         "medium_display": "Watercolor over graphite on cream wove paper",
+//!         "credit_line": "Gift of William Hibbard"
+//!     },
+//!     "info": { "license_text": "", "license_links": [], "version": "1.13" },
+//!     "config": { "iiif_url": "https://www.artic.edu/iiif/2", "website_url": "https://www.artic.edu" }
+//! }"#)?;
+//!
+//! let citation = Citation::new(&artwork).accessed(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap());
+//! assert_eq!(
+//!     citation.format(Style::Mla),
+//!     "Lawrence Carmichael Earle. Priest and Boy. n.d., Watercolor over graphite on cream wove \
+//!      paper. Art Institute of Chicago, www.artic.edu/artworks/4. Accessed 1 May 2024."
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Artist names are used as the museum displays them. They aren't inverted (e.g., "Monet,
+//! Claude"), since that can't be done reliably for workshops, anonymous makers or names that put
+//! the family name first.
+//!
+//! [`Artwork`]: ../artworks/struct.Artwork.html
+
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::anyhow;
+use chrono::{Datelike, NaiveDate, Utc};
+use serde_json::{Value, json};
+
+use crate::{AcresError, artworks::Artwork};
+
+const INSTITUTION: &str = "Art Institute of Chicago";
+
+/// A citation style.
+///
+/// You can create one from a string.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::citation::Style;
+///
+/// # fn main() -> Result<()> {
+/// let style: Style = "bibtex".parse()?;
+/// assert_eq!(style, Style::Bibtex);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Style {
+    /// The Chicago Manual of Style (17th edition), bibliography entry.
+    #[default]
+    Chicago,
+    /// MLA Handbook (9th edition), works-cited entry.
+    Mla,
+    /// APA Publication Manual (7th edition), reference list entry.
+    Apa,
+    /// A BibTeX `@misc` entry.
+    Bibtex,
+    /// A CSL-JSON item, as used by Zotero, Pandoc and friends.
+    CslJson,
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Style::Chicago => f.write_str("chicago"),
+            Style::Mla => f.write_str("mla"),
+            Style::Apa => f.write_str("apa"),
+            Style::Bibtex => f.write_str("bibtex"),
+            Style::CslJson => f.write_str("csl-json"),
+        }
+    }
+}
+
+impl FromStr for Style {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "chicago" => Ok(Style::Chicago),
+            "mla" => Ok(Style::Mla),
+            "apa" => Ok(Style::Apa),
+            "bibtex" => Ok(Style::Bibtex),
+            "csl-json" => Ok(Style::CslJson),
+            _ => Err(AcresError::Unexpected(anyhow!(
+                "{} is not a supported citation style",
+                s
+            ))),
+        }
+    }
+}
+
+/// How titles are set off in prose styles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Markup {
+    /// Plain text, with no italics.
+    #[default]
+    Plain,
+    /// Markdown, with titles in `*italics*`.
+    Markdown,
+    /// HTML, with titles in `<i>italics</i>` and everything escaped.
+    Html,
+}
+
+/// A citation for an artwork.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Citation {
+    id: u64,
+    artist: Option<String>,
+    title: String,
+    date: String,
+    year: Option<i64>,
+    medium: Option<String>,
+    credit_line: Option<String>,
+    url: String,
+    accessed: NaiveDate,
+    markup: Markup,
+}
+
+impl Citation {
+    /// Creates a citation for `artwork`, accessed today.
+    pub fn new(artwork: &Artwork) -> Self {
+        let data = &artwork.data;
+        let non_empty = |s: &Option<String>| {
+            s.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+        Self {
+            id: data.id,
+            artist: non_empty(&data.artist_display).and_then(|artist| artist_name(&artist)),
+            title: match data.title.trim() {
+                "" => "Untitled".to_string(),
+                title => title.to_string(),
+            },
+            date: non_empty(&data.date_display).unwrap_or_else(|| "n.d.".to_string()),
+            year: data.date_start.or(data.date_end),
+            medium: non_empty(&data.medium_display),
+            credit_line: non_empty(&data.credit_line),
+            url: artwork.config.artwork_url(data.id),
+            accessed: Utc::now().date_naive(),
+            markup: Markup::default(),
+        }
+    }
+
+    /// Sets the date the artwork was accessed.
+    pub fn accessed(mut self, accessed: NaiveDate) -> Self {
+        self.accessed = accessed;
+        self
+    }
+
+    /// Sets how titles are set off in the Chicago, MLA and APA styles.
+    pub fn markup(mut self, markup: Markup) -> Self {
+        self.markup = markup;
+        self
+    }
+
+    /// Formats the citation in `style`.
+    pub fn format(&self, style: Style) -> String {
+        match style {
+            Style::Chicago => self.chicago(),
+            Style::Mla => self.mla(),
+            Style::Apa => self.apa(),
+            Style::Bibtex => self.bibtex(),
+            Style::CslJson => {
+                serde_json::to_string_pretty(&self.csl_json()).expect("CSL-JSON is serializable")
+            }
+        }
+    }
+
+    /// Returns the citation as a CSL-JSON item.
+    pub fn csl_json(&self) -> Value {
+        let mut item = json!({
+            "id": self.key(),
+            "type": "graphic",
+            "title": self.title,
+            "archive": INSTITUTION,
+            "archive-place": "Chicago, IL",
+            "URL": self.url,
+            "accessed": {
+                "date-parts": [[self.accessed.year(), self.accessed.month(), self.accessed.day()]]
+            },
+        });
+        if let Some(artist) = &self.artist {
+            item["author"] = json!([{ "literal": artist }]);
+        }
+        item["issued"] = match self.year {
+            Some(year) if self.date.parse::<i64>() == Ok(year) => {
+                json!({ "date-parts": [[year]] })
+            }
+            _ => json!({ "literal": self.date }),
+        };
+        if let Some(medium) = &self.medium {
+            item["medium"] = json!(medium);
+        }
+        if let Some(credit_line) = &self.credit_line {
+            item["note"] = json!(credit_line);
+        }
+        item
+    }
+
+    fn chicago(&self) -> String {
+        let institution = match &self.credit_line {
+            Some(credit_line) => format!("{INSTITUTION}, {credit_line}"),
+            None => INSTITUTION.to_string(),
+        };
+        let accessed = format!("{} (accessed {})", self.url, long_date(self.accessed));
+        sentences(&[
+            self.escaped(self.artist.as_deref()),
+            Some(self.italic(&self.title)),
+            Some(self.escape(&self.date)),
+            self.escaped(self.medium.as_deref()),
+            Some(self.escape(&institution)),
+            Some(self.escape(&accessed)),
+        ])
+    }
+
+    fn mla(&self) -> String {
+        let date_medium = match &self.medium {
+            Some(medium) => format!("{}, {medium}", self.date),
+            None => self.date.clone(),
+        };
+        let url = self
+            .url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        let accessed = format!(
+            "Accessed {} {} {}",
+            self.accessed.day(),
+            MLA_MONTHS[self.accessed.month0() as usize],
+            self.accessed.year()
+        );
+        sentences(&[
+            self.escaped(self.artist.as_deref()),
+            Some(self.italic(&self.title)),
+            Some(self.escape(&date_medium)),
+            Some(self.escape(&format!("{INSTITUTION}, {url}"))),
+            Some(accessed),
+        ])
+    }
+
+    fn apa(&self) -> String {
+        let title = match &self.medium {
+            Some(medium) => format!("{} [{}]", self.italic(&self.title), self.escape(medium)),
+            None => self.italic(&self.title),
+        };
+        let retrieved = format!(
+            "Retrieved {}, from {}",
+            long_date(self.accessed),
+            self.escape(&self.url)
+        );
+        let cited = sentences(&[
+            self.escaped(self.artist.as_deref()),
+            Some(format!("({})", self.escape(&self.date))),
+            Some(title),
+            Some(format!("{INSTITUTION}, Chicago, IL, United States")),
+        ]);
+        // APA doesn't put a period after a URL.
+        format!("{cited} {retrieved}")
+    }
+
+    fn bibtex(&self) -> String {
+        let mut fields = vec![];
+        if let Some(artist) = &self.artist {
+            // Double braces keep BibTeX from splitting the name up.
+            fields.push(("author", format!("{{{}}}", bibtex_escape(artist))));
+        }
+        fields.push(("title", bibtex_escape(&self.title)));
+        match self.year {
+            Some(year) => fields.push(("year", year.to_string())),
+            None => fields.push(("year", bibtex_escape(&self.date))),
+        }
+        if let Some(medium) = &self.medium {
+            fields.push(("howpublished", bibtex_escape(medium)));
+        }
+        let note = match &self.credit_line {
+            Some(credit_line) => format!("{INSTITUTION}. {credit_line}"),
+            None => INSTITUTION.to_string(),
+        };
+        fields.push(("note", bibtex_escape(&note)));
+        fields.push(("url", self.url.clone()));
+        fields.push(("urldate", self.accessed.format("%Y-%m-%d").to_string()));
+        let fields: Vec<String> = fields
+            .into_iter()
+            .map(|(name, value)| format!("  {name} = {{{value}}}"))
+            .collect();
+        format!("@misc{{{},\n{}\n}}", self.key(), fields.join(",\n"))
+    }
+
+    fn key(&self) -> String {
+        format!("aic-{}", self.id)
+    }
+
+    fn italic(&self, text: &str) -> String {
+        match self.markup {
+            Markup::Plain => text.to_string(),
+            Markup::Markdown => format!("*{text}*"),
+            Markup::Html => format!("<i>{}</i>", self.escape(text)),
+        }
+    }
+
+    fn escape(&self, text: &str) -> String {
+        match self.markup {
+            Markup::Html => text
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;"),
+            _ => text.to_string(),
+        }
+    }
+
+    fn escaped(&self, text: Option<&str>) -> Option<String> {
+        text.map(|text| self.escape(text))
+    }
+}
+
+/// Picks the name out of the artist as displayed on the label, which is either "Name\nNationality,
+/// dates" or "Name (Nationality, dates)".
+fn artist_name(artist: &str) -> Option<String> {
+    let name = artist.lines().next()?.trim();
+    let name = match name
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+    {
+        Some((name, _)) => name.trim(),
+        None => name,
+    };
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+const MLA_MONTHS: [&str; 12] = [
+    "Jan.", "Feb.", "Mar.", "Apr.", "May", "June", "July", "Aug.", "Sept.", "Oct.", "Nov.", "Dec.",
+];
+
+fn long_date(date: NaiveDate) -> String {
+    date.format("%B %-d, %Y").to_string()
+}
+
+/// Joins parts into sentences, without doubling up on closing punctuation.
+fn sentences(parts: &[Option<String>]) -> String {
+    parts
+        .iter()
+        .flatten()
+        .map(|part| {
+            let closed =
+                part.ends_with(['.', '?', '!']) || part.ends_with(".*") || part.ends_with(".</i>");
+            if closed {
+                part.clone()
+            } else {
+                format!("{part}.")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn bibtex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::envelope;
+
+    fn artwork() -> Artwork {
+        serde_json::from_value(envelope(json!({
+            "id": 28560,
+            "title": "The Bedroom",
+            "artist_display": "Vincent van Gogh (Dutch, 1853–1890)",
+            "date_display": "1889",
+            "date_start": 1889,
+            "date_end": 1889,
+            "medium_display": "Oil on canvas",
+            "credit_line": "Helen Birch Bartlett Memorial Collection"
+        })))
+        .unwrap()
+    }
+
+    fn citation() -> Citation {
+        Citation::new(&artwork()).accessed(NaiveDate::from_ymd_opt(2024, 9, 3).unwrap())
+    }
+
+    #[test]
+    fn chicago() {
+        assert_eq!(
+            citation().markup(Markup::Markdown).format(Style::Chicago),
+            "Vincent van Gogh. *The Bedroom*. 1889. Oil on canvas. Art \
+             Institute of Chicago, Helen Birch Bartlett Memorial Collection. \
+             https://www.artic.edu/artworks/28560 (accessed September 3, 2024)."
+        );
+    }
+
+    #[test]
+    fn mla() {
+        assert_eq!(
+            citation().format(Style::Mla),
+            "Vincent van Gogh. The Bedroom. 1889, Oil on canvas. Art Institute \
+             of Chicago, www.artic.edu/artworks/28560. Accessed 3 Sept. 2024."
+        );
+    }
+
+    #[test]
+    fn apa() {
+        assert_eq!(
+            citation().markup(Markup::Html).format(Style::Apa),
+            "Vincent van Gogh. (1889). <i>The Bedroom</i> [Oil on canvas]. Art \
+             Institute of Chicago, Chicago, IL, United States. Retrieved September 3, 2024, from \
+             https://www.artic.edu/artworks/28560"
+        );
+    }
+
+    #[test]
+    fn bibtex() {
+        assert_eq!(
+            citation().format(Style::Bibtex),
+            "@misc{aic-28560,
+  author = {{Vincent van Gogh}},
+  title = {The Bedroom},
+  year = {1889},
+  howpublished = {Oil on canvas},
+  note = {Art Institute of Chicago. Helen Birch Bartlett Memorial Collection},
+  url = {https://www.artic.edu/artworks/28560},
+  urldate = {2024-09-03}
+}"
+        );
+    }
+
+    #[test]
+    fn csl_json() {
+        assert_eq!(
+            citation().csl_json(),
+            json!({
+                "id": "aic-28560",
+                "type": "graphic",
+                "title": "The Bedroom",
+                "author": [{ "literal": "Vincent van Gogh" }],
+                "issued": { "date-parts": [[1889]] },
+                "medium": "Oil on canvas",
+                "note": "Helen Birch Bartlett Memorial Collection",
+                "archive": "Art Institute of Chicago",
+                "archive-place": "Chicago, IL",
+                "URL": "https://www.artic.edu/artworks/28560",
+                "accessed": { "date-parts": [[2024, 9, 3]] }
+            })
+        );
+    }
+
+    #[test]
+    fn missing_fields_are_left_out() {
+        let mut artwork = artwork();
+        artwork.data.artist_display = None;
+        artwork.data.medium_display = None;
+        artwork.data.credit_line = None;
+        artwork.data.date_display = None;
+        artwork.data.date_start = None;
+        artwork.data.date_end = None;
+        artwork.data.title = "Cup & Saucer".to_string();
+        let citation =
+            Citation::new(&artwork).accessed(NaiveDate::from_ymd_opt(2024, 9, 3).unwrap());
+
+        assert_eq!(
+            citation.format(Style::Chicago),
+            "Cup & Saucer. n.d. Art Institute of Chicago. https://www.artic.edu/artworks/28560 \
+             (accessed September 3, 2024)."
+        );
+        assert!(
+            citation
+                .format(Style::Bibtex)
+                .contains("title = {Cup \\& Saucer}")
+        );
+        assert_eq!(citation.csl_json()["issued"], json!({ "literal": "n.d." }));
+    }
+}
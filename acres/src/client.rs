@@ -0,0 +1,387 @@
+//! Fluent entrypoints for building and sending requests.
+//!
+//! These wrap the request builders so that the base URI comes from the [`Api`] and `send()`
+//! returns the endpoint's typed response.
+//!
+//! [`Api`]: ../struct.Api.html
+
+use crate::{
+    AcresError, Api,
+    agents::{
+        Agent, Agents,
+        request::{agent, agents},
+    },
+    artworks::{
+        Artwork, Artworks, Manifest, Search,
+        request::{artwork, artworks, manifest, search},
+    },
+    sounds::{
+        Sound, Sounds,
+        request::{sound, sounds},
+    },
+    tours::{
+        Guide, Tour, Tours,
+        request::{tour, tours},
+    },
+};
+
+/// Entrypoint for the [Artworks collection].
+///
+/// Get one of these from [`Api::artworks()`].
+///
+/// [Artworks collection]: https://api.artic.edu/docs/#artworks
+/// [`Api::artworks()`]: ../struct.Api.html#method.artworks
+#[derive(Clone, Copy, Debug)]
+pub struct ArtworksClient<'a> {
+    api: &'a Api,
+}
+
+impl<'a> ArtworksClient<'a> {
+    pub(crate) fn new(api: &'a Api) -> Self {
+        Self { api }
+    }
+
+    /// Starts a [`GET /artworks`] request.
+    ///
+    /// [`GET /artworks`]: https://api.artic.edu/docs/#get-artworks
+    pub fn list(&self) -> Call<'a, artworks::Builder> {
+        Call::new(
+            self.api,
+            artworks::Builder::new().base_uri(self.api.base_uri()),
+        )
+    }
+
+    /// Starts a [`GET /artworks/search`] request.
+    ///
+    /// [`GET /artworks/search`]: https://api.artic.edu/docs/#get-artworks-search
+    pub fn search(&self) -> Call<'a, search::Builder> {
+        Call::new(
+            self.api,
+            search::Builder::new().base_uri(self.api.base_uri()),
+        )
+    }
+
+    /// Starts a [`GET /artworks/{id}`] request.
+    ///
+    /// [`GET /artworks/{id}`]: https://api.artic.edu/docs/#get-artworks-id
+    pub fn get(&self, id: u32) -> Call<'a, artwork::Request> {
+        Call::new(self.api, artwork::Request::new(self.api.base_uri(), id))
+    }
+
+    /// Starts a [`GET /artworks/{id}/manifest.json`] request.
+    ///
+    /// [`GET /artworks/{id}/manifest.json`]: https://api.artic.edu/docs/#get-artworks-id-manifest-json
+    pub fn manifest(&self, id: u32) -> Call<'a, manifest::Request> {
+        Call::new(self.api, manifest::Request::new(self.api.base_uri(), id))
+    }
+}
+
+/// Entrypoint for the [Agents collection].
+///
+/// Get one of these from [`Api::agents()`].
+///
+/// [Agents collection]: https://api.artic.edu/docs/#agents
+/// [`Api::agents()`]: ../struct.Api.html#method.agents
+#[derive(Clone, Copy, Debug)]
+pub struct AgentsClient<'a> {
+    api: &'a Api,
+}
+
+impl<'a> AgentsClient<'a> {
+    pub(crate) fn new(api: &'a Api) -> Self {
+        Self { api }
+    }
+
+    /// Starts a [`GET /agents`] request.
+    ///
+    /// [`GET /agents`]: https://api.artic.edu/docs/#get-agents
+    pub fn list(&self) -> Call<'a, agents::Builder> {
+        Call::new(
+            self.api,
+            agents::Builder::new().base_uri(self.api.base_uri()),
+        )
+    }
+
+    /// Starts a [`GET /agents/{id}`] request.
+    ///
+    /// [`GET /agents/{id}`]: https://api.artic.edu/docs/#get-agents-id
+    pub fn get(&self, id: u32) -> Call<'a, agent::Request> {
+        Call::new(self.api, agent::Request::new(self.api.base_uri(), id))
+    }
+}
+
+/// Entrypoint for the [Tours collection].
+///
+/// Get one of these from [`Api::tours()`].
+///
+/// [Tours collection]: https://api.artic.edu/docs/#tours
+/// [`Api::tours()`]: ../struct.Api.html#method.tours
+#[derive(Clone, Copy, Debug)]
+pub struct ToursClient<'a> {
+    api: &'a Api,
+}
+
+impl<'a> ToursClient<'a> {
+    pub(crate) fn new(api: &'a Api) -> Self {
+        Self { api }
+    }
+
+    /// Starts a [`GET /tours`] request.
+    ///
+    /// [`GET /tours`]: https://api.artic.edu/docs/#get-tours
+    pub fn list(&self) -> Call<'a, tours::Builder> {
+        Call::new(
+            self.api,
+            tours::Builder::new().base_uri(self.api.base_uri()),
+        )
+    }
+
+    /// Starts a [`GET /tours/{id}`] request.
+    ///
+    /// [`GET /tours/{id}`]: https://api.artic.edu/docs/#get-tours-id
+    pub fn get(&self, id: u32) -> Call<'a, tour::Request> {
+        Call::new(self.api, tour::Request::new(self.api.base_uri(), id))
+    }
+
+    /// Looks up a tour and expands it into a [`Guide`] to its artworks.
+    ///
+    /// [`Guide`]: ../tours/struct.Guide.html
+    pub async fn guide(&self, id: u32) -> Result<Guide, AcresError> {
+        Guide::fetch(self.api, id).await
+    }
+}
+
+/// Entrypoint for the [Mobile Sounds collection].
+///
+/// Get one of these from [`Api::sounds()`].
+///
+/// [Mobile Sounds collection]: https://api.artic.edu/docs/#mobile-sounds
+/// [`Api::sounds()`]: ../struct.Api.html#method.sounds
+#[derive(Clone, Copy, Debug)]
+pub struct SoundsClient<'a> {
+    api: &'a Api,
+}
+
+impl<'a> SoundsClient<'a> {
+    pub(crate) fn new(api: &'a Api) -> Self {
+        Self { api }
+    }
+
+    /// Starts a [`GET /mobile-sounds`] request.
+    ///
+    /// [`GET /mobile-sounds`]: https://api.artic.edu/docs/#get-mobile-sounds
+    pub fn list(&self) -> Call<'a, sounds::Builder> {
+        Call::new(
+            self.api,
+            sounds::Builder::new().base_uri(self.api.base_uri()),
+        )
+    }
+
+    /// Starts a [`GET /mobile-sounds/{id}`] request.
+    ///
+    /// [`GET /mobile-sounds/{id}`]: https://api.artic.edu/docs/#get-mobile-sounds-id
+    pub fn get(&self, id: u32) -> Call<'a, sound::Request> {
+        Call::new(self.api, sound::Request::new(self.api.base_uri(), id))
+    }
+}
+
+/// A request that is bound to an [`Api`] and ready to be sent.
+///
+/// [`Api`]: ../struct.Api.html
+#[derive(Clone, Debug)]
+pub struct Call<'a, R> {
+    api: &'a Api,
+    request: R,
+}
+
+impl<'a, R> Call<'a, R> {
+    fn new(api: &'a Api, request: R) -> Self {
+        Self { api, request }
+    }
+
+    /// Updates the underlying request or builder.
+    ///
+    /// Use this when you need an option that doesn't have a shortcut here.
+    ///
+    /// ```rust
+    /// let api = acres::Api::new();
+    /// let call = api.artworks().list().with(|builder| builder.include(Some(vec!["place_pivots".into()])));
+    /// ```
+    pub fn with(mut self, f: impl FnOnce(R) -> R) -> Self {
+        self.request = f(self.request);
+        self
+    }
+}
+
+impl Call<'_, artwork::Request> {
+    /// Sends the request.
+    pub async fn send(self) -> Result<Artwork, AcresError> {
+        self.api.send(&self.request).await
+    }
+}
+
+impl Call<'_, agent::Request> {
+    /// Sends the request.
+    pub async fn send(self) -> Result<Agent, AcresError> {
+        self.api.send(&self.request).await
+    }
+}
+
+impl Call<'_, agents::Builder> {
+    /// Sets the agent ids to retrieve.
+    pub fn ids(self, ids: impl IntoIterator<Item = u32>) -> Self {
+        let ids = ids.into_iter().collect();
+        self.with(|builder| builder.ids(Some(ids)))
+    }
+
+    /// Sets limit on number of agents to return per page.
+    pub fn limit(self, limit: u32) -> Self {
+        self.with(|builder| builder.limit(Some(limit)))
+    }
+
+    /// Sets page number to return.
+    pub fn page(self, page: u32) -> Self {
+        self.with(|builder| builder.page(Some(page)))
+    }
+
+    /// Builds and sends the request.
+    pub async fn send(self) -> Result<Agents, AcresError> {
+        let request = self.request.build()?;
+        self.api.send(&request).await
+    }
+}
+
+impl Call<'_, manifest::Request> {
+    /// Sends the request.
+    pub async fn send(self) -> Result<Manifest, AcresError> {
+        self.api.send(&self.request).await
+    }
+}
+
+impl Call<'_, artworks::Builder> {
+    /// Sets the artwork ids to retrieve.
+    pub fn ids(self, ids: impl IntoIterator<Item = u32>) -> Self {
+        let ids = ids.into_iter().collect();
+        self.with(|builder| builder.ids(Some(ids)))
+    }
+
+    /// Sets limit on number of artworks to return per page.
+    pub fn limit(self, limit: u32) -> Self {
+        self.with(|builder| builder.limit(Some(limit)))
+    }
+
+    /// Sets page number to return.
+    pub fn page(self, page: u32) -> Self {
+        self.with(|builder| builder.page(Some(page)))
+    }
+
+    /// Sets the artwork fields to retrieve.
+    pub fn fields<S: Into<String>>(self, fields: impl IntoIterator<Item = S>) -> Self {
+        let fields = fields.into_iter().map(Into::into).collect();
+        self.with(|builder| builder.fields(Some(fields)))
+    }
+
+    /// Builds and sends the request.
+    pub async fn send(self) -> Result<Artworks, AcresError> {
+        let request = self.request.build()?;
+        self.api.send(&request).await
+    }
+}
+
+impl Call<'_, search::Builder> {
+    /// Sets the search query.
+    pub fn q(self, q: impl Into<String>) -> Self {
+        let q = q.into();
+        self.with(|builder| builder.q(Some(q)))
+    }
+
+    /// Sets the more complex search query.
+    pub fn query(self, query: impl Into<String>) -> Self {
+        let query = query.into();
+        self.with(|builder| builder.query(Some(query)))
+    }
+
+    /// Sets the sort field.
+    pub fn sort(self, field: impl Into<String>) -> Self {
+        let field = field.into();
+        self.with(|builder| builder.sort(Some(field)))
+    }
+
+    /// Sets the from parameter.
+    pub fn from(self, from: u32) -> Self {
+        self.with(|builder| builder.from(Some(from)))
+    }
+
+    /// Sets the size parameter.
+    pub fn size(self, size: u32) -> Self {
+        self.with(|builder| builder.size(Some(size)))
+    }
+
+    /// Builds and sends the request.
+    pub async fn send(self) -> Result<Search, AcresError> {
+        let request = self.request.build()?;
+        self.api.send(&request).await
+    }
+}
+
+impl Call<'_, tour::Request> {
+    /// Sends the request.
+    pub async fn send(self) -> Result<Tour, AcresError> {
+        self.api.send(&self.request).await
+    }
+}
+
+impl Call<'_, tours::Builder> {
+    /// Sets the tour ids to retrieve.
+    pub fn ids(self, ids: impl IntoIterator<Item = u32>) -> Self {
+        let ids = ids.into_iter().collect();
+        self.with(|builder| builder.ids(Some(ids)))
+    }
+
+    /// Sets limit on number of tours to return per page.
+    pub fn limit(self, limit: u32) -> Self {
+        self.with(|builder| builder.limit(Some(limit)))
+    }
+
+    /// Sets page number to return.
+    pub fn page(self, page: u32) -> Self {
+        self.with(|builder| builder.page(Some(page)))
+    }
+
+    /// Builds and sends the request.
+    pub async fn send(self) -> Result<Tours, AcresError> {
+        let request = self.request.build()?;
+        self.api.send(&request).await
+    }
+}
+
+impl Call<'_, sound::Request> {
+    /// Sends the request.
+    pub async fn send(self) -> Result<Sound, AcresError> {
+        self.api.send(&self.request).await
+    }
+}
+
+impl Call<'_, sounds::Builder> {
+    /// Sets the recording ids to retrieve.
+    pub fn ids(self, ids: impl IntoIterator<Item = u32>) -> Self {
+        let ids = ids.into_iter().collect();
+        self.with(|builder| builder.ids(Some(ids)))
+    }
+
+    /// Sets limit on number of recordings to return per page.
+    pub fn limit(self, limit: u32) -> Self {
+        self.with(|builder| builder.limit(Some(limit)))
+    }
+
+    /// Sets page number to return.
+    pub fn page(self, page: u32) -> Self {
+        self.with(|builder| builder.page(Some(page)))
+    }
+
+    /// Builds and sends the request.
+    pub async fn send(self) -> Result<Sounds, AcresError> {
+        let request = self.request.build()?;
+        self.api.send(&request).await
+    }
+}
@@ -0,0 +1,652 @@
+//! Search by color.
+//!
+//! Each artwork has a dominant `color` with hue (`h`, 0 to 360), saturation (`s`, 0 to 100) and
+//! lightness (`l`, 0 to 100). A [`Query`] turns a color and a [`Tolerance`] into a range query over
+//! those fields, wrapping around the hue circle where it needs to, and then ranks the results by
+//! how different they look from the color you asked for.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use acres::color::{Hsl, Query, Tolerance};
+//! use serde_json::json;
+//!
+//! # fn main() -> Result<()> {
+//! let query = Query::new("red".parse::<Hsl>()?).tolerance(Tolerance::uniform(10.0));
+//!
+//! // Red's hue is 0, so the hue range wraps around from 350 to 10.
+//! assert_eq!(
+//!     query.query()["bool"]["must"][0],
+//!     json!({
+//!         "bool": {
+//!             "should": [
+//!                 { "range": { "color.h": { "gte": 350.0, "lte": 360.0 } } },
+//!                 { "range": { "color.h": { "gte": 0.0, "lte": 10.0 } } }
+//!             ],
+//!             "minimum_should_match": 1
+//!         }
+//!     })
+//! );
+//!
+//! let mut records = vec![
+//!     json!({ "id": 1, "color": { "h": 200, "s": 80, "l": 50 } }),
+//!     json!({ "id": 2, "color": { "h": 350, "s": 80, "l": 45 } }),
+//! ];
+//! query.rank(&mut records);
+//! assert_eq!(records[0]["id"], 2);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Colors can be written as CSS color names (`rebeccapurple`), hex (`#639`, `#663399`),
+//! `rgb(102, 51, 153)` or `hsl(270, 50%, 40%)`.
+
+use std::{fmt::Display, str::FromStr};
+
+use serde_json::{Value, json};
+
+use crate::AcresError;
+
+/// A color as hue (0 to 360), saturation (0 to 100) and lightness (0 to 100).
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Hsl {
+    /// Hue, in degrees.
+    pub h: f64,
+    /// Saturation, as a percentage.
+    pub s: f64,
+    /// Lightness, as a percentage.
+    pub l: f64,
+}
+
+impl Hsl {
+    /// Creates a color, wrapping the hue and clamping saturation and lightness.
+    pub fn new(h: f64, s: f64, l: f64) -> Self {
+        Hsl {
+            h: h.rem_euclid(360.0),
+            s: s.clamp(0.0, 100.0),
+            l: l.clamp(0.0, 100.0),
+        }
+    }
+
+    /// Creates a color from red, green and blue.
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        let (r, g, b) = (
+            f64::from(r) / 255.0,
+            f64::from(g) / 255.0,
+            f64::from(b) / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return Hsl::new(0.0, 0.0, l * 100.0);
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / d).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / d + 2.0)
+        } else {
+            60.0 * ((r - g) / d + 4.0)
+        };
+        Hsl::new(h, s * 100.0, l * 100.0)
+    }
+
+    /// Returns the color's red, green and blue, from 0 to 1.
+    fn rgb(&self) -> (f64, f64, f64) {
+        let (s, l) = (self.s / 100.0, self.l / 100.0);
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = self.h / 60.0;
+        let x = c * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        (r + m, g + m, b + m)
+    }
+
+    /// Returns the color in CIELAB, under a D65 white point.
+    fn lab(&self) -> (f64, f64, f64) {
+        let linear = |c: f64| {
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let (r, g, b) = self.rgb();
+        let (r, g, b) = (linear(r), linear(g), linear(b));
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+        let f = |t: f64| {
+            if t > 216.0 / 24389.0 {
+                t.cbrt()
+            } else {
+                (24389.0 / 27.0 * t + 16.0) / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    /// Returns how different two colors look, as CIEDE2000 ΔE.
+    ///
+    /// A difference under about 2 is hard to see; one over 50 is a different color entirely.
+    pub fn distance(&self, other: &Hsl) -> f64 {
+        ciede2000(self.lab(), other.lab())
+    }
+
+    /// Reads an artwork's dominant color from its `color` field.
+    pub fn of(record: &Value) -> Option<Self> {
+        let color = record.get("color")?;
+        Some(Hsl::new(
+            color.get("h")?.as_f64()?,
+            color.get("s")?.as_f64()?,
+            color.get("l")?.as_f64()?,
+        ))
+    }
+}
+
+impl Display for Hsl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "hsl({}, {}%, {}%)", self.h, self.s, self.l)
+    }
+}
+
+impl FromStr for Hsl {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AcresError::InvalidColor(s.to_string());
+        let text = s.trim().to_lowercase();
+        if let Some(hex) = text.strip_prefix('#') {
+            let digits = match hex.len() {
+                3 => hex.chars().flat_map(|c| [c, c]).collect(),
+                6 => hex.to_string(),
+                _ => return Err(invalid()),
+            };
+            let value = u32::from_str_radix(&digits, 16).map_err(|_| invalid())?;
+            let [_, r, g, b] = value.to_be_bytes();
+            return Ok(Hsl::from_rgb(r, g, b));
+        }
+        if let Some(args) = function_args(&text, "hsl") {
+            let [h, s, l] = numbers(&args).ok_or_else(invalid)?;
+            return Ok(Hsl::new(h, s, l));
+        }
+        if let Some(args) = function_args(&text, "rgb") {
+            let [r, g, b] = numbers(&args).ok_or_else(invalid)?;
+            let channel = |c: f64| c.clamp(0.0, 255.0).round() as u8;
+            return Ok(Hsl::from_rgb(channel(r), channel(g), channel(b)));
+        }
+        NAMED
+            .iter()
+            .find(|(name, _)| *name == text)
+            .map(|(_, rgb)| {
+                let [_, r, g, b] = rgb.to_be_bytes();
+                Hsl::from_rgb(r, g, b)
+            })
+            .ok_or_else(invalid)
+    }
+}
+
+/// How far each channel may stray from the color searched for.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Tolerance {
+    /// Degrees of hue either side.
+    pub h: f64,
+    /// Points of saturation either side.
+    pub s: f64,
+    /// Points of lightness either side.
+    pub l: f64,
+}
+
+impl Tolerance {
+    /// Creates a tolerance that's the same for every channel.
+    pub fn uniform(tolerance: f64) -> Self {
+        Tolerance {
+            h: tolerance,
+            s: tolerance,
+            l: tolerance,
+        }
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance {
+            h: 15.0,
+            s: 20.0,
+            l: 20.0,
+        }
+    }
+}
+
+/// A search for artworks by dominant color.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Query {
+    color: Hsl,
+    tolerance: Tolerance,
+}
+
+impl Query {
+    /// Creates a search for the color with the default tolerance.
+    pub fn new(color: Hsl) -> Self {
+        Query {
+            color,
+            tolerance: Tolerance::default(),
+        }
+    }
+
+    /// Sets the tolerance.
+    pub fn tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Returns the color searched for.
+    pub fn color(&self) -> Hsl {
+        self.color
+    }
+
+    /// Returns the range query over `color.h`, `color.s` and `color.l`.
+    ///
+    /// Grays have no meaningful hue, so when the saturation range reaches 0 the hue is left out.
+    pub fn query(&self) -> Value {
+        let Hsl { h, s, l } = self.color;
+        let tolerance = self.tolerance;
+        let mut must = vec![];
+        if s - tolerance.s > 0.0 {
+            must.push(hue_query(h, tolerance.h));
+        }
+        must.push(range(
+            "color.s",
+            (s - tolerance.s).max(0.0),
+            (s + tolerance.s).min(100.0),
+        ));
+        must.push(range(
+            "color.l",
+            (l - tolerance.l).max(0.0),
+            (l + tolerance.l).min(100.0),
+        ));
+        json!({ "bool": { "must": must } })
+    }
+
+    /// Returns how different a record's dominant color looks from the color searched for.
+    pub fn distance(&self, record: &Value) -> Option<f64> {
+        Hsl::of(record).map(|color| self.color.distance(&color))
+    }
+
+    /// Sorts records so the closest colors come first.
+    ///
+    /// Records without a `color` go last, in their original order.
+    pub fn rank(&self, records: &mut [Value]) {
+        records.sort_by(|a, b| {
+            let (a, b) = (self.distance(a), self.distance(b));
+            match (a, b) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        });
+    }
+}
+
+fn hue_query(h: f64, tolerance: f64) -> Value {
+    if tolerance >= 180.0 {
+        return range("color.h", 0.0, 360.0);
+    }
+    let (low, high) = (h - tolerance, h + tolerance);
+    if low < 0.0 {
+        wrapped(
+            range("color.h", low + 360.0, 360.0),
+            range("color.h", 0.0, high),
+        )
+    } else if high > 360.0 {
+        wrapped(
+            range("color.h", low, 360.0),
+            range("color.h", 0.0, high - 360.0),
+        )
+    } else {
+        range("color.h", low, high)
+    }
+}
+
+fn wrapped(upper: Value, lower: Value) -> Value {
+    json!({ "bool": { "should": [upper, lower], "minimum_should_match": 1 } })
+}
+
+fn range(field: &str, gte: f64, lte: f64) -> Value {
+    json!({ "range": { field: { "gte": gte, "lte": lte } } })
+}
+
+fn function_args(text: &str, name: &str) -> Option<String> {
+    let rest = text.strip_prefix(name)?;
+    let rest = rest.strip_prefix('a').unwrap_or(rest);
+    let args = rest.trim().strip_prefix('(')?.strip_suffix(')')?;
+    Some(args.replace(['%', ','], " ").replace("deg", " "))
+}
+
+fn numbers(args: &str) -> Option<[f64; 3]> {
+    let mut numbers = args.split_whitespace().map(str::parse::<f64>);
+    let parsed = [
+        numbers.next()?.ok()?,
+        numbers.next()?.ok()?,
+        numbers.next()?.ok()?,
+    ];
+    Some(parsed)
+}
+
+fn ciede2000((l1, a1, b1): (f64, f64, f64), (l2, a2, b2): (f64, f64, f64)) -> f64 {
+    let pow7 = |x: f64| x.powi(7);
+    let c_bar = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (pow7(c_bar) / (pow7(c_bar) + pow7(25.0))).sqrt());
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |b: f64, a: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let (h1, h2) = (hue(b1, a1), hue(b2, a2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else if h2 - h1 < -180.0 {
+        h2 - h1 + 360.0
+    } else {
+        h2 - h1
+    };
+    let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+    let l_bar = (l1 + l2) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+    let cos = |degrees: f64| degrees.to_radians().cos();
+    let t =
+        1.0 - 0.17 * cos(h_bar - 30.0) + 0.24 * cos(2.0 * h_bar) + 0.32 * cos(3.0 * h_bar + 6.0)
+            - 0.20 * cos(4.0 * h_bar - 63.0);
+    let d_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (pow7(c_bar) / (pow7(c_bar) + pow7(25.0))).sqrt();
+    let s_l = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar;
+    let s_h = 1.0 + 0.015 * c_bar * t;
+    let r_t = -(2.0 * d_theta).to_radians().sin() * r_c;
+
+    ((dl / s_l).powi(2) + (dc / s_c).powi(2) + (dh / s_h).powi(2) + r_t * (dc / s_c) * (dh / s_h))
+        .sqrt()
+}
+
+/// The CSS named colors.
+const NAMED: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 0.5
+    }
+
+    #[test]
+    fn parses_css_colors() {
+        let purple: Hsl = "rebeccapurple".parse().unwrap();
+        assert!(close(purple.h, 270.0) && close(purple.s, 50.0) && close(purple.l, 40.0));
+        assert_eq!("#639".parse::<Hsl>().unwrap(), purple);
+        assert_eq!("#663399".parse::<Hsl>().unwrap(), purple);
+        assert_eq!("rgb(102, 51, 153)".parse::<Hsl>().unwrap(), purple);
+        assert_eq!(
+            "hsl(270, 50%, 40%)".parse::<Hsl>().unwrap(),
+            Hsl::new(270.0, 50.0, 40.0)
+        );
+        assert!(matches!(
+            "#12345".parse::<Hsl>(),
+            Err(AcresError::InvalidColor(_))
+        ));
+        assert!("not a color".parse::<Hsl>().is_err());
+    }
+
+    #[test]
+    fn grays_match_any_hue() {
+        let query = Query::new("gray".parse().unwrap()).query();
+        let must = query["bool"]["must"].as_array().unwrap();
+
+        assert_eq!(must.len(), 2);
+        assert_eq!(
+            must[0],
+            json!({ "range": { "color.s": { "gte": 0.0, "lte": 20.0 } } })
+        );
+        assert!(must[1]["range"].get("color.l").is_some());
+    }
+
+    #[test]
+    fn hue_wraps_around() {
+        let query = Query::new(Hsl::new(5.0, 50.0, 50.0)).tolerance(Tolerance {
+            h: 10.0,
+            s: 5.0,
+            l: 60.0,
+        });
+
+        assert_eq!(
+            query.query(),
+            json!({
+                "bool": {
+                    "must": [
+                        {
+                            "bool": {
+                                "should": [
+                                    { "range": { "color.h": { "gte": 355.0, "lte": 360.0 } } },
+                                    { "range": { "color.h": { "gte": 0.0, "lte": 15.0 } } }
+                                ],
+                                "minimum_should_match": 1
+                            }
+                        },
+                        { "range": { "color.s": { "gte": 45.0, "lte": 55.0 } } },
+                        { "range": { "color.l": { "gte": 0.0, "lte": 100.0 } } }
+                    ]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn ciede2000_matches_reference_data() {
+        // Pairs from Sharma, Wu and Dalal's CIEDE2000 test data.
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+        ];
+        for (a, b, expected) in pairs {
+            assert!((ciede2000(a, b) - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn ranks_by_perceptual_distance() {
+        let query = Query::new("red".parse().unwrap());
+        let mut records = vec![
+            json!({ "id": 1 }),
+            json!({ "id": 2, "color": { "h": 30, "s": 100, "l": 50 } }),
+            json!({ "id": 3, "color": { "h": 355, "s": 90, "l": 48 } }),
+        ];
+
+        query.rank(&mut records);
+
+        let ids: Vec<_> = records.iter().map(|r| r["id"].as_u64().unwrap()).collect();
+        assert_eq!(ids, vec![3, 2, 1]);
+    }
+}
@@ -1,30 +1,221 @@
-use std::{env, path::PathBuf};
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+};
 
 use directories::ProjectDirs;
 use serde::Deserialize;
 
-#[derive(Clone, Debug, Default, Deserialize)]
+use crate::CassetteMode;
+
+/// Settings for the `acres` tools.
+///
+/// Settings are layered, with later layers taking precedence over earlier ones:
+///
+/// 1. built-in defaults,
+/// 2. the top level of the config file,
+/// 3. the selected profile in the config file,
+/// 4. `ACRES_*` environment variables, and
+/// 5. anything set on the [`ApiBuilder`].
+///
+/// The config file is `config.toml` in the platform config directory (e.g.,
+/// `~/.config/acres/config.toml` on Linux), or whatever `ACRES_CONFIG` points to.
+///
+/// ```toml
+/// base_uri = "https://api.artic.edu/api/v1"
+/// timeout = 30
+/// profile = "production"
+///
+/// [ascii]
+/// alphabet = "fast"
+/// width = "80"
+///
+/// [rights]
+/// enforce = true
+/// restricted_width = 200
+///
+/// [profiles.production]
+/// rate_limit = 1
+///
+/// [profiles.offline]
+/// cassette_dir = "/home/me/.local/share/acres/cassettes"
+/// cassette_mode = "replay"
+///
+/// [profiles.staging]
+/// base_uri = "https://api-staging.artic.edu/api/v1"
+/// use_cache = false
+/// ```
+///
+/// The profile is picked with `ACRES_PROFILE`, then the top-level `profile` key. Nested
+/// settings can be overridden from the environment with a double underscore, e.g.
+/// `ACRES_ASCII__WIDTH=64`.
+///
+/// [`ApiBuilder`]: struct.ApiBuilder.html
+#[derive(Clone, Debug, Deserialize)]
 pub struct Config {
+    /// Whether to cache responses.
     #[serde(default)]
     pub use_cache: bool,
+    /// Where to cache responses.
     pub cache_dir: PathBuf,
+    /// Base URI for the API.
     pub base_uri: String,
+    /// Where to record or replay interactions.
+    #[serde(default)]
+    pub cassette_dir: Option<PathBuf>,
+    /// Whether to record or replay interactions.
+    #[serde(default)]
+    pub cassette_mode: Option<CassetteMode>,
+    /// Request timeout, in seconds.
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// Maximum number of requests per second.
+    #[serde(default)]
+    pub rate_limit: Option<u32>,
+    /// Value for the `user-agent` header.
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// Default ASCII art settings.
+    #[serde(default)]
+    pub ascii: AsciiConfig,
+    /// Image rights policy settings.
+    #[serde(default)]
+    pub rights: RightsConfig,
+}
+
+/// Default ASCII art settings.
+///
+/// These are kept as strings so that each tool can parse them into its own types.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct AsciiConfig {
+    /// Alphabet.
+    pub alphabet: Option<String>,
+    /// Brightness offset.
+    pub brightness_offset: Option<String>,
+    /// Conversion algorithm.
+    pub conversion_algorithm: Option<String>,
+    /// Font.
+    pub font: Option<String>,
+    /// Metric.
+    pub metric: Option<String>,
+    /// Width in chars.
+    pub width: Option<String>,
+}
+
+/// Image rights policy settings.
+///
+/// See [`rights::Policy`] for what enforcing the policy does.
+///
+/// [`rights::Policy`]: ../rights/struct.Policy.html
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct RightsConfig {
+    /// Whether to limit image sizes for works that aren't in the public domain.
+    #[serde(default)]
+    pub enforce: bool,
+    /// Largest width, in pixels, to request for works that aren't in the public domain.
+    pub restricted_width: Option<u32>,
+}
+
+/// The built-in base URI.
+const DEFAULT_BASE_URI: &str = "https://api.artic.edu/api/v1";
+
+impl Default for Config {
+    /// Returns the built-in defaults, without reading the config file or environment.
+    fn default() -> Self {
+        Config {
+            use_cache: true,
+            cache_dir: get_acres_cache_dir(),
+            base_uri: DEFAULT_BASE_URI.to_string(),
+            cassette_dir: None,
+            cassette_mode: None,
+            timeout: None,
+            rate_limit: None,
+            user_agent: None,
+            ascii: AsciiConfig::default(),
+            rights: RightsConfig::default(),
+        }
+    }
 }
 
 impl Config {
+    /// Loads settings using the profile from the environment or config file.
     pub fn new() -> Result<Self, config::ConfigError> {
+        Config::load(&config_file(), None, None)
+    }
+
+    /// Loads settings using the named profile.
+    pub fn with_profile(profile: &str) -> Result<Self, config::ConfigError> {
+        Config::load(&config_file(), Some(profile.to_string()), None)
+    }
+
+    fn load(
+        file: &Path,
+        profile: Option<String>,
+        env_vars: Option<HashMap<String, String>>,
+    ) -> Result<Self, config::ConfigError> {
+        let file = config::File::from(file)
+            .format(config::FileFormat::Toml)
+            .required(false);
+        let from_file = config::Config::builder().add_source(file.clone()).build()?;
+        let profile = profile
+            .or_else(|| match &env_vars {
+                Some(vars) => vars.get("ACRES_PROFILE").cloned(),
+                None => env::var("ACRES_PROFILE").ok(),
+            })
+            .or_else(|| from_file.get_string("profile").ok());
+        let profile = match profile {
+            Some(name) => Profile(
+                from_file
+                    .get_table(&format!("profiles.{name}"))
+                    .map_err(|_| config::ConfigError::NotFound(format!("profile {name}")))?,
+            ),
+            None => Profile::default(),
+        };
+
         let cache_dir = get_acres_cache_dir();
         let builder = config::Config::builder()
             .set_default("use_cache", true)?
             .set_default("cache_dir", cache_dir.to_str().expect("path is valid"))?
-            .set_default("base_uri", "https://api.artic.edu/api/v1".to_string())?
-            .add_source(config::Environment::with_prefix("ACRES"));
+            .set_default("base_uri", DEFAULT_BASE_URI)?
+            .add_source(file)
+            .add_source(profile)
+            .add_source(
+                config::Environment::with_prefix("ACRES")
+                    .prefix_separator("_")
+                    .separator("__")
+                    .source(env_vars),
+            );
         let cfg: Self = builder.build()?.try_deserialize()?;
         tracing::debug!(?cfg);
         Ok(cfg)
     }
 }
 
+/// The settings from one `[profiles.*]` table.
+#[derive(Clone, Debug, Default)]
+struct Profile(config::Map<String, config::Value>);
+
+impl config::Source for Profile {
+    fn clone_into_box(&self) -> Box<dyn config::Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<config::Map<String, config::Value>, config::ConfigError> {
+        Ok(self.0.clone())
+    }
+}
+
+fn config_file() -> PathBuf {
+    if let Ok(file) = env::var("ACRES_CONFIG") {
+        PathBuf::from(file)
+    } else if let Some(proj_dirs) = project_directory() {
+        proj_dirs.config_dir().join("config.toml")
+    } else {
+        PathBuf::from(".").join("config.toml")
+    }
+}
+
 fn get_acres_cache_dir() -> PathBuf {
     if let Some(proj_dirs) = project_directory() {
         proj_dirs.data_local_dir().to_path_buf()
@@ -41,8 +232,117 @@ fn project_directory() -> Option<ProjectDirs> {
 mod tests {
     use super::*;
 
+    const CONFIG: &str = r#"
+base_uri = "https://file.example.org/api/v1"
+timeout = 10
+
+[ascii]
+alphabet = "fast"
+
+[profiles.staging]
+base_uri = "https://staging.example.org/api/v1"
+use_cache = false
+
+[profiles.offline]
+cassette_dir = "cassettes"
+cassette_mode = "replay"
+
+[profiles.offline.ascii]
+width = "64"
+
+[profiles.offline.rights]
+enforce = true
+"#;
+
+    fn config_file() -> assert_fs::NamedTempFile {
+        let file = assert_fs::NamedTempFile::new("config.toml").unwrap();
+        std::fs::write(file.path(), CONFIG).unwrap();
+        file
+    }
+
+    fn env(vars: &[(&str, &str)]) -> Option<HashMap<String, String>> {
+        Some(
+            vars.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
     #[test]
     fn defaults_to_using_cache() {
         assert!(Config::new().unwrap().use_cache);
     }
+
+    #[test]
+    fn file_overrides_defaults() {
+        let file = config_file();
+
+        let config = Config::load(file.path(), None, env(&[])).unwrap();
+
+        assert_eq!(config.base_uri, "https://file.example.org/api/v1");
+        assert_eq!(config.timeout, Some(10));
+        assert!(config.use_cache);
+    }
+
+    #[test]
+    fn profile_overrides_file() {
+        let file = config_file();
+
+        let config = Config::load(file.path(), Some("staging".into()), env(&[])).unwrap();
+
+        assert_eq!(config.base_uri, "https://staging.example.org/api/v1");
+        assert!(!config.use_cache);
+        assert_eq!(config.timeout, Some(10));
+    }
+
+    #[test]
+    fn profile_merges_nested_tables() {
+        let file = config_file();
+
+        let config = Config::load(file.path(), Some("offline".into()), env(&[])).unwrap();
+
+        assert_eq!(config.cassette_mode, Some(CassetteMode::Replay));
+        assert_eq!(config.ascii.alphabet.as_deref(), Some("fast"));
+        assert_eq!(config.ascii.width.as_deref(), Some("64"));
+        assert!(config.rights.enforce);
+        assert_eq!(config.rights.restricted_width, None);
+    }
+
+    #[test]
+    fn env_overrides_profile() {
+        let file = config_file();
+
+        let config = Config::load(
+            file.path(),
+            None,
+            env(&[
+                ("ACRES_PROFILE", "staging"),
+                ("ACRES_BASE_URI", "https://env.example.org/api/v1"),
+                ("ACRES_ASCII__ALPHABET", "symbols"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.base_uri, "https://env.example.org/api/v1");
+        assert!(!config.use_cache);
+        assert_eq!(config.ascii.alphabet.as_deref(), Some("symbols"));
+    }
+
+    #[test]
+    fn default_is_built_in_defaults() {
+        let config = Config::default();
+
+        assert!(config.use_cache);
+        assert_eq!(config.base_uri, "https://api.artic.edu/api/v1");
+        assert_eq!(config.cache_dir, get_acres_cache_dir());
+    }
+
+    #[test]
+    fn unknown_profile_is_an_error() {
+        let file = config_file();
+
+        let result = Config::load(file.path(), Some("nope".into()), env(&[]));
+
+        assert!(result.is_err());
+    }
 }
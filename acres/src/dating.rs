@@ -0,0 +1,727 @@
+//! Structured dates for artworks.
+//!
+//! Artworks are dated with a pair of years, `date_start` and `date_end`, where years before the
+//! common era are negative, and a label such as "c. 1890–95". A [`Dating`] brings the two together:
+//! the years become a [`Span`], and the label tells us whether the date is approximate and how it
+//! is qualified.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use acres::dating::{Century, Dating, Era};
+//!
+//! # fn main() -> Result<()> {
+//! let dating = Dating::new(Some(1890), Some(1895), Some("c. 1890–95"));
+//! assert!(dating.is_circa());
+//! assert_eq!(dating.to_string(), "c. 1890–1895");
+//! assert_eq!(dating.centuries(), vec![Century::ce(19)]);
+//! assert_eq!(dating.eras(), vec![Era::Modern]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A [`Span`] can also be parsed from text like "1870 to 1890", "1860s", "19th century" or
+//! "500–400 BCE" and used to [search by date].
+//!
+//! [search by date]: ../artworks/request/search/struct.Builder.html#method.dated
+
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{AcresError, artworks::Data};
+
+/// An inclusive range of years, where years before the common era are negative.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::dating::Span;
+///
+/// # fn main() -> Result<()> {
+/// let span: Span = "19th century".parse()?;
+/// assert_eq!((span.start(), span.end()), (1800, 1899));
+///
+/// let span: Span = "1870 to 1890".parse()?;
+/// assert_eq!((span.start(), span.end()), (1870, 1890));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Span {
+    start: i64,
+    end: i64,
+}
+
+impl Span {
+    /// Creates a span, swapping the years if they're out of order.
+    pub fn new(start: i64, end: i64) -> Self {
+        Span {
+            start: start.min(end),
+            end: start.max(end),
+        }
+    }
+
+    /// Returns the first year.
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    /// Returns the last year.
+    pub fn end(&self) -> i64 {
+        self.end
+    }
+
+    /// Returns true if the span includes the year.
+    pub fn contains(&self, year: i64) -> bool {
+        self.start <= year && year <= self.end
+    }
+
+    /// Returns the search query matching artworks whose dates overlap the span.
+    ///
+    /// An open end, at [`i64::MIN`] or [`i64::MAX`], is left out of the query.
+    ///
+    /// ```rust
+    /// use acres::dating::Span;
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(
+    ///     Span::new(1870, 1890).query(),
+    ///     json!({
+    ///         "bool": {
+    ///             "must": [
+    ///                 { "range": { "date_start": { "lte": 1890 } } },
+    ///                 { "range": { "date_end": { "gte": 1870 } } }
+    ///             ]
+    ///         }
+    ///     })
+    /// );
+    /// ```
+    pub fn query(&self) -> Value {
+        let mut must = vec![];
+        if self.end < i64::MAX {
+            must.push(json!({ "range": { "date_start": { "lte": self.end } } }));
+        }
+        if self.start > i64::MIN {
+            must.push(json!({ "range": { "date_end": { "gte": self.start } } }));
+        }
+        json!({ "bool": { "must": must } })
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.start, self.end) {
+            (start, end) if start == end => write!(f, "{}", Year(start)),
+            (start, end) if end < 0 => write!(f, "{}–{} BCE", -start, -end),
+            (start, end) => write!(f, "{}–{}", Year(start), Year(end)),
+        }
+    }
+}
+
+impl FromStr for Span {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let label = Label::parse(s)?;
+        label
+            .span
+            .ok_or_else(|| AcresError::InvalidDate(s.to_string()))
+    }
+}
+
+/// How a date is qualified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Qualifier {
+    /// Made before the date, e.g. "before 1850".
+    Before,
+    /// Made after the date, e.g. "after 1900".
+    After,
+    /// Probably made at the date, e.g. "probably 1885".
+    Probably,
+}
+
+/// An artwork's date.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Dating {
+    span: Option<Span>,
+    circa: bool,
+    qualifier: Option<Qualifier>,
+    display: Option<String>,
+}
+
+impl Dating {
+    /// Creates a dating from the start and end years and the label.
+    ///
+    /// The years take precedence. If neither is given, the years are read from the label.
+    pub fn new(start: Option<i64>, end: Option<i64>, display: Option<&str>) -> Self {
+        let label = display
+            .and_then(|display| Label::parse(display).ok())
+            .unwrap_or_default();
+        let span = match (start, end) {
+            (None, None) => label.span,
+            (start, end) => Some(Span::new(
+                start.or(end).expect("one is set"),
+                end.or(start).expect("one is set"),
+            )),
+        };
+        Dating {
+            span,
+            circa: label.circa,
+            qualifier: label.qualifier,
+            display: display.map(str::to_string),
+        }
+    }
+
+    /// Creates the dating for an artwork record.
+    pub fn of(data: &Data) -> Self {
+        Dating::new(data.date_start, data.date_end, data.date_display.as_deref())
+    }
+
+    /// Returns the years the artwork was made in, if it's dated.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Returns true if the date is approximate.
+    pub fn is_circa(&self) -> bool {
+        self.circa
+    }
+
+    /// Returns how the date is qualified, if it is.
+    pub fn qualifier(&self) -> Option<Qualifier> {
+        self.qualifier
+    }
+
+    /// Returns the label as the museum displays it.
+    pub fn display(&self) -> Option<&str> {
+        self.display.as_deref()
+    }
+
+    /// Returns the centuries the date falls in, earliest first.
+    pub fn centuries(&self) -> Vec<Century> {
+        self.span.map_or_else(Vec::new, |span| {
+            let (first, last) = (Century::of(span.start), Century::of(span.end));
+            (first.key()..=last.key()).map(Century::from_key).collect()
+        })
+    }
+
+    /// Returns the eras the date falls in, earliest first.
+    pub fn eras(&self) -> Vec<Era> {
+        self.span.map_or_else(Vec::new, |span| {
+            Era::ALL
+                .into_iter()
+                .filter(|era| era.span().start <= span.end && span.start <= era.span().end)
+                .collect()
+        })
+    }
+}
+
+impl Display for Dating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(span) = self.span else {
+            return f.write_str("n.d.");
+        };
+        match self.qualifier {
+            Some(Qualifier::Before) => f.write_str("before ")?,
+            Some(Qualifier::After) => f.write_str("after ")?,
+            Some(Qualifier::Probably) => f.write_str("probably ")?,
+            None => {}
+        }
+        if self.circa {
+            f.write_str("c. ")?;
+        }
+        match self.qualifier {
+            Some(Qualifier::Before) => write!(f, "{}", Year(span.end)),
+            Some(Qualifier::After) => write!(f, "{}", Year(span.start)),
+            _ => write!(f, "{span}"),
+        }
+    }
+}
+
+impl FromStr for Dating {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let label = Label::parse(s)?;
+        Ok(Dating {
+            span: label.span,
+            circa: label.circa,
+            qualifier: label.qualifier,
+            display: Some(s.to_string()),
+        })
+    }
+}
+
+/// A century, e.g. the 19th century (1800–1899) or the 5th century BCE (499–400 BCE).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Century {
+    number: u32,
+    bce: bool,
+}
+
+impl Century {
+    /// Creates a century of the common era.
+    pub fn ce(number: u32) -> Self {
+        Century {
+            number: number.max(1),
+            bce: false,
+        }
+    }
+
+    /// Creates a century before the common era.
+    pub fn bce(number: u32) -> Self {
+        Century {
+            number: number.max(1),
+            bce: true,
+        }
+    }
+
+    /// Returns the century a year falls in.
+    pub fn of(year: i64) -> Self {
+        if year > 0 {
+            Century::ce((year / 100 + 1) as u32)
+        } else {
+            Century::bce((-year / 100 + 1) as u32)
+        }
+    }
+
+    /// Returns the years in the century.
+    pub fn span(&self) -> Span {
+        let n = i64::from(self.number);
+        if self.bce {
+            Span::new(-(n * 100 - 1), -(n - 1) * 100)
+        } else {
+            Span::new((n - 1) * 100, (n - 1) * 100 + 99)
+        }
+    }
+
+    fn key(&self) -> i64 {
+        if self.bce {
+            -i64::from(self.number)
+        } else {
+            i64::from(self.number) - 1
+        }
+    }
+
+    fn from_key(key: i64) -> Self {
+        if key < 0 {
+            Century::bce(-key as u32)
+        } else {
+            Century::ce(key as u32 + 1)
+        }
+    }
+}
+
+impl Display for Century {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let suffix = match (self.number % 10, self.number % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        write!(f, "{}{suffix} century", self.number)?;
+        if self.bce {
+            f.write_str(" BCE")?;
+        }
+        Ok(())
+    }
+}
+
+/// A broad period of art history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Era {
+    /// Before 500.
+    Ancient,
+    /// 500 to 1399.
+    Medieval,
+    /// 1400 to 1799.
+    EarlyModern,
+    /// 1800 to 1944.
+    Modern,
+    /// 1945 on.
+    Contemporary,
+}
+
+impl Era {
+    const ALL: [Era; 5] = [
+        Era::Ancient,
+        Era::Medieval,
+        Era::EarlyModern,
+        Era::Modern,
+        Era::Contemporary,
+    ];
+
+    /// Returns the era a year falls in.
+    pub fn of(year: i64) -> Self {
+        Era::ALL
+            .into_iter()
+            .find(|era| era.span().contains(year))
+            .expect("eras cover every year")
+    }
+
+    /// Returns the years in the era.
+    pub fn span(&self) -> Span {
+        match self {
+            Era::Ancient => Span::new(i64::MIN, 499),
+            Era::Medieval => Span::new(500, 1399),
+            Era::EarlyModern => Span::new(1400, 1799),
+            Era::Modern => Span::new(1800, 1944),
+            Era::Contemporary => Span::new(1945, i64::MAX),
+        }
+    }
+}
+
+impl Display for Era {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Era::Ancient => "Ancient",
+            Era::Medieval => "Medieval",
+            Era::EarlyModern => "Early modern",
+            Era::Modern => "Modern",
+            Era::Contemporary => "Contemporary",
+        })
+    }
+}
+
+struct Year(i64);
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 < 0 {
+            write!(f, "{} BCE", -self.0)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+/// What we can read from a date label.
+#[derive(Debug, Default)]
+struct Label {
+    span: Option<Span>,
+    circa: bool,
+    qualifier: Option<Qualifier>,
+}
+
+impl Label {
+    fn parse(text: &str) -> Result<Self, AcresError> {
+        let invalid = || AcresError::InvalidDate(text.to_string());
+        // Later parts of a label, e.g. "1889, printed 1920", describe other events.
+        let normalized = text
+            .split([',', ';'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+            .replace(['–', '—'], "-")
+            .replace("mid-", "mid ");
+        let mut rest = normalized.trim();
+        let mut label = Label::default();
+        loop {
+            if let Some(r) = strip_word(rest, &["circa", "ca.", "c.", "about"]) {
+                label.circa = true;
+                rest = r;
+            } else if let Some(r) = strip_word(rest, &["before"]) {
+                label.qualifier = Some(Qualifier::Before);
+                rest = r;
+            } else if let Some(r) = strip_word(rest, &["after"]) {
+                label.qualifier = Some(Qualifier::After);
+                rest = r;
+            } else if let Some(r) = strip_word(rest, &["probably", "possibly"]) {
+                label.qualifier = Some(Qualifier::Probably);
+                rest = r;
+            } else {
+                break;
+            }
+        }
+        if matches!(rest, "" | "n.d." | "n.d" | "undated") {
+            return Ok(label);
+        }
+        let (left, right) = match rest.split_once(" to ").or_else(|| rest.split_once('-')) {
+            Some((left, right)) => (left, Some(right)),
+            None => (rest, None),
+        };
+        let mut left = Term::parse(left).ok_or_else(invalid)?;
+        let span = match right {
+            None => left.span(left.bce),
+            Some(right) => {
+                let mut right = Term::parse(right).ok_or_else(invalid)?;
+                // Next to a decade, "1900s" is a decade too, e.g. "1890s-1900s".
+                match (left.kind, right.kind) {
+                    (Kind::Decade(_), Kind::Hundreds(hundreds)) => {
+                        right = right.with_kind(Kind::Decade(hundreds));
+                    }
+                    (Kind::Hundreds(hundreds), Kind::Decade(_)) => {
+                        left = left.with_kind(Kind::Decade(hundreds));
+                    }
+                    _ => {}
+                }
+                let bce = left.bce.or(right.bce);
+                let same_era = left.bce.is_none() || right.bce.is_none() || left.bce == right.bce;
+                let end = match (left.kind, right.kind) {
+                    // Abbreviated end years, e.g. "1890-95" or "510-05 BCE".
+                    (Kind::Year(start, start_digits), Kind::Year(end, end_digits))
+                        if end_digits < start_digits && same_era =>
+                    {
+                        let scale = 10_i64.pow(end_digits);
+                        let mut end = start - start % scale + end;
+                        // Crossing into the next century, e.g. "1898-02", or the one before
+                        // for years counting down, e.g. "105-10 BCE".
+                        if bce == Some(true) && end > start {
+                            end -= scale;
+                        } else if bce != Some(true) && end < start {
+                            end += scale;
+                        }
+                        right.with_kind(Kind::Year(end, start_digits)).span(bce).end
+                    }
+                    _ => right.span(right.bce.or(bce)).end,
+                };
+                let start = left.span(bce).start;
+                if start > end {
+                    return Err(invalid());
+                }
+                Span::new(start, end)
+            }
+        };
+        label.span = Some(span);
+        Ok(label)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    /// A year, along with how many digits were written.
+    Year(i64, u32),
+    Decade(i64),
+    /// A round hundred with an "s", e.g. "1800s", which is a century unless next to a decade.
+    Hundreds(i64),
+    Century(u32),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Part {
+    Early,
+    Mid,
+    Late,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Term {
+    kind: Kind,
+    part: Option<Part>,
+    bce: Option<bool>,
+}
+
+impl Term {
+    fn parse(text: &str) -> Option<Self> {
+        let mut rest = text.trim();
+        let mut bce = None;
+        if let Some(r) = strip_word(rest, &["a.d.", "ad"]) {
+            bce = Some(false);
+            rest = r;
+        }
+        for (suffix, is_bce) in [
+            ("b.c.e.", true),
+            ("bce", true),
+            ("b.c.", true),
+            ("bc", true),
+            ("c.e.", false),
+            ("ce", false),
+            ("a.d.", false),
+            ("ad", false),
+        ] {
+            if let Some(r) = rest.strip_suffix(suffix) {
+                bce = Some(is_bce);
+                rest = r.trim_end();
+                break;
+            }
+        }
+        let mut part = None;
+        for (word, p) in [
+            ("early", Part::Early),
+            ("mid", Part::Mid),
+            ("late", Part::Late),
+        ] {
+            if let Some(r) = strip_word(rest, &[word]) {
+                part = Some(p);
+                rest = r;
+            }
+        }
+        let rest = rest
+            .strip_suffix("century")
+            .or_else(|| rest.strip_suffix("c."))
+            .unwrap_or(rest)
+            .trim_end();
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        let number: i64 = rest[..digits].parse().ok()?;
+        let kind = match &rest[digits..] {
+            "" => Kind::Year(number, digits as u32),
+            "s" if number % 100 == 0 && number > 0 => Kind::Hundreds(number),
+            "s" if number % 10 == 0 => Kind::Decade(number),
+            "st" | "nd" | "rd" | "th" if number > 0 => Kind::Century(number as u32),
+            _ => return None,
+        };
+        Some(Term { kind, part, bce })
+    }
+
+    fn with_kind(self, kind: Kind) -> Self {
+        Term { kind, ..self }
+    }
+
+    fn span(&self, bce: Option<bool>) -> Span {
+        let bce = bce.unwrap_or(false);
+        let span = match (self.kind, bce) {
+            (Kind::Year(year, _), false) => Span::new(year, year),
+            (Kind::Year(year, _), true) => Span::new(-year, -year),
+            (Kind::Decade(decade), false) => Span::new(decade, decade + 9),
+            (Kind::Decade(decade), true) => Span::new(-(decade + 9), -decade),
+            (Kind::Hundreds(hundreds), false) => Century::ce((hundreds / 100 + 1) as u32).span(),
+            (Kind::Hundreds(hundreds), true) => Century::bce((hundreds / 100 + 1) as u32).span(),
+            (Kind::Century(n), false) => Century::ce(n).span(),
+            (Kind::Century(n), true) => Century::bce(n).span(),
+        };
+        // Early, mid and late are thirds of the span, whatever its length.
+        let third = (span.end - span.start + 1) / 3;
+        match self.part {
+            None => span,
+            Some(_) if third == 0 => span,
+            Some(Part::Early) => Span::new(span.start, span.start + third - 1),
+            Some(Part::Mid) => Span::new(span.start + third, span.start + 2 * third - 1),
+            Some(Part::Late) => Span::new(span.start + 2 * third, span.end),
+        }
+    }
+}
+
+/// Strips one of the words from the front of the text, if it's followed by a space or number.
+fn strip_word<'a>(text: &'a str, words: &[&str]) -> Option<&'a str> {
+    words.iter().find_map(|word| {
+        let rest = text.strip_prefix(word)?;
+        match rest.chars().next() {
+            Some(c) if c.is_whitespace() || c.is_ascii_digit() => Some(rest.trim_start()),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str) -> (i64, i64) {
+        let span: Span = text.parse().unwrap();
+        (span.start(), span.end())
+    }
+
+    #[test]
+    fn parses_spans() {
+        assert_eq!(span("1889"), (1889, 1889));
+        assert_eq!(span("1870 to 1890"), (1870, 1890));
+        assert_eq!(span("1890–95"), (1890, 1895));
+        assert_eq!(span("1898–02"), (1898, 1902));
+        assert_eq!(span("1860s"), (1860, 1869));
+        assert_eq!(span("1800s"), (1800, 1899));
+        assert_eq!(span("19th century"), (1800, 1899));
+        assert_eq!(span("late 19th century"), (1866, 1899));
+        assert_eq!(span("Mid-18th century"), (1733, 1765));
+        assert_eq!(span("19th–20th century"), (1800, 1999));
+        assert_eq!(span("500–400 BCE"), (-500, -400));
+        assert_eq!(span("5th century BCE"), (-499, -400));
+        assert_eq!(span("100 BCE–50 CE"), (-100, 50));
+        assert_eq!(span("510–05 BCE"), (-510, -505));
+        assert_eq!(span("1890s–1900s"), (1890, 1909));
+    }
+
+    #[test]
+    fn qualifiers_split_decades_and_years() {
+        assert_eq!(span("early 1880s"), (1880, 1882));
+        assert_eq!(span("mid-1850s"), (1853, 1855));
+        assert_eq!(span("late 1870s"), (1876, 1879));
+        assert_eq!(span("late 1889"), (1889, 1889));
+    }
+
+    #[test]
+    fn rejects_unparseable_spans() {
+        assert!(matches!(
+            "soon".parse::<Span>(),
+            Err(AcresError::InvalidDate(_))
+        ));
+        assert!("n.d.".parse::<Span>().is_err());
+        assert!("1890 to 1850".parse::<Span>().is_err());
+    }
+
+    #[test]
+    fn open_ends_are_left_out_of_queries() {
+        assert_eq!(
+            Span::new(i64::MIN, 1500).query(),
+            json!({ "bool": { "must": [{ "range": { "date_start": { "lte": 1500 } } }] } })
+        );
+        assert_eq!(
+            Era::Contemporary.span().query(),
+            json!({ "bool": { "must": [{ "range": { "date_end": { "gte": 1945 } } }] } })
+        );
+    }
+
+    #[test]
+    fn reads_qualifiers_from_the_label() {
+        let dating = Dating::new(Some(1840), Some(1850), Some("Before 1850"));
+        assert_eq!(dating.qualifier(), Some(Qualifier::Before));
+        assert!(!dating.is_circa());
+        assert_eq!(dating.to_string(), "before 1850");
+
+        let dating = Dating::new(None, None, Some("probably c. 1885, printed 1920"));
+        assert_eq!(dating.qualifier(), Some(Qualifier::Probably));
+        assert!(dating.is_circa());
+        assert_eq!(dating.span(), Some(Span::new(1885, 1885)));
+        assert_eq!(dating.to_string(), "probably c. 1885");
+
+        let dating: Dating = "n.d.".parse().unwrap();
+        assert_eq!(dating.span(), None);
+        assert_eq!(dating.to_string(), "n.d.");
+    }
+
+    #[test]
+    fn renders_bce_dates() {
+        assert_eq!(
+            Dating::new(Some(-500), Some(-400), None).to_string(),
+            "500–400 BCE"
+        );
+        assert_eq!(
+            Dating::new(Some(-100), Some(50), None).to_string(),
+            "100 BCE–50"
+        );
+        assert_eq!(
+            Dating::new(Some(-30), None, Some("c. 30 BCE")).to_string(),
+            "c. 30 BCE"
+        );
+    }
+
+    #[test]
+    fn buckets_into_centuries_and_eras() {
+        let dating = Dating::new(Some(-150), Some(120), None);
+        assert_eq!(
+            dating.centuries(),
+            vec![
+                Century::bce(2),
+                Century::bce(1),
+                Century::ce(1),
+                Century::ce(2)
+            ]
+        );
+        assert_eq!(dating.eras(), vec![Era::Ancient]);
+
+        let dating = Dating::new(Some(1790), Some(1811), None);
+        assert_eq!(
+            dating
+                .centuries()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["18th century", "19th century"]
+        );
+        assert_eq!(dating.eras(), vec![Era::EarlyModern, Era::Modern]);
+        assert_eq!(Century::of(-450).to_string(), "5th century BCE");
+        assert_eq!(Century::ce(21).to_string(), "21st century");
+        assert_eq!(Century::ce(12).to_string(), "12th century");
+    }
+}
@@ -0,0 +1,807 @@
+//! Structured dimensions for artworks.
+//!
+//! Artworks have their dimensions as label text, such as "76.2 × 101.6 cm (30 × 40 in.)" or
+//! "Image: 24.8 × 19.4 cm; sheet: 35.3 × 27.9 cm", and, for many records, as `dimensions_detail`
+//! already in centimeters. [`Dimensions`] reads either into [`Measurement`]s, one per part of the
+//! work, with every length in centimeters.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use acres::dimensions::{Dimensions, Part};
+//!
+//! # fn main() -> Result<()> {
+//! let dimensions: Dimensions =
+//!     "Image: 24.8 × 19.4 cm (9 3/4 × 7 5/8 in.); sheet: 14 × 11 in.".parse()?;
+//! let sheet = dimensions.part(&Part::Sheet).expect("has a sheet");
+//! assert_eq!(sheet.height, Some(35.56));
+//! assert_eq!(sheet.width, Some(27.94));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Labels list height first, then width, then depth, unless they say otherwise with "H.", "W.",
+//! "D." or "Diam.". Where both metric and imperial lengths are given, the metric ones are used.
+//!
+//! To show several works side by side at their true relative size, a [`Scale`] fits the largest
+//! of them into the space you have and sizes the rest to match.
+
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::AcresError;
+
+/// A unit of length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Unit {
+    /// Millimeters.
+    Millimeter,
+    /// Centimeters.
+    Centimeter,
+    /// Meters.
+    Meter,
+    /// Inches.
+    Inch,
+    /// Feet.
+    Foot,
+}
+
+impl Unit {
+    /// Returns how many centimeters one of the unit is.
+    pub fn centimeters(&self) -> f64 {
+        match self {
+            Unit::Millimeter => 0.1,
+            Unit::Centimeter => 1.0,
+            Unit::Meter => 100.0,
+            Unit::Inch => 2.54,
+            Unit::Foot => 30.48,
+        }
+    }
+
+    /// Converts a length in this unit to centimeters.
+    pub fn to_cm(&self, length: f64) -> f64 {
+        round(length * self.centimeters())
+    }
+
+    /// Converts a length in centimeters to this unit.
+    pub fn from_cm(&self, cm: f64) -> f64 {
+        round(cm / self.centimeters())
+    }
+
+    fn is_metric(&self) -> bool {
+        matches!(self, Unit::Millimeter | Unit::Centimeter | Unit::Meter)
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Unit::Millimeter => "mm",
+            Unit::Centimeter => "cm",
+            Unit::Meter => "m",
+            Unit::Inch => "in.",
+            Unit::Foot => "ft.",
+        })
+    }
+}
+
+/// The part of a work a measurement is of.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Part {
+    /// The whole work, e.g. "overall".
+    Overall,
+    /// The printed or drawn image.
+    Image,
+    /// The sheet or paper the image is on.
+    Sheet,
+    /// The mount or mat.
+    Mount,
+    /// The printing plate, or its impression.
+    Plate,
+    /// The frame.
+    Frame,
+    /// Anything else, as labeled.
+    Other(String),
+}
+
+impl Part {
+    fn from_label(label: &str) -> Self {
+        let label = label.trim();
+        match label.to_lowercase().as_str() {
+            "overall" => Part::Overall,
+            "image" | "image/sheet" => Part::Image,
+            "sheet" | "paper" | "primary support" => Part::Sheet,
+            "mount" | "mounted" | "mat" | "secondary support" => Part::Mount,
+            "plate" | "platemark" | "plate mark" => Part::Plate,
+            "frame" | "framed" | "with frame" => Part::Frame,
+            _ => Part::Other(label.to_string()),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Part::Overall => "overall",
+            Part::Image => "image",
+            Part::Sheet => "sheet",
+            Part::Mount => "mount",
+            Part::Plate => "plate",
+            Part::Frame => "frame",
+            Part::Other(label) => label,
+        })
+    }
+}
+
+/// The size of one part of a work, in centimeters.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    /// The part measured, if the label says.
+    pub part: Option<Part>,
+    /// Height.
+    pub height: Option<f64>,
+    /// Width.
+    pub width: Option<f64>,
+    /// Depth.
+    pub depth: Option<f64>,
+    /// Diameter.
+    pub diameter: Option<f64>,
+}
+
+impl Measurement {
+    /// Returns the width and height the part takes up face on.
+    ///
+    /// Round works without a height or width take up their diameter.
+    pub fn extent(&self) -> Option<(f64, f64)> {
+        let width = self.width.or(self.diameter)?;
+        let height = self.height.or(self.diameter)?;
+        Some((width, height))
+    }
+
+    /// Returns the area the part takes up face on, in square centimeters.
+    pub fn area(&self) -> Option<f64> {
+        match (self.width, self.height, self.diameter) {
+            (Some(width), Some(height), _) => Some(width * height),
+            (_, _, Some(diameter)) => Some(std::f64::consts::PI * (diameter / 2.0).powi(2)),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the part fits within another, say a case or a wall, without turning it.
+    ///
+    /// Lengths either one doesn't give are taken to fit.
+    pub fn fits_within(&self, other: &Measurement) -> bool {
+        let fits = |length: Option<f64>, space: Option<f64>| match (length, space) {
+            (Some(length), Some(space)) => length <= space,
+            _ => true,
+        };
+        let (width, height) = self.extent().unzip();
+        let (space_width, space_height) = other.extent().unzip();
+        fits(width, space_width) && fits(height, space_height) && fits(self.depth, other.depth)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.height.is_none()
+            && self.width.is_none()
+            && self.depth.is_none()
+            && self.diameter.is_none()
+    }
+
+    fn set(&mut self, axis: Axis, cm: f64) {
+        let length = match axis {
+            Axis::Height => &mut self.height,
+            Axis::Width => &mut self.width,
+            Axis::Depth => &mut self.depth,
+            Axis::Diameter => &mut self.diameter,
+        };
+        *length = Some(cm);
+    }
+}
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(part) = &self.part {
+            write!(f, "{part}: ")?;
+        }
+        let lengths: Vec<String> = [self.height, self.width, self.depth]
+            .into_iter()
+            .flatten()
+            .map(|cm| cm.to_string())
+            .collect();
+        match (lengths.is_empty(), self.diameter) {
+            (true, Some(diameter)) => write!(f, "diam. {diameter} cm"),
+            (false, Some(diameter)) => write!(f, "{} cm, diam. {diameter} cm", lengths.join(" × ")),
+            _ => write!(f, "{} cm", lengths.join(" × ")),
+        }
+    }
+}
+
+/// An artwork's dimensions, part by part.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Dimensions {
+    parts: Vec<Measurement>,
+}
+
+impl Dimensions {
+    /// Creates dimensions from measurements.
+    pub fn new(parts: Vec<Measurement>) -> Self {
+        Dimensions { parts }
+    }
+
+    /// Reads an artwork's dimensions from its `dimensions_detail` field, or its `dimensions`
+    /// label if the detail is missing.
+    pub fn of(record: &Value) -> Option<Self> {
+        let detail: Vec<Measurement> = record
+            .get("dimensions_detail")
+            .and_then(Value::as_array)
+            .map(|details| details.iter().filter_map(detail).collect())
+            .unwrap_or_default();
+        if !detail.is_empty() {
+            return Some(Dimensions { parts: detail });
+        }
+        record
+            .get("dimensions")
+            .and_then(Value::as_str)
+            .and_then(|text| text.parse().ok())
+    }
+
+    /// Returns the measurements, in the order given.
+    pub fn parts(&self) -> &[Measurement] {
+        &self.parts
+    }
+
+    /// Returns the measurement of a part, if there is one.
+    pub fn part(&self, part: &Part) -> Option<&Measurement> {
+        self.parts
+            .iter()
+            .find(|measurement| measurement.part.as_ref() == Some(part))
+    }
+
+    /// Returns the measurement that best stands for the whole work.
+    ///
+    /// That's the overall or unlabeled measurement, if there is one, and the first otherwise.
+    pub fn primary(&self) -> Option<&Measurement> {
+        self.parts
+            .iter()
+            .find(|measurement| matches!(measurement.part, None | Some(Part::Overall)))
+            .or(self.parts.first())
+    }
+}
+
+impl Display for Dimensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self.parts.iter().map(|part| part.to_string()).collect();
+        f.write_str(&parts.join("; "))
+    }
+}
+
+impl FromStr for Dimensions {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<Measurement> = vec![];
+        for segment in s
+            .split([';', '\n'])
+            .filter(|segment| !segment.trim().is_empty())
+        {
+            let Some(mut measurement) = parse_segment(segment) else {
+                continue;
+            };
+            // "H. 30 cm; Diam. 12 cm" describes one part across segments.
+            if measurement.part.is_none()
+                && let Some(last) = parts.last_mut()
+                && last.part.is_none()
+                && [
+                    (last.height, measurement.height),
+                    (last.width, measurement.width),
+                    (last.depth, measurement.depth),
+                    (last.diameter, measurement.diameter),
+                ]
+                .iter()
+                .all(|pair| matches!(pair, (None, _) | (_, None)))
+            {
+                last.height = last.height.or(measurement.height.take());
+                last.width = last.width.or(measurement.width.take());
+                last.depth = last.depth.or(measurement.depth.take());
+                last.diameter = last.diameter.or(measurement.diameter.take());
+                continue;
+            }
+            parts.push(measurement);
+        }
+        if parts.is_empty() {
+            return Err(AcresError::InvalidDimensions(s.to_string()));
+        }
+        Ok(Dimensions { parts })
+    }
+}
+
+/// Sizes works at the same scale, so they can be shown at their true relative size.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::dimensions::{Dimensions, Scale};
+///
+/// # fn main() -> Result<()> {
+/// let large: Dimensions = "200 × 300 cm".parse()?;
+/// let small: Dimensions = "50 × 40 cm".parse()?;
+/// let parts = [large.primary().unwrap(), small.primary().unwrap()];
+///
+/// let scale = Scale::fit(parts, 600, 400).expect("both have an extent");
+/// assert_eq!(scale.pixels(parts[0]), Some((600, 400)));
+/// assert_eq!(scale.pixels(parts[1]), Some((80, 100)));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Scale {
+    per_cm: f64,
+}
+
+impl Scale {
+    /// Creates a scale of so many pixels (or chars, or anything else) per centimeter.
+    pub fn new(per_cm: f64) -> Self {
+        Scale { per_cm }
+    }
+
+    /// Returns the largest scale at which every measurement fits within the width and height.
+    ///
+    /// Measurements without an extent are left out. If none have one, there's no scale.
+    pub fn fit<'a>(
+        measurements: impl IntoIterator<Item = &'a Measurement>,
+        width: u32,
+        height: u32,
+    ) -> Option<Self> {
+        let (widest, tallest) = measurements
+            .into_iter()
+            .filter_map(Measurement::extent)
+            .filter(|&(w, h)| w > 0.0 && h > 0.0)
+            .fold(None, |most: Option<(f64, f64)>, (w, h)| {
+                Some(most.map_or((w, h), |(most_w, most_h)| (most_w.max(w), most_h.max(h))))
+            })?;
+        Some(Scale::new(
+            (f64::from(width) / widest).min(f64::from(height) / tallest),
+        ))
+    }
+
+    /// Returns how many pixels stand for a centimeter.
+    pub fn per_cm(&self) -> f64 {
+        self.per_cm
+    }
+
+    /// Returns the width and height of a measurement at this scale, at least 1 each.
+    pub fn pixels(&self, measurement: &Measurement) -> Option<(u32, u32)> {
+        let (width, height) = measurement.extent()?;
+        let scale = |cm: f64| ((cm * self.per_cm).round() as u32).max(1);
+        Some((scale(width), scale(height)))
+    }
+
+    /// Returns the IIIF size to request for a measurement's image at this scale.
+    pub fn image_size(&self, measurement: &Measurement) -> Option<iiif::Size> {
+        self.pixels(measurement)
+            .map(|(width, height)| iiif::Size::BestFit(width, height))
+    }
+}
+
+/// A direction a length is measured in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Axis {
+    Height,
+    Width,
+    Depth,
+    Diameter,
+}
+
+impl Axis {
+    fn from_word(word: &str) -> Option<Self> {
+        match word {
+            "h" | "ht" | "height" => Some(Axis::Height),
+            "w" | "wd" | "width" | "l" | "length" => Some(Axis::Width),
+            "d" | "dp" | "depth" => Some(Axis::Depth),
+            "diam" | "dia" | "diameter" => Some(Axis::Diameter),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Fraction(f64),
+    Times,
+    Unit(Unit),
+    Axis(Axis),
+    Open,
+    Close,
+}
+
+/// Lengths read together, like "30 × 40 in.", before they're converted.
+#[derive(Debug, Default)]
+struct Group {
+    lengths: Vec<(Option<Axis>, f64, Option<Unit>)>,
+    nested: bool,
+}
+
+impl Group {
+    fn unit(&self) -> Option<Unit> {
+        self.lengths.iter().rev().find_map(|(_, _, unit)| *unit)
+    }
+}
+
+/// Reads a segment like "Sheet: 38.1 × 27.9 cm (15 × 11 in.)".
+fn parse_segment(segment: &str) -> Option<Measurement> {
+    let (part, text) = match segment.split_once(':') {
+        Some((label, rest))
+            if !label.chars().any(|c| c.is_ascii_digit())
+                && Axis::from_word(&word(label)).is_none() =>
+        {
+            (Some(Part::from_label(label)), rest)
+        }
+        _ => (None, segment),
+    };
+    let groups = groups(&tokens(text));
+    let group = groups
+        .iter()
+        .find(|group| !group.nested && group.unit().is_some_and(|unit| unit.is_metric()))
+        .or_else(|| {
+            groups
+                .iter()
+                .find(|group| group.unit().is_some_and(|unit| unit.is_metric()))
+        })
+        .or_else(|| groups.first())?;
+    let unit = group.unit()?;
+    let mut measurement = Measurement {
+        part,
+        ..Default::default()
+    };
+    let mut order = [Axis::Height, Axis::Width, Axis::Depth].into_iter();
+    for &(axis, length, own_unit) in &group.lengths {
+        let axis = match axis {
+            Some(axis) => axis,
+            None => order.by_ref().find(|&axis| {
+                group
+                    .lengths
+                    .iter()
+                    .all(|(named, _, _)| *named != Some(axis))
+            })?,
+        };
+        measurement.set(axis, own_unit.unwrap_or(unit).to_cm(length));
+    }
+    (!measurement.is_empty()).then_some(measurement)
+}
+
+fn tokens(text: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            '0'..='9' => {
+                let mut number = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit() || **c == '.') {
+                    number.push(c);
+                    chars.next();
+                }
+                let number = number.trim_end_matches('.');
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    let mut denominator = String::new();
+                    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                        denominator.push(c);
+                        chars.next();
+                    }
+                    if let (Ok(numerator), Ok(denominator)) =
+                        (number.parse::<f64>(), denominator.parse::<f64>())
+                        && denominator > 0.0
+                    {
+                        tokens.push(Token::Fraction(numerator / denominator));
+                    }
+                } else if let Ok(number) = number.parse() {
+                    tokens.push(Token::Number(number));
+                }
+            }
+            '×' | '*' => {
+                chars.next();
+                tokens.push(Token::Times);
+            }
+            '(' | '[' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' | ']' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' | '″' => {
+                chars.next();
+                tokens.push(Token::Unit(Unit::Inch));
+            }
+            '′' => {
+                chars.next();
+                tokens.push(Token::Unit(Unit::Foot));
+            }
+            c if c.is_alphabetic() => {
+                let mut text = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_alphabetic()) {
+                    text.push(c);
+                    chars.next();
+                }
+                let text = text.to_lowercase();
+                let token = match text.as_str() {
+                    "x" => Some(Token::Times),
+                    "mm" => Some(Token::Unit(Unit::Millimeter)),
+                    "cm" => Some(Token::Unit(Unit::Centimeter)),
+                    "m" => Some(Token::Unit(Unit::Meter)),
+                    "in" | "inch" | "inches" => Some(Token::Unit(Unit::Inch)),
+                    "ft" | "foot" | "feet" => Some(Token::Unit(Unit::Foot)),
+                    word => Axis::from_word(word).map(Token::Axis),
+                };
+                tokens.extend(token);
+            }
+            _ => {
+                chars.next();
+            }
+        }
+    }
+    tokens
+}
+
+fn groups(tokens: &[Token]) -> Vec<Group> {
+    let mut groups = vec![];
+    let mut group = Group::default();
+    let mut depth = 0usize;
+    let mut axis = None;
+    // Set once a unit ends a run of lengths; another length then starts a new group, unless
+    // it's joined on with "×", as in "12 in. × 9 in.".
+    let mut ended = false;
+    // Set when a length starts a new group right after feet, so that inches can still join
+    // them, as in "2 ft. 6 in.".
+    let mut after_feet = false;
+    let finish = |groups: &mut Vec<Group>, group: &mut Group| {
+        let group = std::mem::take(group);
+        if !group.lengths.is_empty() {
+            groups.push(group);
+        }
+    };
+    // Starts a new group if a unit ended the current one, and returns whether that was feet.
+    let split = |groups: &mut Vec<Group>, group: &mut Group, ended: &mut bool| {
+        let mut after_feet = false;
+        if *ended {
+            after_feet = group.unit() == Some(Unit::Foot);
+            finish(groups, group);
+            *ended = false;
+        }
+        after_feet
+    };
+    for token in tokens {
+        match *token {
+            Token::Number(number) => {
+                after_feet = split(&mut groups, &mut group, &mut ended);
+                if group.lengths.is_empty() {
+                    group.nested = depth > 0;
+                }
+                group.lengths.push((axis.take(), number, None));
+            }
+            Token::Fraction(fraction) => match group.lengths.last_mut() {
+                // "36 5/8"
+                Some((_, whole, None)) if !ended && axis.is_none() && whole.fract() == 0.0 => {
+                    *whole += fraction;
+                }
+                _ => {
+                    after_feet = split(&mut groups, &mut group, &mut ended);
+                    if group.lengths.is_empty() {
+                        group.nested = depth > 0;
+                    }
+                    group.lengths.push((axis.take(), fraction, None));
+                }
+            },
+            Token::Times => ended = false,
+            Token::Unit(unit) => {
+                if std::mem::take(&mut after_feet)
+                    && unit == Unit::Inch
+                    && let [(None, inches, None)] = group.lengths[..]
+                    && let Some(mut feet) = groups.pop()
+                    && let Some((_, length, _)) = feet.lengths.last_mut()
+                {
+                    *length += inches / 12.0;
+                    group = feet;
+                    ended = true;
+                    continue;
+                }
+                for length in group.lengths.iter_mut().rev() {
+                    if length.2.is_some() {
+                        break;
+                    }
+                    length.2 = Some(unit);
+                }
+                ended = true;
+            }
+            Token::Axis(named) => axis = Some(named),
+            Token::Open | Token::Close => {
+                finish(&mut groups, &mut group);
+                ended = false;
+                depth = match token {
+                    Token::Open => depth + 1,
+                    _ => depth.saturating_sub(1),
+                };
+            }
+        }
+    }
+    finish(&mut groups, &mut group);
+    groups
+}
+
+/// Reads an entry of `dimensions_detail`, which is already in centimeters.
+fn detail(entry: &Value) -> Option<Measurement> {
+    let cm = |key: &str| {
+        entry
+            .get(key)
+            .or_else(|| entry.get(format!("{key}_cm")))
+            .and_then(Value::as_f64)
+            .filter(|cm| *cm > 0.0)
+    };
+    let measurement = Measurement {
+        part: entry
+            .get("clarification")
+            .and_then(Value::as_str)
+            .filter(|label| !label.trim().is_empty())
+            .map(Part::from_label),
+        height: cm("height"),
+        width: cm("width"),
+        depth: cm("depth"),
+        diameter: cm("diameter"),
+    };
+    (!measurement.is_empty()).then_some(measurement)
+}
+
+/// Returns a label's first word, lowercased and without punctuation.
+fn word(label: &str) -> String {
+    label.trim().trim_end_matches('.').to_lowercase()
+}
+
+/// Rounds to a hundredth of a unit, which is finer than any label.
+fn round(length: f64) -> f64 {
+    (length * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn dimensions(text: &str) -> Vec<Measurement> {
+        text.parse::<Dimensions>().unwrap().parts
+    }
+
+    fn measurement(
+        part: Option<Part>,
+        height: Option<f64>,
+        width: Option<f64>,
+        depth: Option<f64>,
+        diameter: Option<f64>,
+    ) -> Measurement {
+        Measurement {
+            part,
+            height,
+            width,
+            depth,
+            diameter,
+        }
+    }
+
+    #[test]
+    fn parses_labels() {
+        assert_eq!(
+            dimensions("76.2 × 101.6 cm (30 × 40 in.)"),
+            vec![measurement(None, Some(76.2), Some(101.6), None, None)]
+        );
+        assert_eq!(
+            dimensions("30.5 x 22.9 x 15.2 cm"),
+            vec![measurement(None, Some(30.5), Some(22.9), Some(15.2), None)]
+        );
+        assert_eq!(
+            dimensions("(29 × 36 5/8 in.)"),
+            vec![measurement(None, Some(73.66), Some(93.03), None, None)]
+        );
+        assert_eq!(
+            dimensions("12 in. × 9 in."),
+            vec![measurement(None, Some(30.48), Some(22.86), None, None)]
+        );
+        assert_eq!(
+            dimensions("Diam. 12.7 cm (5 in.)"),
+            vec![measurement(None, None, None, None, Some(12.7))]
+        );
+        assert_eq!(
+            dimensions("H. 1.2 m; Diam.: 350 mm"),
+            vec![measurement(None, Some(120.0), None, None, Some(35.0))]
+        );
+        assert_eq!(
+            dimensions("W. 20 × H. 30 cm"),
+            vec![measurement(None, Some(30.0), Some(20.0), None, None)]
+        );
+    }
+
+    #[test]
+    fn combines_feet_and_inches() {
+        assert_eq!(
+            dimensions("2 ft. 6 in. × 3 ft."),
+            vec![measurement(None, Some(76.2), Some(91.44), None, None)]
+        );
+        assert_eq!(
+            dimensions("H. 5 ft. 6 1/2 in. × W. 2 ft."),
+            vec![measurement(None, Some(168.91), Some(60.96), None, None)]
+        );
+        assert_eq!(
+            dimensions("3 ft. × 6 in."),
+            vec![measurement(None, Some(91.44), Some(15.24), None, None)]
+        );
+    }
+
+    #[test]
+    fn parses_parts() {
+        let parts = dimensions(
+            "Image: 24.8 × 19.4 cm (9 3/4 × 7 5/8 in.); sheet: 35.3 × 27.9 cm\nFramed: 60 × 50 × 4 cm; Box: 5 × 5 cm",
+        );
+        let labels: Vec<Option<Part>> = parts.iter().map(|part| part.part.clone()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                Some(Part::Image),
+                Some(Part::Sheet),
+                Some(Part::Frame),
+                Some(Part::Other("Box".into()))
+            ]
+        );
+        assert_eq!(parts[2].depth, Some(4.0));
+    }
+
+    #[test]
+    fn rejects_labels_without_lengths() {
+        assert!(matches!(
+            "Dimensions variable".parse::<Dimensions>(),
+            Err(AcresError::InvalidDimensions(_))
+        ));
+        assert!("30 × 40".parse::<Dimensions>().is_err());
+    }
+
+    #[test]
+    fn prefers_dimensions_detail() {
+        let record = json!({
+            "dimensions": "76.2 × 101.6 cm (30 × 40 in.)",
+            "dimensions_detail": [
+                { "height": 76, "width": 102, "depth": null, "diameter": null, "clarification": null },
+                { "height": 90, "width": 115, "depth": 8, "diameter": null, "clarification": "Frame" }
+            ]
+        });
+        let dimensions = Dimensions::of(&record).unwrap();
+        assert_eq!(
+            dimensions.primary(),
+            Some(&measurement(None, Some(76.0), Some(102.0), None, None))
+        );
+        assert_eq!(dimensions.part(&Part::Frame).unwrap().depth, Some(8.0));
+
+        let record = json!({ "dimensions": "76.2 × 101.6 cm", "dimensions_detail": [] });
+        assert_eq!(
+            Dimensions::of(&record).unwrap().primary().unwrap().width,
+            Some(101.6)
+        );
+        assert!(Dimensions::of(&json!({})).is_none());
+    }
+
+    #[test]
+    fn compares_measurements() {
+        let painting = measurement(None, Some(76.2), Some(101.6), Some(3.0), None);
+        let case = measurement(None, Some(80.0), Some(120.0), Some(10.0), None);
+        let plate = measurement(None, None, None, None, Some(30.0));
+
+        assert!(painting.fits_within(&case));
+        assert!(!case.fits_within(&painting));
+        assert!(plate.fits_within(&case));
+        assert_eq!(plate.extent(), Some((30.0, 30.0)));
+        assert!(painting.area().unwrap() > plate.area().unwrap());
+        assert_eq!(Unit::Inch.from_cm(101.6), 40.0);
+        assert_eq!(painting.to_string(), "76.2 × 101.6 × 3 cm");
+    }
+}
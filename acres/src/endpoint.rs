@@ -0,0 +1,144 @@
+//! Typed endpoints.
+
+use std::fmt::Display;
+
+use anyhow::Context;
+use bytes::{Buf, Bytes};
+use serde::de::DeserializeOwned;
+
+use crate::AcresError;
+
+/// An endpoint of the [AIC public APIs].
+///
+/// Every request type knows how to render itself as a URL (via [`Display`]), which HTTP method to
+/// use, and what type it expects back. That lets [`Api::send()`] hand you the correct response
+/// type without you having to guess it.
+///
+/// ```rust
+/// use acres::{Api, Endpoint, artworks::{Artwork, request::artwork}};
+///
+/// fn response_of<E: Endpoint>(_: &E) -> &'static str {
+///     std::any::type_name::<E::Response>()
+/// }
+///
+/// let request = artwork::Request::new(Api::new().base_uri(), 4);
+/// assert_eq!(response_of(&request), std::any::type_name::<Artwork>());
+/// ```
+///
+/// [AIC public APIs]: https://api.artic.edu/docs/#introduction
+/// [`Api::send()`]: struct.Api.html#method.send
+pub trait Endpoint: Display {
+    /// The type we get back from this endpoint.
+    type Response: TryFrom<Bytes, Error = AcresError>;
+
+    /// The HTTP method used to request this endpoint.
+    const METHOD: reqwest::Method = reqwest::Method::GET;
+}
+
+/// Parses a JSON response body, keeping the serde error as the cause.
+pub(crate) fn from_json<T: DeserializeOwned>(value: Bytes) -> Result<T, AcresError> {
+    let parsed = serde_json::from_reader(value.reader())
+        .with_context(|| format!("parsing {}", std::any::type_name::<T>()))?;
+    Ok(parsed)
+}
+
+/// Implements `TryFrom<Bytes>` for response types that are parsed from JSON.
+macro_rules! json_response {
+    ($($response:ty),+ $(,)?) => {
+        $(
+            impl TryFrom<bytes::Bytes> for $response {
+                type Error = $crate::AcresError;
+
+                fn try_from(value: bytes::Bytes) -> Result<Self, Self::Error> {
+                    $crate::endpoint::from_json(value)
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use json_response;
+
+impl Endpoint for crate::agents::request::agent::Request {
+    type Response = crate::agents::Agent;
+}
+
+impl Endpoint for crate::agents::request::agents::Request {
+    type Response = crate::agents::Agents;
+}
+
+impl Endpoint for crate::artworks::request::artwork::Request {
+    type Response = crate::artworks::Artwork;
+}
+
+impl Endpoint for crate::artworks::request::artworks::Request {
+    type Response = crate::artworks::Artworks;
+}
+
+impl Endpoint for crate::artworks::request::manifest::Request {
+    type Response = crate::artworks::Manifest;
+}
+
+impl Endpoint for crate::artworks::request::search::Request {
+    type Response = crate::artworks::Search;
+}
+
+impl Endpoint for crate::exhibitions::request::search::Request {
+    type Response = crate::exhibitions::Exhibitions;
+}
+
+impl Endpoint for crate::galleries::request::gallery::Request {
+    type Response = crate::galleries::Gallery;
+}
+
+impl Endpoint for crate::galleries::request::galleries::Request {
+    type Response = crate::galleries::Galleries;
+}
+
+impl Endpoint for crate::sounds::request::sound::Request {
+    type Response = crate::sounds::Sound;
+}
+
+impl Endpoint for crate::sounds::request::sounds::Request {
+    type Response = crate::sounds::Sounds;
+}
+
+impl Endpoint for crate::tours::request::tour::Request {
+    type Response = crate::tours::Tour;
+}
+
+impl Endpoint for crate::tours::request::tours::Request {
+    type Response = crate::tours::Tours;
+}
+
+impl Endpoint for crate::vocabulary::request::artwork_type::Request {
+    type Response = crate::vocabulary::ArtworkType;
+}
+
+impl Endpoint for crate::vocabulary::request::artwork_types::Request {
+    type Response = crate::vocabulary::ArtworkTypes;
+}
+
+impl Endpoint for crate::vocabulary::request::category_term::Request {
+    type Response = crate::vocabulary::CategoryTerm;
+}
+
+impl Endpoint for crate::vocabulary::request::category_terms::Request {
+    type Response = crate::vocabulary::CategoryTerms;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_keep_the_serde_error() {
+        let error = crate::tours::Tour::try_from(Bytes::from_static(b"{\"data\":")).unwrap_err();
+
+        let AcresError::Unexpected(error) = error else {
+            panic!("expected an unexpected error, got {error}");
+        };
+        assert!(error.root_cause().is::<serde_json::Error>());
+        assert!(error.to_string().contains("Tour"));
+    }
+}
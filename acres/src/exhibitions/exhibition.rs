@@ -0,0 +1,143 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AcresError,
+    artworks::{Pagination, SearchQueryParams},
+    endpoint::json_response,
+};
+
+/// A page of exhibitions, as returned by [`GET /exhibitions/search`].
+///
+/// [`GET /exhibitions/search`]: https://api.artic.edu/docs/#get-exhibitions-search
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Exhibitions {
+    /// Data.
+    pub data: Vec<Data>,
+    /// Pagination.
+    #[serde(default)]
+    pub pagination: Pagination,
+}
+
+json_response!(Exhibitions);
+
+/// Exhibition record.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Data {
+    /// ID.
+    pub id: u64,
+    /// Title.
+    #[serde(default)]
+    pub title: String,
+    /// IDs of the artworks shown.
+    #[serde(default)]
+    pub artwork_ids: Vec<u64>,
+    /// ID of the gallery the exhibition was held in.
+    #[serde(default)]
+    pub gallery_id: Option<u64>,
+    /// Title of the gallery the exhibition was held in.
+    #[serde(default)]
+    pub gallery_title: Option<String>,
+}
+
+/// A [`GET /exhibitions/search`] request.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::{Api, exhibitions::request::search};
+///
+/// # fn main() -> Result<()> {
+/// let request = search::Builder::new()
+///     .base_uri(Api::new().base_uri())
+///     .query(Some(r#"{"term": {"artwork_ids": 27992}}"#.to_string()))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`GET /exhibitions/search`]: https://api.artic.edu/docs/#get-exhibitions-search
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SearchRequest(String);
+
+impl Display for SearchRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+/// A [`GET /exhibitions/search`] request builder.
+///
+/// [`GET /exhibitions/search`]: https://api.artic.edu/docs/#get-exhibitions-search
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    base_uri: String,
+    q: Option<String>,
+    query: Option<String>,
+    from: Option<u32>,
+    size: Option<u32>,
+    fields: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Sets the base URI.
+    pub fn base_uri(mut self, base_uri: String) -> Self {
+        self.base_uri = base_uri;
+        self
+    }
+
+    /// Sets the search query.
+    pub fn q(mut self, q: Option<String>) -> Self {
+        self.q = q;
+        self
+    }
+
+    /// Sets the search more complex query.
+    pub fn query(mut self, query: Option<String>) -> Self {
+        self.query = query;
+        self
+    }
+
+    /// Sets the from parameter.
+    pub fn from(mut self, from: Option<u32>) -> Self {
+        self.from = from;
+        self
+    }
+
+    /// Sets the size parameter.
+    pub fn size(mut self, size: Option<u32>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the exhibition fields to retrieve.
+    pub fn fields(mut self, fields: Option<Vec<String>>) -> Self {
+        if let Some(fields) = fields {
+            self.fields = fields;
+        }
+        self
+    }
+
+    /// Builds exhibitions search.
+    pub fn build(&self) -> Result<SearchRequest, AcresError> {
+        let query_params = SearchQueryParams {
+            q: self.q.clone(),
+            query: self.query.clone(),
+            sort: None,
+            from: self.from,
+            size: self.size,
+            facets: None,
+            fields: self.fields.clone(),
+        };
+        query_params.valid()?;
+        Ok(SearchRequest(format!(
+            "{}/exhibitions/search{}",
+            self.base_uri, query_params
+        )))
+    }
+}
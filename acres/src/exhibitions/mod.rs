@@ -0,0 +1,19 @@
+//! Modules and types for working with the [Exhibitions Collection].
+//!
+//! [Exhibitions Collection]: https://api.artic.edu/docs/#exhibitions
+
+mod exhibition;
+
+pub use exhibition::{Data, Exhibitions};
+
+/// Modules for requesting items from the [Exhibitions Collection].
+///
+/// [Exhibitions Collection]: https://api.artic.edu/docs/#exhibitions
+pub mod request {
+    /// A [`GET /exhibitions/search`] request.
+    ///
+    /// [`GET /exhibitions/search`]: https://api.artic.edu/docs/#get-exhibitions-search
+    pub mod search {
+        pub use crate::exhibitions::exhibition::{Builder, SearchRequest as Request};
+    }
+}
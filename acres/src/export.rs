@@ -0,0 +1,674 @@
+//! Tabular export of artwork records.
+//!
+//! An [`Exporter`] flattens artwork records into one row per artwork and one column per field
+//! path, and writes them out as CSV, NDJSON or (with the `parquet` feature) Parquet. Field paths
+//! use dots to reach into nested objects and arrays, e.g. `color.h`, `thumbnail.alt_text` or
+//! `artist_ids.0`.
+//!
+//! Records can come from paging through a [listing] or a [search], which goes through the
+//! [`Api`] and its response cache, or from a local [`JsonStore`].
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! # use serde_json::json;
+//! use acres::{
+//!     artworks::request::search,
+//!     export::{Exporter, Format},
+//! };
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! # let mock_server = wiremock::MockServer::start().await;
+//! # let mock_uri = format!("{}/api/v1", mock_server.uri());
+//! # wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+//! #     .and(wiremock::matchers::query_param("from", "0"))
+//! #     .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({"data": [
+//! #         {"id": 16568, "title": "Water Lilies", "color": {"h": 196, "s": 27, "l": 46}}
+//! #     ]})))
+//! #     .mount(&mock_server)
+//! #     .await;
+//! let api = acres::Api::new();
+//! # let api = acres::Api::builder().base_uri(&mock_uri).use_cache(false).build();
+//! let mut exporter = Exporter::new(Format::Csv, ["id", "title", "color.h"], Vec::new())?;
+//! exporter
+//!     .search(&api, search::Builder::new().q(Some("monet".into())), Some(10))
+//!     .await?;
+//! let csv = String::from_utf8(exporter.finish()?)?;
+//! assert_eq!(csv, "id,title,color.h\n16568,Water Lilies,196\n");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [listing]: ../artworks/request/artworks/index.html
+//! [search]: ../artworks/request/search/index.html
+//! [`Api`]: ../struct.Api.html
+//! [`JsonStore`]: ../sync/struct.JsonStore.html
+
+use std::{fmt::Display, io::Write, str::FromStr};
+
+use anyhow::{Context, anyhow};
+use serde_json::{Map, Value};
+
+use crate::{
+    AcresError, Api,
+    artworks::request::{artworks, search},
+    sync::JsonStore,
+};
+
+/// How many records to request at a time when paging.
+const PAGE_SIZE: u32 = 100;
+
+/// An output format.
+///
+/// You can create one from a string.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::export::Format;
+///
+/// # fn main() -> Result<()> {
+/// let format: Format = "ndjson".parse()?;
+/// assert_eq!(format, Format::Ndjson);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Format {
+    /// Comma-separated values with a header row.
+    #[default]
+    Csv,
+    /// One JSON object per line, keyed by field path.
+    Ndjson,
+    /// Apache Parquet, with column types inferred from the values.
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Csv => f.write_str("csv"),
+            Format::Ndjson => f.write_str("ndjson"),
+            #[cfg(feature = "parquet")]
+            Format::Parquet => f.write_str("parquet"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "ndjson" => Ok(Format::Ndjson),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(Format::Parquet),
+            _ => Err(AcresError::Unexpected(anyhow!(
+                "{} is not a supported export format",
+                s
+            ))),
+        }
+    }
+}
+
+/// A dotted path to a (possibly nested) field, such as `color.h`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FieldPath {
+    path: String,
+}
+
+impl FieldPath {
+    /// Returns the top-level field, which is what needs to be requested from the API.
+    pub fn root(&self) -> &str {
+        self.path.split('.').next().unwrap_or_default()
+    }
+
+    /// Looks the path up in a record.
+    ///
+    /// Numeric segments index into arrays.
+    ///
+    /// ```rust
+    /// # use anyhow::Result;
+    /// use acres::export::FieldPath;
+    /// use serde_json::json;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let record = json!({"color": {"h": 196}, "artist_ids": [34, 35]});
+    /// let path: FieldPath = "artist_ids.1".parse()?;
+    /// assert_eq!(path.get(&record), Some(&json!(35)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get<'a>(&self, record: &'a Value) -> Option<&'a Value> {
+        self.path
+            .split('.')
+            .try_fold(record, |value, segment| match value {
+                Value::Object(map) => map.get(segment),
+                Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+                _ => None,
+            })
+    }
+}
+
+impl Display for FieldPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.path)
+    }
+}
+
+impl FromStr for FieldPath {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || s.split('.').any(str::is_empty) {
+            return Err(AcresError::Unexpected(anyhow!(
+                "'{}' is not a valid field path",
+                s
+            )));
+        }
+        Ok(Self {
+            path: s.to_string(),
+        })
+    }
+}
+
+/// Writes artwork records as rows.
+///
+/// Call [`Exporter::finish()`] when done to flush everything and get the writer back.
+#[derive(Debug)]
+pub struct Exporter<W: Write + Send> {
+    columns: Vec<FieldPath>,
+    sink: Sink<W>,
+    rows: usize,
+}
+
+enum Sink<W: Write + Send> {
+    Csv(Box<csv::Writer<W>>),
+    Ndjson(W),
+    #[cfg(feature = "parquet")]
+    Parquet(W, Vec<Vec<Value>>),
+}
+
+impl<W: Write + Send> std::fmt::Debug for Sink<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Sink::Csv(_) => f.write_str("Csv"),
+            Sink::Ndjson(_) => f.write_str("Ndjson"),
+            #[cfg(feature = "parquet")]
+            Sink::Parquet(_, rows) => write!(f, "Parquet({} rows)", rows.len()),
+        }
+    }
+}
+
+impl<W: Write + Send> Exporter<W> {
+    /// Creates an exporter that writes `fields` in `format` to `writer`.
+    pub fn new<S: AsRef<str>>(
+        format: Format,
+        fields: impl IntoIterator<Item = S>,
+        writer: W,
+    ) -> Result<Self, AcresError> {
+        let columns = fields
+            .into_iter()
+            .map(|field| field.as_ref().parse())
+            .collect::<Result<Vec<FieldPath>, _>>()?;
+        if columns.is_empty() {
+            return Err(AcresError::Unexpected(anyhow!(
+                "at least one field is needed to export"
+            )));
+        }
+        let sink = match format {
+            Format::Csv => {
+                let mut writer = csv::Writer::from_writer(writer);
+                writer
+                    .write_record(columns.iter().map(|column| column.to_string()))
+                    .context("writing CSV header")?;
+                Sink::Csv(Box::new(writer))
+            }
+            Format::Ndjson => Sink::Ndjson(writer),
+            #[cfg(feature = "parquet")]
+            Format::Parquet => Sink::Parquet(writer, vec![]),
+        };
+        Ok(Self {
+            columns,
+            sink,
+            rows: 0,
+        })
+    }
+
+    /// Returns the columns being written.
+    pub fn columns(&self) -> &[FieldPath] {
+        &self.columns
+    }
+
+    /// Returns the top-level fields to request from the API for these columns.
+    pub fn fields(&self) -> Vec<String> {
+        let mut fields: Vec<String> = vec![];
+        for column in &self.columns {
+            if !fields.iter().any(|field| field == column.root()) {
+                fields.push(column.root().to_string());
+            }
+        }
+        fields
+    }
+
+    /// Returns the number of rows written so far.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Writes one record as a row.
+    pub fn write(&mut self, record: &Value) -> Result<(), AcresError> {
+        let cells = self.columns.iter().map(|column| column.get(record));
+        match &mut self.sink {
+            Sink::Csv(writer) => writer
+                .write_record(cells.map(|cell| cell.map(text).unwrap_or_default()))
+                .context("writing CSV row")?,
+            Sink::Ndjson(writer) => {
+                // Written by hand so that keys keep the column order.
+                let row = self
+                    .columns
+                    .iter()
+                    .zip(cells)
+                    .map(|(column, cell)| {
+                        format!(
+                            "{}:{}",
+                            Value::String(column.to_string()),
+                            cell.unwrap_or(&Value::Null)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",");
+                writeln!(writer, "{{{row}}}").context("writing NDJSON row")?;
+            }
+            #[cfg(feature = "parquet")]
+            Sink::Parquet(_, rows) => rows.push(
+                cells
+                    .map(|cell| cell.cloned().unwrap_or_default())
+                    .collect(),
+            ),
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Writes every record.
+    pub fn write_all<'a>(
+        &mut self,
+        records: impl IntoIterator<Item = &'a Value>,
+    ) -> Result<(), AcresError> {
+        records
+            .into_iter()
+            .try_for_each(|record| self.write(record))
+    }
+
+    /// Writes every record in a local store.
+    pub fn store(&mut self, store: &JsonStore) -> Result<(), AcresError> {
+        for record in store.records()? {
+            self.write(&record)?;
+        }
+        Ok(())
+    }
+
+    /// Pages through a listing, writing up to `max` records.
+    ///
+    /// The builder's base URI and fields are replaced with the API's and the ones needed for the
+    /// columns, and its page and limit are managed here.
+    pub async fn listing(
+        &mut self,
+        api: &Api,
+        builder: artworks::Builder,
+        max: Option<usize>,
+    ) -> Result<(), AcresError> {
+        let builder = builder.base_uri(api.base_uri()).fields(Some(self.fields()));
+        let mut written = 0;
+        let mut page = 1;
+        loop {
+            let request = builder
+                .clone()
+                .limit(Some(PAGE_SIZE))
+                .page(Some(page))
+                .build()?;
+            let (page_written, envelope) = self
+                .write_page(api, request.to_string(), max.map(|max| max - written))
+                .await?;
+            written += page_written;
+            let total_pages = envelope["pagination"]["total_pages"].as_u64().unwrap_or(0);
+            if max.is_some_and(|max| written >= max) || u64::from(page) >= total_pages {
+                break;
+            }
+            page += 1;
+        }
+        Ok(())
+    }
+
+    /// Pages through search results, writing up to `max` records.
+    ///
+    /// The builder's base URI and fields are replaced with the API's and the ones needed for the
+    /// columns, and its from and size are managed here. Note that the API won't page past the first 10,000 results.
+    pub async fn search(
+        &mut self,
+        api: &Api,
+        builder: search::Builder,
+        max: Option<usize>,
+    ) -> Result<(), AcresError> {
+        let builder = builder.base_uri(api.base_uri()).fields(Some(self.fields()));
+        let mut written = 0;
+        loop {
+            let request = builder
+                .clone()
+                .from(Some(written as u32))
+                .size(Some(PAGE_SIZE))
+                .build()?;
+            let (page, _) = self
+                .write_page(api, request.to_string(), max.map(|max| max - written))
+                .await?;
+            written += page;
+            if max.is_some_and(|max| written >= max) || page < PAGE_SIZE as usize {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Streams a page of records, writing up to `max` of them.
+    ///
+    /// Returns how many were written and the rest of the page, like its `pagination`.
+    async fn write_page(
+        &mut self,
+        api: &Api,
+        request: String,
+        max: Option<usize>,
+    ) -> Result<(usize, Map<String, Value>), AcresError> {
+        let mut stream = api.stream(request).await?;
+        let mut written = 0;
+        while max.is_none_or(|max| written < max)
+            && let Some(record) = stream.next_item().await?
+        {
+            self.write(&record)?;
+            written += 1;
+        }
+        Ok((written, stream.envelope().clone()))
+    }
+
+    /// Flushes everything out and returns the writer.
+    pub fn finish(self) -> Result<W, AcresError> {
+        match self.sink {
+            Sink::Csv(writer) => Ok(writer
+                .into_inner()
+                .map_err(|error| anyhow!("flushing CSV: {}", error.error()))?),
+            Sink::Ndjson(mut writer) => {
+                writer.flush().context("flushing NDJSON")?;
+                Ok(writer)
+            }
+            #[cfg(feature = "parquet")]
+            Sink::Parquet(writer, rows) => parquet::write(writer, &self.columns, &rows),
+        }
+    }
+}
+
+/// Renders a cell as text, with nested values as compact JSON.
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet {
+    use std::{io::Write, sync::Arc};
+
+    use anyhow::Context;
+    use parquet::{
+        basic::{LogicalType, Repetition, Type as PhysicalType},
+        data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type},
+        file::{properties::WriterProperties, writer::SerializedFileWriter},
+        schema::types::Type,
+    };
+    use serde_json::Value;
+
+    use super::{FieldPath, text};
+    use crate::AcresError;
+
+    /// The type of a column, inferred from its values.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Kind {
+        Int,
+        Double,
+        Bool,
+        Text,
+    }
+
+    impl Kind {
+        fn of<'a>(cells: impl Iterator<Item = &'a Value>) -> Kind {
+            let mut kind = None;
+            for cell in cells {
+                let cell_kind = match cell {
+                    Value::Null => continue,
+                    Value::Number(n) if n.is_i64() => Kind::Int,
+                    Value::Number(_) => Kind::Double,
+                    Value::Bool(_) => Kind::Bool,
+                    _ => Kind::Text,
+                };
+                kind = Some(match (kind, cell_kind) {
+                    (None, k) => k,
+                    (Some(a), b) if a == b => a,
+                    (Some(Kind::Int | Kind::Double), Kind::Int | Kind::Double) => Kind::Double,
+                    _ => Kind::Text,
+                });
+            }
+            kind.unwrap_or(Kind::Text)
+        }
+    }
+
+    pub(super) fn write<W: Write + Send>(
+        writer: W,
+        columns: &[FieldPath],
+        rows: &[Vec<Value>],
+    ) -> Result<W, AcresError> {
+        let kinds: Vec<Kind> = (0..columns.len())
+            .map(|i| Kind::of(rows.iter().map(|row| &row[i])))
+            .collect();
+        let fields = columns
+            .iter()
+            .zip(&kinds)
+            .map(|(column, kind)| {
+                let name = column.to_string();
+                let builder = match kind {
+                    Kind::Int => Type::primitive_type_builder(&name, PhysicalType::INT64),
+                    Kind::Double => Type::primitive_type_builder(&name, PhysicalType::DOUBLE),
+                    Kind::Bool => Type::primitive_type_builder(&name, PhysicalType::BOOLEAN),
+                    Kind::Text => Type::primitive_type_builder(&name, PhysicalType::BYTE_ARRAY)
+                        .with_logical_type(Some(LogicalType::String)),
+                };
+                Ok(Arc::new(
+                    builder
+                        .with_repetition(Repetition::OPTIONAL)
+                        .build()
+                        .with_context(|| format!("building Parquet column {name}"))?,
+                ))
+            })
+            .collect::<Result<Vec<_>, AcresError>>()?;
+        let schema = Type::group_type_builder("artwork")
+            .with_fields(fields)
+            .build()
+            .context("building Parquet schema")?;
+        let mut file = SerializedFileWriter::new(
+            writer,
+            Arc::new(schema),
+            Arc::new(WriterProperties::builder().build()),
+        )
+        .context("starting Parquet file")?;
+        let mut row_group = file.next_row_group().context("starting row group")?;
+        let mut i = 0;
+        while let Some(mut column) = row_group.next_column().context("starting column")? {
+            let cells: Vec<&Value> = rows.iter().map(|row| &row[i]).collect();
+            let levels: Vec<i16> = cells
+                .iter()
+                .map(|cell| i16::from(!cell.is_null()))
+                .collect();
+            let present = cells.iter().filter(|cell| !cell.is_null());
+            match kinds[i] {
+                Kind::Int => {
+                    let values: Vec<i64> = present.filter_map(|cell| cell.as_i64()).collect();
+                    column
+                        .typed::<Int64Type>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+                Kind::Double => {
+                    let values: Vec<f64> = present.filter_map(|cell| cell.as_f64()).collect();
+                    column
+                        .typed::<DoubleType>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+                Kind::Bool => {
+                    let values: Vec<bool> = present.filter_map(|cell| cell.as_bool()).collect();
+                    column
+                        .typed::<BoolType>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+                Kind::Text => {
+                    let values: Vec<ByteArray> = present
+                        .map(|cell| ByteArray::from(text(cell).as_str()))
+                        .collect();
+                    column
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+            }
+            .with_context(|| format!("writing Parquet column {}", columns[i]))?;
+            column.close().context("closing column")?;
+            i += 1;
+        }
+        row_group.close().context("closing row group")?;
+        Ok(file.into_inner().context("finishing Parquet file")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn records() -> Vec<Value> {
+        vec![
+            json!({"id": 1, "title": "Water Lilies", "color": {"h": 196, "s": 27.5},
+                   "thumbnail": {"alt_text": "Pond, with \"lilies\""}, "artist_ids": [10]}),
+            json!({"id": 2, "title": "Nighthawks", "color": null, "artist_ids": [12, 13]}),
+        ]
+    }
+
+    #[test]
+    fn csv_flattens_nested_paths() {
+        let mut exporter = Exporter::new(
+            Format::Csv,
+            ["id", "color.h", "thumbnail.alt_text", "artist_ids"],
+            vec![],
+        )
+        .unwrap();
+
+        exporter.write_all(&records()).unwrap();
+        let csv = String::from_utf8(exporter.finish().unwrap()).unwrap();
+
+        assert_eq!(
+            csv,
+            "id,color.h,thumbnail.alt_text,artist_ids\n\
+             1,196,\"Pond, with \"\"lilies\"\"\",[10]\n\
+             2,,,\"[12,13]\"\n"
+        );
+    }
+
+    #[test]
+    fn ndjson_keeps_json_types() {
+        let mut exporter =
+            Exporter::new(Format::Ndjson, ["id", "color.s", "artist_ids.1"], vec![]).unwrap();
+
+        exporter.write_all(&records()).unwrap();
+        let ndjson = String::from_utf8(exporter.finish().unwrap()).unwrap();
+
+        assert_eq!(
+            ndjson,
+            "{\"id\":1,\"color.s\":27.5,\"artist_ids.1\":null}\n\
+             {\"id\":2,\"color.s\":null,\"artist_ids.1\":13}\n"
+        );
+    }
+
+    #[test]
+    fn fields_are_top_level_roots() {
+        let exporter = Exporter::new(
+            Format::Csv,
+            ["id", "color.h", "color.s", "thumbnail.alt_text"],
+            vec![],
+        )
+        .unwrap();
+
+        assert_eq!(exporter.fields(), vec!["id", "color", "thumbnail"]);
+    }
+
+    #[test]
+    fn invalid_field_path_is_an_error() {
+        assert!(Exporter::new(Format::Csv, ["color..h"], vec![]).is_err());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_infers_column_types() {
+        use ::parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let mut exporter =
+            Exporter::new(Format::Parquet, ["id", "title", "color.s"], vec![]).unwrap();
+        exporter.write_all(&records()).unwrap();
+        let file = exporter.finish().unwrap();
+
+        let reader = SerializedFileReader::new(bytes::Bytes::from(file)).unwrap();
+        let rows: Vec<String> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap().to_string())
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                "{id: 1, title: \"Water Lilies\", color.s: 27.5}",
+                "{id: 2, title: \"Nighthawks\", color.s: null}",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn listing_pages_until_max() {
+        let mock_server = wiremock::MockServer::start().await;
+        let page = |n: u64| {
+            json!({
+                "data": [{"id": n * 2 - 1}, {"id": n * 2}],
+                "pagination": { "total": 6, "limit": 2, "offset": 0, "total_pages": 3, "current_page": n },
+            })
+        };
+        for n in 1..=2 {
+            wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+                .and(wiremock::matchers::query_param("page", n.to_string()))
+                .and(wiremock::matchers::query_param("fields", "id"))
+                .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(page(n)))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .use_cache(false)
+            .build();
+        let mut exporter = Exporter::new(Format::Csv, ["id"], vec![]).unwrap();
+
+        exporter
+            .listing(&api, artworks::Builder::new(), Some(3))
+            .await
+            .unwrap();
+
+        assert_eq!(exporter.rows(), 3);
+        let csv = String::from_utf8(exporter.finish().unwrap()).unwrap();
+        assert_eq!(csv, "id\n1\n2\n3\n");
+    }
+}
@@ -0,0 +1,22 @@
+//! Response bodies shared by the tests.
+
+use serde_json::{Value, json};
+
+/// Wraps records in the `info` and `config` that come with every API response.
+pub(crate) fn envelope(data: Value) -> Value {
+    json!({
+        "data": data,
+        "info": { "license_text": "", "license_links": [], "version": "1.13" },
+        "config": { "iiif_url": "https://www.artic.edu/iiif/2", "website_url": "https://www.artic.edu" }
+    })
+}
+
+/// Wraps records in a response envelope for one page of a listing.
+pub(crate) fn page(data: Value, total: u64, current_page: u64, total_pages: u64) -> Value {
+    let mut body = envelope(data);
+    body["pagination"] = json!({
+        "total": total, "limit": 100, "offset": (current_page - 1) * 100,
+        "total_pages": total_pages, "current_page": current_page
+    });
+    body
+}
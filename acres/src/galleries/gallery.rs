@@ -0,0 +1,214 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AcresError,
+    artworks::{CollectionQueryParams, Config, Info, Pagination},
+    endpoint::json_response,
+};
+
+/// A gallery, as returned by [`GET /galleries/{id}`].
+///
+/// [`GET /galleries/{id}`]: https://api.artic.edu/docs/#get-galleries-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Gallery {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Data,
+    /// Info.
+    pub info: Info,
+}
+
+impl Display for Gallery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} ({})", self.data.title, self.data.id))
+    }
+}
+
+json_response!(Gallery, Galleries);
+
+/// A page of galleries, as returned by [`GET /galleries`].
+///
+/// [`GET /galleries`]: https://api.artic.edu/docs/#get-galleries
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Galleries {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Vec<Data>,
+    /// Info.
+    pub info: Info,
+    /// Pagination.
+    pub pagination: Pagination,
+}
+
+/// Gallery record.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Data {
+    /// ID.
+    pub id: u64,
+    /// Title.
+    #[serde(default)]
+    pub title: String,
+    /// Floor the gallery is on, such as `1` or `LL`.
+    #[serde(default, deserialize_with = "floor")]
+    pub floor: Option<String>,
+    /// Whether the gallery is closed.
+    #[serde(default)]
+    pub is_closed: Option<bool>,
+}
+
+/// Reads a floor, which the API gives as a number or a string.
+fn floor<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::String(floor)) => Some(floor),
+            Some(serde_json::Value::Number(floor)) => Some(floor.to_string()),
+            _ => None,
+        },
+    )
+}
+
+/// A [`GET /galleries/{id}`] request.
+///
+/// ```rust
+/// use acres::{Api, galleries::request::gallery::Request};
+///
+/// let request = Request::new(Api::new().base_uri(), 2147);
+/// ```
+///
+/// [`GET /galleries/{id}`]: https://api.artic.edu/docs/#get-galleries-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Request {
+    base_uri: String,
+    id: u32,
+}
+
+impl Display for Request {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}/galleries/{}", self.base_uri, self.id))
+    }
+}
+
+impl Request {
+    /// Constructs a new gallery request.
+    pub fn new(base_uri: String, id: u32) -> Self {
+        Self { base_uri, id }
+    }
+}
+
+/// A [`GET /galleries`] request.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::{Api, galleries::request::galleries};
+///
+/// # fn main() -> Result<()> {
+/// let request = galleries::Builder::new()
+///     .base_uri(Api::new().base_uri())
+///     .ids(Some(vec![2147, 2148]))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`GET /galleries`]: https://api.artic.edu/docs/#get-galleries
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ListRequest(String);
+
+impl Display for ListRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+/// A [`GET /galleries`] request builder.
+///
+/// [`GET /galleries`]: https://api.artic.edu/docs/#get-galleries
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    base_uri: String,
+    ids: Option<Vec<u32>>,
+    limit: Option<u32>,
+    page: Option<u32>,
+    fields: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Sets the base URI.
+    pub fn base_uri(mut self, base_uri: String) -> Self {
+        self.base_uri = base_uri;
+        self
+    }
+
+    /// Sets the gallery ids to retrieve.
+    pub fn ids(mut self, ids: Option<Vec<u32>>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Sets limit on number of galleries to return per page.
+    pub fn limit(mut self, limit: Option<u32>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets page number to return.
+    pub fn page(mut self, page: Option<u32>) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Sets the gallery fields to retrieve.
+    pub fn fields(mut self, fields: Option<Vec<String>>) -> Self {
+        if let Some(fields) = fields {
+            self.fields = fields;
+        }
+        self
+    }
+
+    /// Builds request for the galleries collection.
+    pub fn build(&self) -> Result<ListRequest, AcresError> {
+        let query_params = CollectionQueryParams {
+            ids: self.ids.clone(),
+            limit: self.limit,
+            page: self.page,
+            fields: self.fields.clone(),
+            include: vec![],
+        };
+        Ok(ListRequest(format!(
+            "{}/galleries{}",
+            self.base_uri, query_params
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn floors_are_numbers_or_strings() {
+        let galleries: Vec<Data> = serde_json::from_value(json!([
+            { "id": 2147, "title": "Gallery 240", "floor": 2, "is_closed": false },
+            { "id": 2148, "title": "Gallery LL", "floor": "LL" },
+            { "id": 2149, "title": "Gallery ?", "floor": null }
+        ]))
+        .unwrap();
+
+        let floors: Vec<_> = galleries.iter().map(|g| g.floor.as_deref()).collect();
+        assert_eq!(floors, vec![Some("2"), Some("LL"), None]);
+    }
+}
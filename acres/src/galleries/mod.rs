@@ -0,0 +1,28 @@
+//! Modules and types for working with the [Galleries Collection].
+//!
+//! These are the rooms artworks hang in, with the floor they're on and whether they're open.
+//!
+//! [Galleries Collection]: https://api.artic.edu/docs/#galleries
+
+mod gallery;
+
+pub use gallery::{Data, Galleries, Gallery};
+
+/// Modules for requesting items from the [Galleries Collection].
+///
+/// [Galleries Collection]: https://api.artic.edu/docs/#galleries
+pub mod request {
+    /// A [`GET /galleries/{id}`] request.
+    ///
+    /// [`GET /galleries/{id}`]: https://api.artic.edu/docs/#get-galleries-id
+    pub mod gallery {
+        pub use crate::galleries::gallery::Request;
+    }
+
+    /// A [`GET /galleries`] request.
+    ///
+    /// [`GET /galleries`]: https://api.artic.edu/docs/#get-galleries
+    pub mod galleries {
+        pub use crate::galleries::gallery::{Builder, ListRequest as Request};
+    }
+}
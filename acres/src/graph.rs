@@ -0,0 +1,945 @@
+//! Graphs of how artworks relate to artists, exhibitions, galleries and terms.
+//!
+//! An [`Explorer`] starts from seed artworks and follows the relations in their records: the
+//! artist who made each one, the exhibitions it was shown in, the gallery it hangs in and the terms
+//! it's tagged with, along with the artworks most [similar] to it. From each of those it can carry
+//! on to related artworks, by the same artist or in the same exhibition, say, and from those to
+//! their artists and exhibitions, up to a depth you choose. The result is a [`Graph`] you can write out as [DOT] for Graphviz, [GraphML] for tools
+//! such as Gephi, or JSON.
+//!
+//! ```no_run
+//! # use anyhow::Result;
+//! use acres::graph::{Explorer, Format};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let api = acres::Api::new();
+//! let graph = Explorer::new().depth(2).limit(5).explore(&api, [27992]).await?;
+//! println!("{}", graph.to_format(Format::Dot));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [similar]: ../similar/index.html
+//! [DOT]: https://graphviz.org/doc/info/lang.html
+//! [GraphML]: http://graphml.graphdrawing.org/
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    AcresError, Api, Cached,
+    artworks::request::{artworks, search},
+    exhibitions::{self, request::search as exhibitions_search},
+    similar::Similar,
+};
+
+/// Fields retrieved for each artwork that's expanded.
+const ARTWORK_FIELDS: [&str; 12] = [
+    "id",
+    "title",
+    "artist_id",
+    "artist_title",
+    "gallery_id",
+    "gallery_title",
+    "style_ids",
+    "style_titles",
+    "subject_ids",
+    "subject_titles",
+    "classification_ids",
+    "classification_titles",
+];
+
+/// Fields retrieved for each exhibition.
+const EXHIBITION_FIELDS: [&str; 5] = ["id", "title", "artwork_ids", "gallery_id", "gallery_title"];
+
+/// Fields holding term ids, and the fields holding their labels.
+const TERM_FIELDS: [(&str, &str); 3] = [
+    ("style_ids", "style_titles"),
+    ("subject_ids", "subject_titles"),
+    ("classification_ids", "classification_titles"),
+];
+
+/// Most records the API returns per page.
+const MAX_LIMIT: usize = 100;
+
+/// What a node stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    /// An artwork.
+    Artwork,
+    /// An artist.
+    Artist,
+    /// An exhibition.
+    Exhibition,
+    /// A gallery in the museum.
+    Gallery,
+    /// A style, subject or classification term.
+    Term,
+}
+
+impl Display for NodeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            NodeKind::Artwork => "artwork",
+            NodeKind::Artist => "artist",
+            NodeKind::Exhibition => "exhibition",
+            NodeKind::Gallery => "gallery",
+            NodeKind::Term => "term",
+        })
+    }
+}
+
+/// Identifies a node, e.g. `artwork:27992`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct NodeId {
+    /// What the node stands for.
+    pub kind: NodeKind,
+    /// Its id in the API.
+    pub id: String,
+}
+
+impl NodeId {
+    /// Creates a node id.
+    pub fn new(kind: NodeKind, id: impl ToString) -> Self {
+        NodeId {
+            kind,
+            id: id.to_string(),
+        }
+    }
+}
+
+impl Display for NodeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.kind, self.id)
+    }
+}
+
+/// A relation that can be followed from an artwork.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Relation {
+    /// Made by an artist.
+    Artist,
+    /// Shown in an exhibition.
+    Exhibition,
+    /// Hangs in, or was shown in, a gallery.
+    Gallery,
+    /// Tagged with a style, subject or classification.
+    Term,
+    /// Similar to another artwork, as found by [`Similar`].
+    ///
+    /// [`Similar`]: ../similar/struct.Similar.html
+    Related,
+}
+
+impl Relation {
+    /// Every relation.
+    pub const ALL: [Relation; 5] = [
+        Relation::Artist,
+        Relation::Exhibition,
+        Relation::Gallery,
+        Relation::Term,
+        Relation::Related,
+    ];
+}
+
+impl Display for Relation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Relation::Artist => "artist",
+            Relation::Exhibition => "exhibition",
+            Relation::Gallery => "gallery",
+            Relation::Term => "term",
+            Relation::Related => "related",
+        })
+    }
+}
+
+impl FromStr for Relation {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "artist" => Ok(Relation::Artist),
+            "exhibition" => Ok(Relation::Exhibition),
+            "gallery" => Ok(Relation::Gallery),
+            "term" => Ok(Relation::Term),
+            "related" => Ok(Relation::Related),
+            _ => Err(AcresError::Unexpected(anyhow!(
+                "{s} is not a relation; expected artist, exhibition, gallery, term or related"
+            ))),
+        }
+    }
+}
+
+/// A node in a [`Graph`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Node {
+    /// What the node is.
+    pub id: NodeId,
+    /// Its title or name, if known.
+    pub label: String,
+}
+
+/// An edge in a [`Graph`], from an artwork (or exhibition) to what it's related to.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Edge {
+    /// Where the edge starts.
+    pub source: NodeId,
+    /// Where the edge ends.
+    pub target: NodeId,
+    /// How they're related.
+    pub relation: Relation,
+}
+
+/// A format a [`Graph`] can be written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Format {
+    /// Graphviz DOT.
+    #[default]
+    Dot,
+    /// GraphML.
+    GraphMl,
+    /// JSON, with lists of nodes and edges.
+    Json,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Dot => f.write_str("dot"),
+            Format::GraphMl => f.write_str("graphml"),
+            Format::Json => f.write_str("json"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(Format::Dot),
+            "graphml" => Ok(Format::GraphMl),
+            "json" => Ok(Format::Json),
+            _ => Err(AcresError::Unexpected(anyhow!(
+                "{} is not a supported graph format",
+                s
+            ))),
+        }
+    }
+}
+
+/// Nodes and the edges between them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    nodes: BTreeMap<NodeId, String>,
+    edges: BTreeSet<Edge>,
+}
+
+impl Graph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    /// Adds a node, or fills in its label if it didn't have one.
+    ///
+    /// Returns whether the node is new.
+    pub fn add_node(&mut self, id: NodeId, label: impl Into<String>) -> bool {
+        let label = label.into();
+        match self.nodes.get_mut(&id) {
+            Some(existing) => {
+                if existing.is_empty() {
+                    *existing = label;
+                }
+                false
+            }
+            None => {
+                self.nodes.insert(id, label);
+                true
+            }
+        }
+    }
+
+    /// Adds an edge. Nodes at either end are added too, if they're missing.
+    pub fn add_edge(&mut self, source: NodeId, target: NodeId, relation: Relation) {
+        self.add_node(source.clone(), "");
+        self.add_node(target.clone(), "");
+        self.edges.insert(Edge {
+            source,
+            target,
+            relation,
+        });
+    }
+
+    /// Returns whether there's a node.
+    pub fn contains(&self, id: &NodeId) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    /// Returns the nodes, ordered by id.
+    pub fn nodes(&self) -> impl Iterator<Item = Node> + '_ {
+        self.nodes.iter().map(|(id, label)| Node {
+            id: id.clone(),
+            label: label.clone(),
+        })
+    }
+
+    /// Returns the edges.
+    pub fn edges(&self) -> impl Iterator<Item = &Edge> {
+        self.edges.iter()
+    }
+
+    /// Writes the graph in a format.
+    pub fn to_format(&self, format: Format) -> String {
+        match format {
+            Format::Dot => self.to_dot(),
+            Format::GraphMl => self.to_graphml(),
+            Format::Json => self.to_json().to_string(),
+        }
+    }
+
+    /// Writes the graph as Graphviz DOT.
+    ///
+    /// ```rust
+    /// use acres::graph::{Graph, NodeId, NodeKind, Relation};
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_node(NodeId::new(NodeKind::Artwork, 27992), "A Sunday on La Grande Jatte — 1884");
+    /// graph.add_node(NodeId::new(NodeKind::Artist, 40482), "Georges Seurat");
+    /// graph.add_edge(
+    ///     NodeId::new(NodeKind::Artwork, 27992),
+    ///     NodeId::new(NodeKind::Artist, 40482),
+    ///     Relation::Artist,
+    /// );
+    /// assert!(graph.to_dot().contains(r#""artwork:27992" -> "artist:40482" [label="artist"];"#));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph acres {\n");
+        for (id, label) in &self.nodes {
+            let shape = match id.kind {
+                NodeKind::Artwork => "box",
+                NodeKind::Artist => "ellipse",
+                NodeKind::Exhibition => "hexagon",
+                NodeKind::Gallery => "house",
+                NodeKind::Term => "note",
+            };
+            let _ = writeln!(
+                dot,
+                "  {} [label={}, shape={shape}];",
+                dot_string(&id.to_string()),
+                dot_string(label_or_id(label, id).as_str())
+            );
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                dot,
+                "  {} -> {} [label={}];",
+                dot_string(&edge.source.to_string()),
+                dot_string(&edge.target.to_string()),
+                dot_string(&edge.relation.to_string())
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes the graph as GraphML, with `label` and `kind` on nodes and `relation` on edges.
+    pub fn to_graphml(&self) -> String {
+        let mut xml = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            "\n",
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#,
+            "\n",
+            r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>"#,
+            "\n",
+            r#"  <key id="kind" for="node" attr.name="kind" attr.type="string"/>"#,
+            "\n",
+            r#"  <key id="relation" for="edge" attr.name="relation" attr.type="string"/>"#,
+            "\n",
+            r#"  <graph id="acres" edgedefault="directed">"#,
+            "\n",
+        ));
+        for (id, label) in &self.nodes {
+            let _ = writeln!(
+                xml,
+                r#"    <node id="{}"><data key="label">{}</data><data key="kind">{}</data></node>"#,
+                xml_escape(&id.to_string()),
+                xml_escape(&label_or_id(label, id)),
+                id.kind
+            );
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                xml,
+                r#"    <edge source="{}" target="{}"><data key="relation">{}</data></edge>"#,
+                xml_escape(&edge.source.to_string()),
+                xml_escape(&edge.target.to_string()),
+                edge.relation
+            );
+        }
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+
+    /// Returns the graph as JSON, with `nodes` and `edges` lists.
+    pub fn to_json(&self) -> Value {
+        let nodes: Vec<Value> = self
+            .nodes
+            .iter()
+            .map(|(id, label)| {
+                json!({
+                    "id": id.to_string(),
+                    "kind": id.kind,
+                    "label": label_or_id(label, id),
+                })
+            })
+            .collect();
+        let edges: Vec<Value> = self
+            .edges
+            .iter()
+            .map(|edge| {
+                json!({
+                    "source": edge.source.to_string(),
+                    "target": edge.target.to_string(),
+                    "relation": edge.relation,
+                })
+            })
+            .collect();
+        json!({ "nodes": nodes, "edges": edges })
+    }
+}
+
+fn label_or_id(label: &str, id: &NodeId) -> String {
+    match label {
+        "" => id.to_string(),
+        label => label.to_string(),
+    }
+}
+
+fn dot_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Builds a [`Graph`] by following relations out from seed artworks.
+///
+/// Depth counts hops from the seeds. At depth 1 you get the seeds and their artists, exhibitions,
+/// galleries, terms and similar artworks; at depth 2, the artworks those lead to as well; and so on. Each hop
+/// follows at most [`limit`] artworks or exhibitions from a node, so graphs stay a manageable size.
+///
+/// [`limit`]: #method.limit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explorer {
+    depth: usize,
+    limit: u32,
+    relations: BTreeSet<Relation>,
+}
+
+impl Default for Explorer {
+    fn default() -> Self {
+        Explorer {
+            depth: 2,
+            limit: 10,
+            relations: Relation::ALL.into_iter().collect(),
+        }
+    }
+}
+
+impl Explorer {
+    /// Creates an explorer that follows every relation two hops out.
+    pub fn new() -> Self {
+        Explorer::default()
+    }
+
+    /// Sets how many hops out from the seeds to go.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets how many artworks or exhibitions to follow from each node.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = limit.clamp(1, MAX_LIMIT as u32);
+        self
+    }
+
+    /// Sets which relations to follow.
+    pub fn relations(mut self, relations: impl IntoIterator<Item = Relation>) -> Self {
+        self.relations = relations.into_iter().collect();
+        self
+    }
+
+    /// Explores out from seed artworks.
+    pub async fn explore(
+        &self,
+        api: &Api,
+        seeds: impl IntoIterator<Item = u32>,
+    ) -> Result<Graph, AcresError> {
+        let mut graph = Graph::new();
+        let mut frontier: Vec<NodeId> = seeds
+            .into_iter()
+            .map(|id| NodeId::new(NodeKind::Artwork, id))
+            .collect();
+        frontier.iter().for_each(|id| {
+            graph.add_node(id.clone(), "");
+        });
+        // Exhibitions list their artworks, so there's no need to look them up again.
+        let mut exhibited: BTreeMap<NodeId, Vec<u64>> = BTreeMap::new();
+        let mut expanded = BTreeSet::new();
+        for _ in 0..self.depth {
+            let mut next = vec![];
+            let (artworks, hubs): (Vec<_>, Vec<_>) = frontier
+                .into_iter()
+                .filter(|id| expanded.insert(id.clone()))
+                .partition(|id| id.kind == NodeKind::Artwork);
+            let artwork_ids: Vec<u32> = artworks
+                .iter()
+                .filter_map(|id| id.id.parse().ok())
+                .collect();
+            for ids in artwork_ids.chunks(MAX_LIMIT) {
+                for record in self.fetch_artworks(api, ids).await? {
+                    next.extend(self.add_artwork(&mut graph, &record));
+                }
+            }
+            if self.relations.contains(&Relation::Related) {
+                for id in &artwork_ids {
+                    let artwork = NodeId::new(NodeKind::Artwork, id);
+                    for record in self.fetch_similar(api, *id).await? {
+                        let Some(similar) = record["id"].as_u64() else {
+                            continue;
+                        };
+                        let similar = NodeId::new(NodeKind::Artwork, similar);
+                        graph.add_node(
+                            similar.clone(),
+                            record["title"].as_str().unwrap_or_default(),
+                        );
+                        graph.add_edge(artwork.clone(), similar.clone(), Relation::Related);
+                        next.push(similar);
+                    }
+                }
+            }
+            if self.relations.contains(&Relation::Exhibition) {
+                for id in &artwork_ids {
+                    for record in self.fetch_exhibitions(api, *id).await? {
+                        let artwork = NodeId::new(NodeKind::Artwork, id);
+                        let (exhibition, artworks) =
+                            self.add_exhibition(&mut graph, &artwork, &record);
+                        exhibited.insert(exhibition.clone(), artworks);
+                        next.push(exhibition);
+                    }
+                }
+            }
+            for hub in hubs {
+                let related = match hub.kind {
+                    NodeKind::Exhibition => exhibited
+                        .get(&hub)
+                        .map(|ids| {
+                            ids.iter()
+                                .take(self.limit as usize)
+                                .map(|id| json!({ "id": id }))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    _ => self.fetch_related(api, &hub).await?,
+                };
+                for record in related {
+                    let Some(id) = record["id"].as_u64() else {
+                        continue;
+                    };
+                    let artwork = NodeId::new(NodeKind::Artwork, id);
+                    graph.add_node(
+                        artwork.clone(),
+                        record["title"].as_str().unwrap_or_default(),
+                    );
+                    let relation = match hub.kind {
+                        NodeKind::Artist => Relation::Artist,
+                        NodeKind::Exhibition => Relation::Exhibition,
+                        NodeKind::Gallery => Relation::Gallery,
+                        _ => Relation::Term,
+                    };
+                    graph.add_edge(artwork.clone(), hub.clone(), relation);
+                    next.push(artwork);
+                }
+            }
+            frontier = next;
+        }
+        // Fill in titles for artworks that were reached but not expanded.
+        let unlabelled: Vec<u32> = graph
+            .nodes
+            .iter()
+            .filter(|(id, label)| id.kind == NodeKind::Artwork && label.is_empty())
+            .filter_map(|(id, _)| id.id.parse().ok())
+            .collect();
+        for ids in unlabelled.chunks(MAX_LIMIT) {
+            for record in self.fetch_artworks(api, ids).await? {
+                if let Some(id) = record["id"].as_u64() {
+                    graph.add_node(
+                        NodeId::new(NodeKind::Artwork, id),
+                        record["title"].as_str().unwrap_or_default(),
+                    );
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Adds an artwork and the nodes its record points to, and returns those nodes.
+    fn add_artwork(&self, graph: &mut Graph, record: &Value) -> Vec<NodeId> {
+        let Some(id) = record["id"].as_u64() else {
+            return vec![];
+        };
+        let artwork = NodeId::new(NodeKind::Artwork, id);
+        graph.add_node(
+            artwork.clone(),
+            record["title"].as_str().unwrap_or_default(),
+        );
+        let mut related = vec![];
+        let mut relate = |kind: NodeKind, id: &Value, label: &Value, relation: Relation| {
+            let id = match id {
+                Value::String(id) => id.clone(),
+                Value::Number(id) => id.to_string(),
+                _ => return,
+            };
+            let node = NodeId::new(kind, id);
+            graph.add_node(node.clone(), label.as_str().unwrap_or_default());
+            graph.add_edge(artwork.clone(), node.clone(), relation);
+            related.push(node);
+        };
+        if self.relations.contains(&Relation::Artist) {
+            relate(
+                NodeKind::Artist,
+                &record["artist_id"],
+                &record["artist_title"],
+                Relation::Artist,
+            );
+        }
+        if self.relations.contains(&Relation::Gallery) {
+            relate(
+                NodeKind::Gallery,
+                &record["gallery_id"],
+                &record["gallery_title"],
+                Relation::Gallery,
+            );
+        }
+        if self.relations.contains(&Relation::Term) {
+            for (ids, titles) in TERM_FIELDS {
+                let ids = record[ids].as_array().map_or(&[][..], Vec::as_slice);
+                for (index, id) in ids.iter().enumerate() {
+                    relate(NodeKind::Term, id, &record[titles][index], Relation::Term);
+                }
+            }
+        }
+        related
+    }
+
+    /// Adds an exhibition an artwork was shown in, and returns it with the artworks it showed.
+    fn add_exhibition(
+        &self,
+        graph: &mut Graph,
+        artwork: &NodeId,
+        record: &exhibitions::Data,
+    ) -> (NodeId, Vec<u64>) {
+        let exhibition = NodeId::new(NodeKind::Exhibition, record.id);
+        graph.add_node(exhibition.clone(), &record.title);
+        graph.add_edge(artwork.clone(), exhibition.clone(), Relation::Exhibition);
+        if self.relations.contains(&Relation::Gallery)
+            && let Some(gallery) = record.gallery_id
+        {
+            let gallery = NodeId::new(NodeKind::Gallery, gallery);
+            graph.add_node(
+                gallery.clone(),
+                record.gallery_title.as_deref().unwrap_or_default(),
+            );
+            graph.add_edge(exhibition.clone(), gallery, Relation::Gallery);
+        }
+        (exhibition, record.artwork_ids.clone())
+    }
+
+    async fn fetch_artworks(&self, api: &Api, ids: &[u32]) -> Result<Vec<Value>, AcresError> {
+        let request = artworks::Builder::new()
+            .base_uri(api.base_uri())
+            .ids(Some(ids.to_vec()))
+            .limit(Some(ids.len() as u32))
+            .fields(Some(ARTWORK_FIELDS.map(String::from).to_vec()))
+            .build()?;
+        let response: Cached = api.fetch(request.to_string()).await?;
+        Ok(data(response))
+    }
+
+    async fn fetch_exhibitions(
+        &self,
+        api: &Api,
+        artwork: u32,
+    ) -> Result<Vec<exhibitions::Data>, AcresError> {
+        let request = exhibitions_search::Builder::new()
+            .base_uri(api.base_uri())
+            .query(Some(
+                json!({ "term": { "artwork_ids": artwork } }).to_string(),
+            ))
+            .size(Some(self.limit))
+            .fields(Some(EXHIBITION_FIELDS.map(String::from).to_vec()))
+            .build()?;
+        Ok(api.send(&request).await?.data)
+    }
+
+    /// Searches for the artworks most similar to an artwork.
+    async fn fetch_similar(&self, api: &Api, artwork: u32) -> Result<Vec<Value>, AcresError> {
+        let similar = Similar::new()
+            .size(self.limit)
+            .fields(vec!["id".into(), "title".into()])
+            .find(api, artwork)
+            .await?;
+        Ok(similar.data().to_vec())
+    }
+
+    /// Searches for artworks that share an artist, gallery or term.
+    async fn fetch_related(&self, api: &Api, hub: &NodeId) -> Result<Vec<Value>, AcresError> {
+        let id = match hub.id.parse::<u64>() {
+            Ok(id) => json!(id),
+            Err(_) => json!(hub.id),
+        };
+        let query = match hub.kind {
+            NodeKind::Artist => json!({ "term": { "artist_ids": id } }),
+            NodeKind::Gallery => json!({ "term": { "gallery_id": id } }),
+            NodeKind::Term => json!({
+                "bool": {
+                    "should": TERM_FIELDS
+                        .iter()
+                        .map(|(field, _)| json!({ "term": { *field: id } }))
+                        .collect::<Vec<_>>(),
+                    "minimum_should_match": 1
+                }
+            }),
+            NodeKind::Artwork | NodeKind::Exhibition => return Ok(vec![]),
+        };
+        let request = search::Builder::new()
+            .base_uri(api.base_uri())
+            .query(Some(query.to_string()))
+            .size(Some(self.limit))
+            .fields(Some(vec!["id".into(), "title".into()]))
+            .build()?;
+        Ok(api.send(&request).await?.data().to_vec())
+    }
+}
+
+fn data(response: Cached) -> Vec<Value> {
+    match response.0 {
+        Value::Object(mut object) => match object.remove("data") {
+            Some(Value::Array(data)) => data,
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        let mut graph = Graph::new();
+        let artwork = NodeId::new(NodeKind::Artwork, 27992);
+        let artist = NodeId::new(NodeKind::Artist, 40482);
+        graph.add_node(artwork.clone(), "A Sunday on La Grande Jatte — 1884");
+        graph.add_node(artist.clone(), "Georges \"Seurat\" & co");
+        graph.add_edge(artwork, artist, Relation::Artist);
+        graph.add_edge(
+            NodeId::new(NodeKind::Artwork, 27992),
+            NodeId::new(NodeKind::Term, "TM-7543"),
+            Relation::Term,
+        );
+        graph
+    }
+
+    #[test]
+    fn writes_dot() {
+        assert_eq!(
+            graph().to_dot(),
+            "digraph acres {\n  \
+             \"artwork:27992\" [label=\"A Sunday on La Grande Jatte — 1884\", shape=box];\n  \
+             \"artist:40482\" [label=\"Georges \\\"Seurat\\\" & co\", shape=ellipse];\n  \
+             \"term:TM-7543\" [label=\"term:TM-7543\", shape=note];\n  \
+             \"artwork:27992\" -> \"artist:40482\" [label=\"artist\"];\n  \
+             \"artwork:27992\" -> \"term:TM-7543\" [label=\"term\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn writes_graphml() {
+        let xml = graph().to_graphml();
+
+        assert!(xml.contains(
+            r#"<node id="artist:40482"><data key="label">Georges &quot;Seurat&quot; &amp; co</data><data key="kind">artist</data></node>"#
+        ));
+        assert!(xml.contains(
+            r#"<edge source="artwork:27992" target="term:TM-7543"><data key="relation">term</data></edge>"#
+        ));
+        assert!(xml.ends_with("</graphml>\n"));
+    }
+
+    #[test]
+    fn writes_json() {
+        let json = graph().to_json();
+
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            json["edges"][0],
+            json!({ "source": "artwork:27992", "target": "artist:40482", "relation": "artist" })
+        );
+    }
+
+    #[test]
+    fn parses_formats_and_relations() {
+        assert_eq!("graphml".parse::<Format>().unwrap(), Format::GraphMl);
+        assert!("svg".parse::<Format>().is_err());
+        assert_eq!("gallery".parse::<Relation>().unwrap(), Relation::Gallery);
+        assert_eq!("related".parse::<Relation>().unwrap(), Relation::Related);
+        assert!("owner".parse::<Relation>().is_err());
+    }
+
+    #[tokio::test]
+    async fn explores_to_a_bounded_depth() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+            .and(wiremock::matchers::query_param("ids", "27992"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{
+                    "id": 27992,
+                    "title": "A Sunday on La Grande Jatte — 1884",
+                    "artist_id": 40482,
+                    "artist_title": "Georges Seurat",
+                    "gallery_id": 2147478,
+                    "gallery_title": "Gallery 240",
+                    "style_ids": ["TM-7543"],
+                    "style_titles": ["Pointillism"]
+                }]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/exhibitions/search"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{
+                    "id": 1290,
+                    "title": "Seurat and the Making of La Grande Jatte",
+                    "artwork_ids": [27992, 111442]
+                }]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .and(wiremock::matchers::query_param(
+                "query",
+                r#"{"term":{"artist_ids":40482}}"#,
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{ "id": 111442, "title": "Study for A Sunday on La Grande Jatte" }]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .and(wiremock::matchers::query_param_contains("query", "TM-7543"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({ "data": [] })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+
+        let graph = Explorer::new()
+            .relations([Relation::Artist, Relation::Exhibition, Relation::Term])
+            .depth(2)
+            .explore(&api, [27992])
+            .await
+            .unwrap();
+
+        let study = NodeId::new(NodeKind::Artwork, 111442);
+        assert!(graph.contains(&NodeId::new(NodeKind::Term, "TM-7543")));
+        assert!(!graph.contains(&NodeId::new(NodeKind::Gallery, 2147478)));
+        assert_eq!(
+            graph
+                .nodes()
+                .find(|node| node.id == study)
+                .map(|node| node.label),
+            Some("Study for A Sunday on La Grande Jatte".into())
+        );
+        let relations: BTreeSet<_> = graph
+            .edges()
+            .filter(|edge| edge.source == study)
+            .map(|edge| edge.relation)
+            .collect();
+        assert_eq!(
+            relations,
+            BTreeSet::from([Relation::Artist, Relation::Exhibition])
+        );
+    }
+
+    #[tokio::test]
+    async fn relates_similar_artworks() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+            .and(wiremock::matchers::query_param("ids", "27992"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{ "id": 27992, "title": "A Sunday on La Grande Jatte — 1884" }]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/27992"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "id": 27992, "title": "A Sunday on La Grande Jatte — 1884", "artist_id": 40482 }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .and(wiremock::matchers::query_param_contains(
+                "query",
+                r#""must_not":[{"term":{"id":27992}}]"#,
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{ "id": 28067, "title": "The Bathers" }]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+
+        let graph = Explorer::new()
+            .relations([Relation::Related])
+            .depth(1)
+            .explore(&api, [27992])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            graph.edges().cloned().collect::<Vec<_>>(),
+            vec![Edge {
+                source: NodeId::new(NodeKind::Artwork, 27992),
+                target: NodeId::new(NodeKind::Artwork, 28067),
+                relation: Relation::Related,
+            }]
+        );
+    }
+}
@@ -0,0 +1,622 @@
+//! Structured provenance, exhibition and publication histories.
+//!
+//! Artwork records tell the story of a work in three long text fields: `provenance_text`, who
+//! owned it and how it changed hands; `exhibition_history`, where it was shown; and
+//! `publication_history`, where it was written about. Each is read into a [`Timeline`] of entries
+//! in the order given, with the dates, owners or venues, and locations that can be picked out.
+//! Every entry keeps its text, and the timeline keeps the whole, so nothing is lost when an entry
+//! doesn't follow the usual pattern.
+//!
+//! ```rust
+//! use acres::history::{Transfer, provenance};
+//!
+//! let timeline = provenance(
+//!     "Sold by the artist to Durand-Ruel, Paris, 1891 [according to the stock book]; \
+//!      sold to Potter Palmer, Chicago, 1892; bequeathed to the Art Institute, 1922.",
+//! );
+//! let owners: Vec<_> = timeline.iter().filter_map(|entry| entry.owner.as_deref()).collect();
+//! assert_eq!(owners, ["Durand-Ruel", "Potter Palmer", "the Art Institute"]);
+//! assert_eq!(timeline.entries()[1].location.as_deref(), Some("Chicago"));
+//! assert_eq!(timeline.entries()[2].transfer, Some(Transfer::Bequest));
+//! assert!(timeline.passed_through("durand-ruel"));
+//! ```
+//!
+//! The same fields can be searched with the [`query`] keys `provenance:`, `exhibition:` and
+//! `publication:`.
+//!
+//! [`query`]: ../query/index.html
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dating::Span;
+
+/// The entries of a history, in order, along with the text they came from.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Timeline<E> {
+    raw: String,
+    entries: Vec<E>,
+}
+
+impl<E> Timeline<E> {
+    /// Returns the text the timeline was read from.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the entries, in the order given.
+    pub fn entries(&self) -> &[E] {
+        &self.entries
+    }
+
+    /// Returns an iterator over the entries.
+    pub fn iter(&self) -> std::slice::Iter<'_, E> {
+        self.entries.iter()
+    }
+
+    /// Returns true if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<'a, E> IntoIterator for &'a Timeline<E> {
+    type Item = &'a E;
+    type IntoIter = std::slice::Iter<'a, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl Timeline<Ownership> {
+    /// Returns true if anyone whose name contains `name` owned the work, ignoring case.
+    pub fn passed_through(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.iter()
+            .filter_map(|entry| entry.owner.as_deref())
+            .any(|owner| owner.to_lowercase().contains(&name))
+    }
+}
+
+impl Timeline<Exhibition> {
+    /// Returns true if the work was shown at a venue whose name contains `name`, ignoring case.
+    pub fn shown_at(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.iter()
+            .filter_map(|entry| entry.venue.as_deref())
+            .any(|venue| venue.to_lowercase().contains(&name))
+    }
+}
+
+/// How a work changed hands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transfer {
+    /// Sold or bought.
+    Sale,
+    /// Given or donated.
+    Gift,
+    /// Left in a will.
+    Bequest,
+    /// Inherited.
+    Descent,
+    /// Traded for something else.
+    Exchange,
+    /// Left with a dealer to sell.
+    Consignment,
+}
+
+impl Display for Transfer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Transfer::Sale => "sale",
+            Transfer::Gift => "gift",
+            Transfer::Bequest => "bequest",
+            Transfer::Descent => "descent",
+            Transfer::Exchange => "exchange",
+            Transfer::Consignment => "consignment",
+        })
+    }
+}
+
+/// Words that say how a work changed hands.
+const TRANSFERS: [(&str, Transfer); 14] = [
+    ("bequeathed", Transfer::Bequest),
+    ("bequest", Transfer::Bequest),
+    ("by descent", Transfer::Descent),
+    ("by inheritance", Transfer::Descent),
+    ("inherited", Transfer::Descent),
+    ("exchanged", Transfer::Exchange),
+    ("in exchange", Transfer::Exchange),
+    ("consigned", Transfer::Consignment),
+    ("given", Transfer::Gift),
+    ("donated", Transfer::Gift),
+    ("presented", Transfer::Gift),
+    ("sold", Transfer::Sale),
+    ("purchased", Transfer::Sale),
+    ("acquired", Transfer::Sale),
+];
+
+/// An owner in a work's provenance.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Ownership {
+    /// The entry as written.
+    pub text: String,
+    /// Who owned the work.
+    pub owner: Option<String>,
+    /// Where they were.
+    pub location: Option<String>,
+    /// When, as the years mentioned.
+    pub date: Option<Span>,
+    /// How the work came to them.
+    pub transfer: Option<Transfer>,
+    /// Bracketed notes, such as sources.
+    pub notes: Vec<String>,
+}
+
+/// An exhibition a work was shown in.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Exhibition {
+    /// The entry as written.
+    pub text: String,
+    /// The city.
+    pub location: Option<String>,
+    /// The museum or gallery.
+    pub venue: Option<String>,
+    /// The exhibition's title.
+    pub title: Option<String>,
+    /// When, as the years mentioned.
+    pub date: Option<Span>,
+    /// The catalog number, e.g. "cat. 24".
+    pub catalog: Option<String>,
+}
+
+/// A publication a work appears in.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Publication {
+    /// The entry as written.
+    pub text: String,
+    /// The author or editor.
+    pub author: Option<String>,
+    /// The title.
+    pub title: Option<String>,
+    /// When, as the years mentioned.
+    pub date: Option<Span>,
+    /// Where in it the work appears, e.g. "p. 12, fig. 3".
+    pub pages: Option<String>,
+}
+
+/// Reads a `provenance_text`, whose entries are separated by semicolons.
+pub fn provenance(text: &str) -> Timeline<Ownership> {
+    timeline(text, split_outside_brackets(text, &[';', '\n']), ownership)
+}
+
+/// Reads an `exhibition_history`, with one exhibition per line.
+///
+/// Entries usually read "City, Venue, Title, Dates, cat. N".
+pub fn exhibitions(text: &str) -> Timeline<Exhibition> {
+    timeline(text, text.lines().map(str::to_string).collect(), exhibition)
+}
+
+/// Reads a `publication_history`, with one publication per line.
+///
+/// Entries usually read "Author, Title (Place, Year), p. N".
+pub fn publications(text: &str) -> Timeline<Publication> {
+    timeline(
+        text,
+        text.lines().map(str::to_string).collect(),
+        publication,
+    )
+}
+
+fn timeline<E>(raw: &str, entries: Vec<String>, parse: impl Fn(&str) -> E) -> Timeline<E> {
+    Timeline {
+        raw: raw.to_string(),
+        entries: entries
+            .iter()
+            .map(|entry| entry.trim())
+            .filter(|entry| !entry.is_empty())
+            .map(parse)
+            .collect(),
+    }
+}
+
+fn ownership(text: &str) -> Ownership {
+    let (plain, notes) = without_notes(text);
+    // ASCII lowercasing keeps byte offsets the same as in `plain`.
+    let lower = plain.to_ascii_lowercase();
+    let transfer = TRANSFERS
+        .iter()
+        .filter_map(|(word, transfer)| lower.find(word).map(|at| (at, *word, *transfer)))
+        .min_by_key(|(at, _, _)| *at);
+    // "Sold by the artist to Durand-Ruel, Paris" names the new owner after "to", while
+    // "purchased by Potter Palmer" names them after "by".
+    let start = match transfer {
+        Some((at, word, _)) => {
+            let after = at + word.len();
+            let to = lower[after..]
+                .find(" to ")
+                .map(|to| after + to + " to ".len());
+            let first_comma = lower[after..].find(',').map_or(lower.len(), |c| after + c);
+            match to.filter(|to| *to < first_comma) {
+                Some(to) => to,
+                None => match lower[after..].strip_prefix(" by ") {
+                    Some(_) => after + " by ".len(),
+                    None => after,
+                },
+            }
+        }
+        None => 0,
+    };
+    let mut fields = plain[start..]
+        .split(',')
+        .map(|field| field.trim().trim_end_matches('.').trim());
+    let owner = fields
+        .next()
+        .filter(|owner| !owner.is_empty() && !is_date(owner));
+    let location = fields.next().filter(|field| is_place(field));
+    Ownership {
+        text: text.to_string(),
+        owner: owner.map(str::to_string),
+        location: location.map(str::to_string),
+        date: years(&plain),
+        transfer: transfer.map(|(_, _, transfer)| transfer),
+        notes,
+    }
+}
+
+fn exhibition(text: &str) -> Exhibition {
+    let mut fields: Vec<String> = split_outside_brackets(text, &[','])
+        .into_iter()
+        .map(|field| field.trim().trim_end_matches('.').trim().to_string())
+        .filter(|field| !field.is_empty())
+        .collect();
+    // The dates and catalog number come last, after the title.
+    let mut catalog = None;
+    let mut dates = vec![];
+    while let Some(field) = fields.last() {
+        if is_catalog(field) {
+            catalog = catalog.or(fields.pop());
+        } else if is_date_like(field) {
+            dates.extend(fields.pop());
+        } else {
+            break;
+        }
+    }
+    let mut fields = fields.into_iter();
+    let first = fields.next();
+    let (location, venue) = match first {
+        Some(first) if is_place(&first) && !is_venue(&first) => (Some(first), fields.next()),
+        first => (None, first),
+    };
+    let title = fields.collect::<Vec<_>>().join(", ");
+    Exhibition {
+        text: text.to_string(),
+        location,
+        venue,
+        title: (!title.is_empty()).then_some(title),
+        date: years(&dates.join(" ")),
+        catalog,
+    }
+}
+
+fn publication(text: &str) -> Publication {
+    let (plain, _) = without_notes(text);
+    let (author, rest) = match plain.split_once(", ") {
+        Some((author, rest)) if !is_date(author) => (Some(author.trim()), rest),
+        _ => (None, plain.as_str()),
+    };
+    let pages_at = PAGE_MARKERS
+        .iter()
+        .filter_map(|marker| {
+            rest.match_indices(marker)
+                .map(|(at, _)| at)
+                .find(|&at| rest[..at].ends_with(' ') || at == 0)
+        })
+        .min();
+    let (title_and_imprint, pages) = match pages_at {
+        Some(at) => (&rest[..at], Some(&rest[at..])),
+        None => (rest, None),
+    };
+    // The place and year of publication are in parentheses after the title.
+    let title = title_and_imprint
+        .split_once(" (")
+        .map_or(title_and_imprint, |(title, _)| title)
+        .trim()
+        .trim_end_matches([',', '.'])
+        .trim();
+    Publication {
+        text: text.to_string(),
+        author: author.map(str::to_string),
+        title: (!title.is_empty()).then(|| title.to_string()),
+        // Page and catalog numbers can look like years, so only the title and imprint count.
+        date: years(title_and_imprint).or_else(|| author.and_then(years)),
+        pages: pages
+            .map(|pages| pages.trim().trim_end_matches('.').to_string())
+            .filter(|pages| !pages.is_empty()),
+    }
+}
+
+/// What precedes page, figure and plate references.
+const PAGE_MARKERS: [&str; 6] = ["pp. ", "p. ", "fig. ", "figs. ", "pl. ", "cat. "];
+
+/// Splits text at separators that aren't within brackets or parentheses.
+fn split_outside_brackets(text: &str, separators: &[char]) -> Vec<String> {
+    let mut parts = vec![];
+    let mut part = String::new();
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && separators.contains(&c) => {
+                parts.push(std::mem::take(&mut part));
+                continue;
+            }
+            _ => {}
+        }
+        part.push(c);
+    }
+    parts.push(part);
+    parts
+}
+
+/// Returns the text without bracketed notes, and the notes.
+fn without_notes(text: &str) -> (String, Vec<String>) {
+    let mut plain = String::new();
+    let mut notes = vec![];
+    let mut note: Option<String> = None;
+    for c in text.chars() {
+        match (c, note.as_mut()) {
+            ('[', None) => note = Some(String::new()),
+            (']', Some(_)) => notes.extend(note.take().map(|note| note.trim().to_string())),
+            (c, Some(note)) => note.push(c),
+            (c, None) => plain.push(c),
+        }
+    }
+    // An unclosed note runs to the end.
+    notes.extend(note.map(|note| note.trim().to_string()));
+    let plain = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+    (plain, notes)
+}
+
+/// Returns the span of the years mentioned, earliest to latest.
+///
+/// Years are four-digit numbers from 1000 to 2099 that aren't part of a longer number.
+fn years(text: &str) -> Option<Span> {
+    let bytes = text.as_bytes();
+    let mut span: Option<Span> = None;
+    let mut at = 0;
+    while at < bytes.len() {
+        if !bytes[at].is_ascii_digit() {
+            at += 1;
+            continue;
+        }
+        let end = bytes[at..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |len| at + len);
+        if end - at == 4
+            && let Ok(year) = text[at..end].parse::<i64>()
+            && (1000..2100).contains(&year)
+        {
+            span = Some(span.map_or(Span::new(year, year), |span| {
+                Span::new(span.start().min(year), span.end().max(year))
+            }));
+        }
+        at = end;
+    }
+    span
+}
+
+/// Returns true for fields that are only a date with a year, like "by 1891" or "Nov. 1906".
+fn is_date(field: &str) -> bool {
+    years(field).is_some() && is_date_like(field)
+}
+
+/// Returns true for fields made up of numbers and date words, like "July 22–Nov. 26".
+fn is_date_like(field: &str) -> bool {
+    field.chars().any(|c| c.is_ascii_digit())
+        && field.split_whitespace().all(|word| {
+            word.chars().any(|c| c.is_ascii_digit())
+                || DATE_WORDS.contains(&word.to_lowercase().trim_end_matches(['.', ',']))
+        })
+}
+
+/// Words that appear in dates.
+const DATE_WORDS: &[&str] = &[
+    "by",
+    "until",
+    "in",
+    "from",
+    "to",
+    "after",
+    "before",
+    "c",
+    "ca",
+    "about",
+    "and",
+    "or",
+    "jan",
+    "january",
+    "feb",
+    "february",
+    "mar",
+    "march",
+    "apr",
+    "april",
+    "may",
+    "june",
+    "july",
+    "aug",
+    "august",
+    "sept",
+    "sep",
+    "september",
+    "oct",
+    "october",
+    "nov",
+    "november",
+    "dec",
+    "december",
+];
+
+/// Words that name a venue rather than a place.
+const VENUE_WORDS: &[&str] = &[
+    "gallery",
+    "galleries",
+    "galerie",
+    "museum",
+    "musée",
+    "institute",
+    "society",
+    "club",
+    "center",
+    "centre",
+    "hall",
+    "foundation",
+    "library",
+    "academy",
+    "salon",
+];
+
+/// Returns true for fields that look like a place: capitalized, without digits or notes.
+fn is_place(field: &str) -> bool {
+    field.chars().next().is_some_and(char::is_uppercase)
+        && !field
+            .chars()
+            .any(|c| c.is_ascii_digit() || c == '[' || c == '(')
+        && field.split_whitespace().count() <= 3
+}
+
+fn is_venue(field: &str) -> bool {
+    field
+        .split_whitespace()
+        .any(|word| VENUE_WORDS.contains(&word.to_lowercase().as_str()))
+}
+
+fn is_catalog(field: &str) -> bool {
+    let lower = field.to_lowercase();
+    ["cat. ", "no. ", "cat ", "nos. "]
+        .iter()
+        .any(|marker| lower.starts_with(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_provenance() {
+        let timeline = provenance(
+            "The artist, Paris, until 1891; sold by the artist to Paul Durand-Ruel, Paris, Nov. 1891 \
+             [stock book, no. 1234; see note 1]; purchased by Mrs. Potter Palmer, Chicago, 1892; \
+             by descent to her sons, Honoré and Potter Palmer, Jr., Chicago, 1918; given to the Art \
+             Institute, 1922.",
+        );
+
+        assert_eq!(timeline.len(), 5);
+        let owners: Vec<Option<&str>> = timeline.iter().map(|e| e.owner.as_deref()).collect();
+        assert_eq!(
+            owners,
+            vec![
+                Some("The artist"),
+                Some("Paul Durand-Ruel"),
+                Some("Mrs. Potter Palmer"),
+                Some("her sons"),
+                Some("the Art Institute"),
+            ]
+        );
+        let transfers: Vec<Option<Transfer>> = timeline.iter().map(|e| e.transfer).collect();
+        assert_eq!(
+            transfers,
+            vec![
+                None,
+                Some(Transfer::Sale),
+                Some(Transfer::Sale),
+                Some(Transfer::Descent),
+                Some(Transfer::Gift)
+            ]
+        );
+        let second = &timeline.entries()[1];
+        assert_eq!(second.location.as_deref(), Some("Paris"));
+        assert_eq!(second.date, Some(Span::new(1891, 1891)));
+        assert_eq!(second.notes, vec!["stock book, no. 1234; see note 1"]);
+        assert!(second.text.contains("[stock book"));
+        assert_eq!(timeline.entries()[4].location, None);
+        assert!(timeline.passed_through("DURAND-RUEL"));
+        assert!(!timeline.passed_through("Vollard"));
+        assert!(timeline.raw().starts_with("The artist, Paris"));
+    }
+
+    #[test]
+    fn reads_exhibitions() {
+        let timeline = exhibitions(
+            "Chicago, The Art Institute of Chicago, Claude Monet: 1840–1926, July 22–Nov. 26, 1995, cat. 112.\n\n\
+             New York, Durand-Ruel Galleries, Jan. 1–15, 1914, no. 3.\n\
+             Art Institute of Chicago, Exhibition of Paintings by Claude Monet, Mar. 22–Apr. 15, 1895.",
+        );
+
+        assert_eq!(timeline.len(), 3);
+        let first = &timeline.entries()[0];
+        assert_eq!(first.location.as_deref(), Some("Chicago"));
+        assert_eq!(first.venue.as_deref(), Some("The Art Institute of Chicago"));
+        assert_eq!(first.title.as_deref(), Some("Claude Monet: 1840–1926"));
+        assert_eq!(first.catalog.as_deref(), Some("cat. 112"));
+        assert_eq!(first.date, Some(Span::new(1995, 1995)));
+
+        let second = &timeline.entries()[1];
+        assert_eq!(second.location.as_deref(), Some("New York"));
+        assert_eq!(second.venue.as_deref(), Some("Durand-Ruel Galleries"));
+        assert_eq!(second.title, None);
+
+        let third = &timeline.entries()[2];
+        assert_eq!(third.venue.as_deref(), Some("Art Institute of Chicago"));
+        assert_eq!(
+            third.title.as_deref(),
+            Some("Exhibition of Paintings by Claude Monet")
+        );
+        assert!(timeline.shown_at("durand-ruel"));
+    }
+
+    #[test]
+    fn reads_publications() {
+        let timeline = publications(
+            "Daniel Wildenstein, Claude Monet: Biographie et catalogue raisonné, vol. 3 (Lausanne/Paris, 1979), pp. 200–01, no. 1345.\n\
+             Art Institute of Chicago, Annual Report (1933), p. 12, fig. 3.\n\
+             1995 catalogue, pp. 4–5.",
+        );
+
+        let first = &timeline.entries()[0];
+        assert_eq!(first.author.as_deref(), Some("Daniel Wildenstein"));
+        assert_eq!(
+            first.title.as_deref(),
+            Some("Claude Monet: Biographie et catalogue raisonné, vol. 3")
+        );
+        assert_eq!(first.date, Some(Span::new(1979, 1979)));
+        assert_eq!(first.pages.as_deref(), Some("pp. 200–01, no. 1345"));
+
+        let second = &timeline.entries()[1];
+        assert_eq!(second.title.as_deref(), Some("Annual Report"));
+        assert_eq!(second.pages.as_deref(), Some("p. 12, fig. 3"));
+
+        assert_eq!(timeline.entries()[2].date, Some(Span::new(1995, 1995)));
+    }
+
+    #[test]
+    fn keeps_entries_that_do_not_follow_the_pattern() {
+        let timeline = provenance("Unknown");
+        assert_eq!(timeline.entries()[0].owner.as_deref(), Some("Unknown"));
+        assert_eq!(timeline.entries()[0].date, None);
+
+        assert!(provenance("").is_empty());
+        assert_eq!(exhibitions("\n\n").len(), 0);
+    }
+}
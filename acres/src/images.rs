@@ -0,0 +1,737 @@
+//! A store for IIIF images.
+//!
+//! Images are kept apart from the JSON response cache, keyed by their canonical [IIIF image
+//! request] rather than by the URL they happened to be fetched with. Each entry has a
+//! [`Sidecar`] with its content type, dimensions and the artwork it came from, while the bytes
+//! themselves are stored once per distinct content, so the same image fetched through two
+//! requests only takes up space once.
+//!
+//! When there's no exact match for a request, but there is a larger rendition of the same
+//! region, the store scales that down locally instead of going back to the server. The result is
+//! stored as well.
+//!
+//! ```text
+//! images/
+//!   blobs/<hash of bytes>
+//!   entries/<hash of image uri>/<hash of request>.json
+//! ```
+//!
+//! Most of the time you'll go through [`Api::fetch_image`], which builds the request for an
+//! artwork from [`ImageOptions`], or [`Api::image`] for a request you already have. Both use the
+//! store in the cache directory whenever caching is on.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use acres::images::ImageStore;
+//!
+//! # fn main() -> Result<()> {
+//! # let dir = assert_fs::TempDir::new()?;
+//! # let jpeg = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/../acres-cli-ascii-art-77333.jpg"))?;
+//! let store = ImageStore::open(dir.path())?;
+//! let large: iiif::ImageRequest =
+//!     "https://www.artic.edu/iiif/2/3c27b499-af56-f0d5-93b5-a7f2f1ad5813/full/843,/0/default.jpg"
+//!         .parse()?;
+//! store.put(&large, jpeg.into(), None, Some(77333))?;
+//!
+//! let small = large.with_size(iiif::Size::Width(200));
+//! let image = store.get(&small)?.expect("scaled down from the larger one");
+//! assert_eq!(image.sidecar().width, Some(200));
+//! assert_eq!(image.sidecar().artwork_id, Some(77333));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [IIIF image request]: https://iiif.io/api/image/2.0/#4-image-requests
+//! [`Api::image`]: ../struct.Api.html#method.image
+//! [`Api::fetch_image`]: ../struct.Api.html#method.fetch_image
+
+use std::{
+    io::Cursor,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::Context;
+use bytes::Bytes;
+use iiif::{Format, ImageRequest, Quality, Region, Rotation, Size};
+use image::{ImageOutputFormat, imageops::FilterType};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AcresError, Api,
+    artworks::ArtworkInfo,
+    cache::{self, DirLock},
+    rights::Policy,
+};
+
+/// Quality used when re-encoding a scaled-down JPEG.
+const JPEG_QUALITY: u8 = 90;
+
+/// The width the API recommends asking for.
+const DEFAULT_WIDTH: u32 = 843;
+
+/// Lengths tried, largest first, when a server refuses a size.
+const FALLBACK_LENGTHS: [u32; 4] = [843, 600, 400, 200];
+
+/// What's known about a stored image.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sidecar {
+    /// The canonical image request.
+    pub request: String,
+    /// The media type, e.g. `image/jpeg`.
+    pub content_type: String,
+    /// Width in pixels, if the image could be decoded.
+    pub width: Option<u32>,
+    /// Height in pixels, if the image could be decoded.
+    pub height: Option<u32>,
+    /// The artwork the image belongs to, if known.
+    pub artwork_id: Option<u32>,
+    /// The hash the bytes are stored under.
+    pub blob: String,
+}
+
+/// An image and what's known about it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    bytes: Bytes,
+    sidecar: Sidecar,
+}
+
+impl Image {
+    /// Describes some image bytes fetched for a request.
+    ///
+    /// The content type falls back to what the bytes look like, and then to the requested format.
+    pub fn new(
+        request: &ImageRequest,
+        bytes: Bytes,
+        content_type: Option<&str>,
+        artwork_id: Option<u32>,
+    ) -> Self {
+        let dimensions = image::io::Reader::new(Cursor::new(&bytes))
+            .with_guessed_format()
+            .ok()
+            .and_then(|reader| reader.into_dimensions().ok());
+        let content_type = content_type
+            .map(str::to_string)
+            .or_else(|| {
+                image::guess_format(&bytes)
+                    .ok()
+                    .map(|format| format.to_mime_type().to_string())
+            })
+            .unwrap_or_else(|| mime_type(request.format()).to_string());
+        let sidecar = Sidecar {
+            request: canonical(request),
+            content_type,
+            width: dimensions.map(|(width, _)| width),
+            height: dimensions.map(|(_, height)| height),
+            artwork_id,
+            blob: blob_name(&bytes),
+        };
+        Image { bytes, sidecar }
+    }
+
+    /// Returns the image bytes.
+    pub fn bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+
+    /// Returns the image bytes, consuming the image.
+    pub fn into_bytes(self) -> Bytes {
+        self.bytes
+    }
+
+    /// Returns what's known about the image.
+    pub fn sidecar(&self) -> &Sidecar {
+        &self.sidecar
+    }
+
+    /// Checks that a response for a request really is an image.
+    ///
+    /// Error pages sometimes come back with a success status, so the content type has to say
+    /// it's an image, and formats we can decode have to decode.
+    pub(crate) fn validate(&self, format: &Format) -> Result<(), AcresError> {
+        let content_type = self.sidecar.content_type.as_str();
+        let expected = content_type.starts_with("image/")
+            || (*format == Format::Pdf && content_type.starts_with(mime_type(format)));
+        if !expected {
+            return Err(AcresError::InvalidImage(format!(
+                "{} is {content_type}",
+                self.sidecar.request
+            )));
+        }
+        let decodable = output_format(format).is_some() || *format == Format::WebP;
+        if decodable && self.sidecar.width.is_none() {
+            return Err(AcresError::InvalidImage(format!(
+                "{} couldn't be decoded",
+                self.sidecar.request
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Options for fetching an artwork's image with [`Api::fetch_image`].
+///
+/// These are the parts of an [`ImageRequest`] that aren't about the artwork, plus the
+/// [rights policy] to apply and whether to fall back to smaller sizes. The defaults ask for the
+/// recommended `full/843,/0/default.jpg`.
+///
+/// ```rust
+/// use acres::{images::ImageOptions, rights::Policy};
+///
+/// let options = ImageOptions::new()
+///     .size(iiif::Size::Width(600))
+///     .format(iiif::Format::Png)
+///     .policy(Some(Policy::default()));
+/// ```
+///
+/// [`Api::fetch_image`]: ../struct.Api.html#method.fetch_image
+/// [rights policy]: crate::rights::Policy
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageOptions {
+    region: Region,
+    size: Size,
+    rotation: Rotation,
+    quality: Quality,
+    format: Format,
+    policy: Option<Policy>,
+    fallback: bool,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        ImageOptions {
+            region: Region::Full,
+            size: Size::Width(DEFAULT_WIDTH),
+            rotation: Rotation::Degrees(0.0.try_into().expect("0 degrees is a valid setting")),
+            quality: Quality::Default,
+            format: Format::Jpg,
+            policy: None,
+            fallback: true,
+        }
+    }
+}
+
+impl ImageOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        ImageOptions::default()
+    }
+
+    /// Sets the region.
+    pub fn region(mut self, region: Region) -> Self {
+        self.region = region;
+        self
+    }
+
+    /// Sets the size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the rotation.
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Sets the quality.
+    pub fn quality(mut self, quality: Quality) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    /// Sets the format.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the rights policy that limits sizes for works that aren't in the public domain.
+    pub fn policy(mut self, policy: Option<Policy>) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets whether to try smaller sizes when the server refuses the one asked for.
+    ///
+    /// On by default.
+    pub fn fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Returns the image request for an artwork, after applying the rights policy.
+    pub fn request(&self, artwork: &ArtworkInfo) -> Result<ImageRequest, AcresError> {
+        let size = match &self.policy {
+            Some(policy) => policy.apply(artwork.data.is_public_domain, self.size.clone()),
+            None => self.size.clone(),
+        };
+        Ok(ImageRequest::builder()
+            .uri(iiif::Uri::try_from(artwork.clone())?)
+            .region(self.region.clone())
+            .size(size)
+            .rotation(self.rotation.clone())
+            .quality(self.quality.clone())
+            .format(self.format.clone())
+            .build())
+    }
+
+    /// Returns the sizes to try, in order, when `size` is refused.
+    pub(crate) fn fallbacks(&self, size: &Size) -> Vec<Size> {
+        if !self.fallback {
+            return vec![];
+        }
+        let smaller = |than: u32| {
+            FALLBACK_LENGTHS
+                .into_iter()
+                .filter(move |&length| length < than)
+        };
+        match *size {
+            Size::Width(width) => smaller(width).map(Size::Width).collect(),
+            Size::Height(height) => smaller(height).map(Size::Height).collect(),
+            Size::BestFit(width, height) => smaller(width.max(height))
+                .map(|length| Size::BestFit(length, length))
+                .collect(),
+            Size::Full | Size::Percentage(_) | Size::Exactly(_, _) => {
+                FALLBACK_LENGTHS.into_iter().map(Size::Width).collect()
+            }
+        }
+    }
+}
+
+/// An artwork's image, as fetched by [`Api::fetch_image`].
+///
+/// [`Api::fetch_image`]: ../struct.Api.html#method.fetch_image
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArtworkImage {
+    artwork: ArtworkInfo,
+    requested: ImageRequest,
+    request: ImageRequest,
+    image: Image,
+}
+
+impl ArtworkImage {
+    pub(crate) fn new(
+        artwork: ArtworkInfo,
+        requested: ImageRequest,
+        request: ImageRequest,
+        image: Image,
+    ) -> Self {
+        ArtworkImage {
+            artwork,
+            requested,
+            request,
+            image,
+        }
+    }
+
+    /// Returns the artwork the image belongs to.
+    pub fn artwork(&self) -> &ArtworkInfo {
+        &self.artwork
+    }
+
+    /// Returns the request that was asked for, after applying the rights policy.
+    pub fn requested(&self) -> &ImageRequest {
+        &self.requested
+    }
+
+    /// Returns the request that was served, which is smaller than the one asked for if the
+    /// server refused that.
+    pub fn request(&self) -> &ImageRequest {
+        &self.request
+    }
+
+    /// Returns whether a smaller size was served than was asked for.
+    pub fn is_fallback(&self) -> bool {
+        self.request != self.requested
+    }
+
+    /// Returns the media type, e.g. `image/jpeg`.
+    pub fn content_type(&self) -> &str {
+        &self.image.sidecar.content_type
+    }
+
+    /// Returns the width and height in pixels, if the image could be decoded.
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        self.image.sidecar.width.zip(self.image.sidecar.height)
+    }
+
+    /// Returns the image bytes.
+    pub fn bytes(&self) -> &Bytes {
+        self.image.bytes()
+    }
+
+    /// Returns the image bytes, consuming the image.
+    pub fn into_bytes(self) -> Bytes {
+        self.image.into_bytes()
+    }
+
+    /// Returns the stored image.
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+}
+
+/// A directory of IIIF images.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageStore {
+    dir: PathBuf,
+}
+
+impl ImageStore {
+    /// Opens (or creates) a store in `dir`.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, AcresError> {
+        let dir = dir.as_ref().to_path_buf();
+        for sub in ["blobs", "entries"] {
+            std::fs::create_dir_all(dir.join(sub))
+                .with_context(|| format!("creating image store at {}", dir.display()))?;
+        }
+        Ok(ImageStore { dir })
+    }
+
+    /// Opens the store in an API client's cache directory.
+    pub fn in_cache(api: &Api) -> Result<Self, AcresError> {
+        ImageStore::open(api.cache_dir().join("images"))
+    }
+
+    /// Loads the image for a request, scaling down a larger one if there's no exact match.
+    pub fn get(&self, request: &ImageRequest) -> Result<Option<Image>, AcresError> {
+        if let Some(image) = self.load(&self.entry_path(request))? {
+            return Ok(Some(image));
+        }
+        self.downscale(request)
+    }
+
+    /// Stores the image fetched for a request.
+    pub fn put(
+        &self,
+        request: &ImageRequest,
+        bytes: Bytes,
+        content_type: Option<&str>,
+        artwork_id: Option<u32>,
+    ) -> Result<Image, AcresError> {
+        let image = Image::new(request, bytes, content_type, artwork_id);
+        self.save(&image)?;
+        Ok(image)
+    }
+
+    pub(crate) fn save(&self, image: &Image) -> Result<(), AcresError> {
+        let request = ImageRequest::from_str(&image.sidecar.request)?;
+        let entry = self.entry_path(&request);
+        let sidecar = serde_json::to_vec_pretty(&image.sidecar).context("serializing sidecar")?;
+        let _lock = DirLock::exclusive(&self.dir)?;
+        let blob = self.blob_path(&image.sidecar.blob);
+        if !blob.is_file() {
+            cache::write_atomic(&blob, &image.bytes)?;
+        }
+        if let Some(parent) = entry.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        cache::write_atomic(&entry, &sidecar)?;
+        tracing::info!("Stored image '{}'", image.sidecar.request);
+        Ok(())
+    }
+
+    /// Loads an entry, quarantining it if it's corrupt or its bytes are gone.
+    fn load(&self, entry: &Path) -> Result<Option<Image>, AcresError> {
+        if !entry.is_file() {
+            return Ok(None);
+        }
+        let data = std::fs::read(entry).with_context(|| format!("reading {}", entry.display()))?;
+        let Ok(sidecar) = serde_json::from_slice::<Sidecar>(&data) else {
+            cache::quarantine(entry)?;
+            return Ok(None);
+        };
+        let blob = self.blob_path(&sidecar.blob);
+        let bytes = match std::fs::read(&blob) {
+            Ok(bytes) if blob_name(&bytes) == sidecar.blob => Bytes::from(bytes),
+            Ok(_) => {
+                cache::quarantine(&blob)?;
+                cache::quarantine(entry)?;
+                return Ok(None);
+            }
+            Err(_) => {
+                cache::quarantine(entry)?;
+                return Ok(None);
+            }
+        };
+        Ok(Some(Image { bytes, sidecar }))
+    }
+
+    /// Scales down the smallest stored rendition that's large enough, if there is one.
+    fn downscale(&self, request: &ImageRequest) -> Result<Option<Image>, AcresError> {
+        let Some(output) = output_format(request.format()) else {
+            return Ok(None);
+        };
+        let Ok(entries) = std::fs::read_dir(self.group_path(request)) else {
+            return Ok(None);
+        };
+        let mut best: Option<(Image, (u32, u32))> = None;
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let Some(candidate) = self.load(&entry.path())? else {
+                continue;
+            };
+            let Ok(stored) = ImageRequest::from_str(&candidate.sidecar.request) else {
+                continue;
+            };
+            let same_region = stored.uri() == request.uri()
+                && stored.region() == request.region()
+                && stored.rotation() == request.rotation()
+                && stored.quality() == request.quality();
+            let (Some(width), Some(height)) = (candidate.sidecar.width, candidate.sidecar.height)
+            else {
+                continue;
+            };
+            let Some(target) =
+                target_dimensions(request.size(), width, height).filter(|_| same_region)
+            else {
+                continue;
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(best, _)| best.sidecar.width > Some(width))
+            {
+                best = Some((candidate, target));
+            }
+        }
+        let Some((source, (width, height))) = best else {
+            return Ok(None);
+        };
+        let decoded = image::load_from_memory(&source.bytes).context("decoding stored image")?;
+        let scaled = decoded.resize_exact(width, height, FilterType::Lanczos3);
+        let mut bytes = Cursor::new(vec![]);
+        scaled
+            .write_to(&mut bytes, output)
+            .context("encoding scaled image")?;
+        tracing::info!(
+            "Scaled '{}' down for '{}'",
+            source.sidecar.request,
+            canonical(request)
+        );
+        let image = Image::new(
+            request,
+            bytes.into_inner().into(),
+            Some(mime_type(request.format())),
+            source.sidecar.artwork_id,
+        );
+        self.save(&image)?;
+        Ok(Some(image))
+    }
+
+    fn blob_path(&self, blob: &str) -> PathBuf {
+        self.dir.join("blobs").join(blob)
+    }
+
+    fn group_path(&self, request: &ImageRequest) -> PathBuf {
+        let group = xxhash_rust::xxh3::xxh3_64(request.uri().to_string().as_bytes());
+        self.dir.join("entries").join(format!("{group:016x}"))
+    }
+
+    fn entry_path(&self, request: &ImageRequest) -> PathBuf {
+        let entry = xxhash_rust::xxh3::xxh3_64(canonical(request).as_bytes());
+        self.group_path(request).join(format!("{entry:016x}.json"))
+    }
+}
+
+/// Returns the canonical form of a request, which is how it's keyed.
+///
+/// Parsing and printing the request normalizes things like percentages, so equivalent requests
+/// share an entry.
+fn canonical(request: &ImageRequest) -> String {
+    let request = request.to_string();
+    ImageRequest::from_str(&request).map_or(request, |parsed| parsed.to_string())
+}
+
+fn blob_name(bytes: &[u8]) -> String {
+    format!("{:032x}", xxhash_rust::xxh3::xxh3_128(bytes))
+}
+
+fn mime_type(format: &Format) -> &'static str {
+    match format {
+        Format::Jpg => "image/jpeg",
+        Format::Tif => "image/tiff",
+        Format::Png => "image/png",
+        Format::Gif => "image/gif",
+        Format::Jp2 => "image/jp2",
+        Format::Pdf => "application/pdf",
+        Format::WebP => "image/webp",
+    }
+}
+
+/// Returns how to encode a scaled-down image, for the formats that can be.
+fn output_format(format: &Format) -> Option<ImageOutputFormat> {
+    match format {
+        Format::Jpg => Some(ImageOutputFormat::Jpeg(JPEG_QUALITY)),
+        Format::Png => Some(ImageOutputFormat::Png),
+        Format::Gif => Some(ImageOutputFormat::Gif),
+        Format::Tif => Some(ImageOutputFormat::Tiff),
+        Format::Jp2 | Format::Pdf | Format::WebP => None,
+    }
+}
+
+/// Returns the dimensions a size asks for, given a rendition of the same region.
+///
+/// Only sizes that a rendition at least as large can be scaled down to are handled.
+pub(crate) fn target_dimensions(size: &Size, width: u32, height: u32) -> Option<(u32, u32)> {
+    let scale = |length: u32, by: f64| ((f64::from(length) * by).round() as u32).max(1);
+    match *size {
+        Size::Width(w) if w <= width => Some((w, scale(height, f64::from(w) / f64::from(width)))),
+        Size::Height(h) if h <= height => Some((scale(width, f64::from(h) / f64::from(height)), h)),
+        Size::Exactly(w, h) if w <= width && h <= height => Some((w, h)),
+        Size::BestFit(w, h) => {
+            let by = (f64::from(w) / f64::from(width)).min(f64::from(h) / f64::from(height));
+            (by <= 1.0).then(|| (scale(width, by), scale(height, by)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(size: &str) -> ImageRequest {
+        format!("https://www.artic.edu/iiif/2/abc/full/{size}/0/default.png")
+            .parse()
+            .unwrap()
+    }
+
+    fn png(width: u32, height: u32) -> Bytes {
+        let mut bytes = Cursor::new(vec![]);
+        image::DynamicImage::new_rgb8(width, height)
+            .write_to(&mut bytes, ImageOutputFormat::Png)
+            .unwrap();
+        bytes.into_inner().into()
+    }
+
+    #[test]
+    fn exact_matches_keep_their_sidecar() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let store = ImageStore::open(dir.path()).unwrap();
+
+        store
+            .put(&request("40,"), png(40, 20), Some("image/png"), Some(7))
+            .unwrap();
+        let image = store.get(&request("40,")).unwrap().unwrap();
+
+        assert_eq!(image.bytes(), &png(40, 20));
+        assert_eq!(
+            image.sidecar(),
+            &Sidecar {
+                request: "https://www.artic.edu/iiif/2/abc/full/40,/0/default.png".into(),
+                content_type: "image/png".into(),
+                width: Some(40),
+                height: Some(20),
+                artwork_id: Some(7),
+                blob: blob_name(&png(40, 20)),
+            }
+        );
+    }
+
+    #[test]
+    fn identical_bytes_are_stored_once() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let store = ImageStore::open(dir.path()).unwrap();
+
+        store.put(&request("40,"), png(40, 20), None, None).unwrap();
+        store
+            .put(&request("!40,40"), png(40, 20), None, None)
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("blobs")).unwrap().count(),
+            1
+        );
+        assert!(store.get(&request("!40,40")).unwrap().is_some());
+    }
+
+    #[test]
+    fn smaller_sizes_are_scaled_down_locally() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let store = ImageStore::open(dir.path()).unwrap();
+        store
+            .put(&request("80,"), png(80, 40), None, Some(7))
+            .unwrap();
+        store
+            .put(&request("40,"), png(40, 20), None, Some(7))
+            .unwrap();
+
+        let image = store.get(&request(",10")).unwrap().unwrap();
+        assert_eq!(
+            (image.sidecar().width, image.sidecar().height),
+            (Some(20), Some(10))
+        );
+        assert_eq!(image.sidecar().content_type, "image/png");
+        assert_eq!(image.sidecar().artwork_id, Some(7));
+
+        assert!(store.get(&request("100,")).unwrap().is_none());
+        assert!(store.get(&request("full")).unwrap().is_none());
+    }
+
+    #[test]
+    fn corrupt_entries_are_misses() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let store = ImageStore::open(dir.path()).unwrap();
+        let image = store.put(&request("40,"), png(40, 20), None, None).unwrap();
+        std::fs::write(store.blob_path(&image.sidecar().blob), b"\x89PNG").unwrap();
+
+        assert!(store.get(&request("40,")).unwrap().is_none());
+        assert!(!store.entry_path(&request("40,")).exists());
+    }
+
+    #[test]
+    fn fallbacks_are_smaller_sizes() {
+        let options = ImageOptions::new();
+
+        assert_eq!(
+            options.fallbacks(&Size::Width(843)),
+            vec![Size::Width(600), Size::Width(400), Size::Width(200)]
+        );
+        assert_eq!(
+            options.fallbacks(&Size::BestFit(500, 300)),
+            vec![Size::BestFit(400, 400), Size::BestFit(200, 200)]
+        );
+        assert_eq!(options.fallbacks(&Size::Full).len(), 4);
+        assert!(options.fallback(false).fallbacks(&Size::Full).is_empty());
+    }
+
+    #[test]
+    fn non_images_are_rejected() {
+        let html = Image::new(&request("40,"), "<html>".into(), Some("text/html"), None);
+        assert!(matches!(
+            html.validate(&Format::Png),
+            Err(AcresError::InvalidImage(_))
+        ));
+
+        let truncated = Image::new(
+            &request("40,"),
+            Bytes::from_static(b"\x89PNG"),
+            Some("image/png"),
+            None,
+        );
+        assert!(truncated.validate(&Format::Png).is_err());
+
+        let png = Image::new(&request("40,"), png(40, 20), Some("image/png"), None);
+        assert!(png.validate(&Format::Png).is_ok());
+    }
+
+    #[test]
+    fn target_dimensions_only_scale_down() {
+        assert_eq!(
+            target_dimensions(&Size::Width(400), 843, 600),
+            Some((400, 285))
+        );
+        assert_eq!(
+            target_dimensions(&Size::BestFit(100, 100), 843, 600),
+            Some((100, 71))
+        );
+        assert_eq!(
+            target_dimensions(&Size::BestFit(1000, 1000), 843, 600),
+            None
+        );
+        assert_eq!(target_dimensions(&Size::Exactly(900, 10), 843, 600), None);
+    }
+}
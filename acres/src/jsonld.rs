@@ -0,0 +1,487 @@
+//! JSON-LD for artworks.
+//!
+//! [`JsonLd`] maps an [`Artwork`], and any [agents] you've fetched for it, onto two vocabularies:
+//!
+//! - [Linked Art], for linked-data partners, as a `HumanMadeObject`, and
+//! - [schema.org], for embedding in web pages, as a `VisualArtwork`.
+//!
+//! Both include references to the artwork's IIIF image service, when it has an image.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use acres::{artworks::Artwork, jsonld::JsonLd};
+//!
+//! # fn main() -> Result<()> {
+//! let artwork: Artwork = serde_json::from_str(r#"{
+//!     "data": { "id": 4, "title": "Priest and Boy", "image_id": "2d484387-2509-5e8e-2c43-22f9981972eb" },
+//!     "info": { "license_text": "", "license_links": [], "version": "1.13" },
+//!     "config": { "iiif_url": "https://www.artic.edu/iiif/2", "website_url": "https://www.artic.edu" }
+//! }"#)?;
+//!
+//! let schema_org = JsonLd::new(&artwork).schema_org();
+//! assert_eq!(schema_org["@type"], "VisualArtwork");
+//! assert_eq!(
+//!     schema_org["image"]["contentUrl"],
+//!     "https://www.artic.edu/iiif/2/2d484387-2509-5e8e-2c43-22f9981972eb/full/843,/0/default.jpg"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Artwork`]: ../artworks/struct.Artwork.html
+//! [agents]: ../agents/struct.Agent.html
+//! [Linked Art]: https://linked.art/model/
+//! [schema.org]: https://schema.org/VisualArtwork
+
+use serde_json::{Value, json};
+
+use crate::{agents, artworks::Artwork};
+
+/// The width the museum recommends for full-size images.
+const IMAGE_WIDTH: u32 = 843;
+/// A width for thumbnails.
+const THUMBNAIL_WIDTH: u32 = 200;
+
+// Getty AAT terms used to classify Linked Art statements and identifiers.
+const AAT_PRIMARY_NAME: &str = "http://vocab.getty.edu/aat/300404670";
+const AAT_ACCESSION_NUMBER: &str = "http://vocab.getty.edu/aat/300312355";
+const AAT_BRIEF_TEXT: &str = "http://vocab.getty.edu/aat/300418049";
+const AAT_MATERIAL_STATEMENT: &str = "http://vocab.getty.edu/aat/300435429";
+const AAT_DIMENSION_STATEMENT: &str = "http://vocab.getty.edu/aat/300435430";
+const AAT_CREDIT_LINE: &str = "http://vocab.getty.edu/aat/300435418";
+const AAT_ARTIST_STATEMENT: &str = "http://vocab.getty.edu/aat/300435446";
+const AAT_WEB_PAGE: &str = "http://vocab.getty.edu/aat/300264578";
+
+/// JSON-LD serialisers for an artwork.
+#[derive(Clone, Debug)]
+pub struct JsonLd<'a> {
+    artwork: &'a Artwork,
+    agents: &'a [agents::Data],
+}
+
+impl<'a> JsonLd<'a> {
+    /// Creates serialisers for `artwork`.
+    pub fn new(artwork: &'a Artwork) -> Self {
+        Self {
+            artwork,
+            agents: &[],
+        }
+    }
+
+    /// Sets agents to draw on for the artist's details.
+    ///
+    /// Without these, the artist is described with just the artwork's `artist_id` and
+    /// `artist_title`.
+    pub fn agents(mut self, agents: &'a [agents::Data]) -> Self {
+        self.agents = agents;
+        self
+    }
+
+    /// Returns the artwork as a Linked Art `HumanMadeObject`.
+    pub fn linked_art(&self) -> Value {
+        let data = &self.artwork.data;
+        let mut object = json!({
+            "@context": "https://linked.art/ns/v1/linked-art.json",
+            "id": self.object_uri(),
+            "type": "HumanMadeObject",
+            "_label": data.title,
+            "identified_by": [{
+                "type": "Name",
+                "classified_as": [aat(AAT_PRIMARY_NAME, "Primary Name")],
+                "content": data.title,
+            }],
+        });
+        if let Some(number) = &data.main_reference_number {
+            push(
+                &mut object,
+                "identified_by",
+                json!({
+                    "type": "Identifier",
+                    "classified_as": [aat(AAT_ACCESSION_NUMBER, "Accession Number")],
+                    "content": number,
+                }),
+            );
+        }
+        if let Some(classification) = &data.classification_title {
+            object["classified_as"] = json!([{ "type": "Type", "_label": classification }]);
+        }
+        let statements = [
+            (
+                &data.artist_display,
+                AAT_ARTIST_STATEMENT,
+                "Artist/Maker Description",
+            ),
+            (
+                &data.medium_display,
+                AAT_MATERIAL_STATEMENT,
+                "Material Statement",
+            ),
+            (
+                &data.dimensions,
+                AAT_DIMENSION_STATEMENT,
+                "Dimensions Statement",
+            ),
+            (&data.credit_line, AAT_CREDIT_LINE, "Credit Line"),
+        ];
+        for (content, id, label) in statements {
+            if let Some(content) = content {
+                push(
+                    &mut object,
+                    "referred_to_by",
+                    json!({
+                        "type": "LinguisticObject",
+                        "classified_as": [{
+                            "id": id,
+                            "type": "Type",
+                            "_label": label,
+                            "classified_as": [aat(AAT_BRIEF_TEXT, "Brief Text")],
+                        }],
+                        "content": content,
+                    }),
+                );
+            }
+        }
+        let mut production = json!({ "type": "Production" });
+        if let Some(timespan) =
+            timespan(data.date_display.as_deref(), data.date_start, data.date_end)
+        {
+            production["timespan"] = timespan;
+        }
+        if let Some(artist) = self.linked_art_artist() {
+            production["carried_out_by"] = json!([artist]);
+        }
+        if production.as_object().is_some_and(|p| p.len() > 1) {
+            object["produced_by"] = production;
+        }
+        if let Some(uri) = self.iiif_uri() {
+            object["representation"] = json!([{
+                "type": "VisualItem",
+                "digitally_shown_by": [{
+                    "type": "DigitalObject",
+                    "format": "image/jpeg",
+                    "access_point": [{ "id": image_url(&uri, IMAGE_WIDTH), "type": "DigitalObject" }],
+                    "digitally_available_via": [{
+                        "type": "DigitalService",
+                        "access_point": [{ "id": uri.to_string(), "type": "DigitalObject" }],
+                        "conforms_to": [{ "id": "http://iiif.io/api/image", "type": "InformationObject" }],
+                    }],
+                }],
+            }]);
+        }
+        object["subject_of"] = json!([{
+            "type": "LinguisticObject",
+            "digitally_carried_by": [{
+                "type": "DigitalObject",
+                "classified_as": [aat(AAT_WEB_PAGE, "Web Page")],
+                "format": "text/html",
+                "access_point": [{ "id": self.url(), "type": "DigitalObject" }],
+            }],
+        }]);
+        object
+    }
+
+    /// Returns the artwork as a schema.org `VisualArtwork`.
+    pub fn schema_org(&self) -> Value {
+        let data = &self.artwork.data;
+        let mut object = json!({
+            "@context": "https://schema.org",
+            "@type": "VisualArtwork",
+            "@id": self.object_uri(),
+            "url": self.url(),
+            "name": data.title,
+            "provider": {
+                "@type": "Museum",
+                "name": "Art Institute of Chicago",
+                "url": self.artwork.config.website_url(),
+            },
+        });
+        let properties = [
+            ("identifier", &data.main_reference_number),
+            ("dateCreated", &data.date_display),
+            ("artMedium", &data.medium_display),
+            ("artform", &data.classification_title),
+            ("creditText", &data.credit_line),
+            ("size", &data.dimensions),
+        ];
+        for (name, value) in properties {
+            if let Some(value) = value {
+                object[name] = json!(value);
+            }
+        }
+        if let Some(artist) = self.schema_org_artist() {
+            object["creator"] = json!([artist]);
+        }
+        if let Some(uri) = self.iiif_uri() {
+            let mut image = json!({
+                "@type": "ImageObject",
+                "contentUrl": image_url(&uri, IMAGE_WIDTH),
+                "thumbnailUrl": image_url(&uri, THUMBNAIL_WIDTH),
+                "encodingFormat": "image/jpeg",
+                "sameAs": iiif::InformationRequest::new(uri).to_string(),
+            });
+            let thumbnail = data.thumbnail.as_ref();
+            if let Some(alt_text) = thumbnail.and_then(|t| t.alt_text.as_ref()) {
+                image["caption"] = json!(alt_text);
+            }
+            if let Some((width, height)) = thumbnail.and_then(|t| t.width.zip(t.height)) {
+                image["width"] = json!(width);
+                image["height"] = json!(height);
+            }
+            object["image"] = image;
+        }
+        object
+    }
+
+    fn url(&self) -> String {
+        self.artwork.config.artwork_url(self.artwork.data.id)
+    }
+
+    /// Returns the URI of the artwork itself, as opposed to its web page.
+    fn object_uri(&self) -> String {
+        format!("{}#object", self.url())
+    }
+
+    fn iiif_uri(&self) -> Option<iiif::Uri> {
+        iiif::Uri::try_from(self.artwork).ok()
+    }
+
+    /// Finds the preferred artist, from the agents if we have it there.
+    fn artist(&self) -> Option<(u64, String, Option<&agents::Data>)> {
+        let id = self.artwork.data.artist_id?;
+        let agent = self.agents.iter().find(|agent| agent.id == id);
+        let name = agent
+            .map(|agent| agent.title.clone())
+            .or_else(|| self.artwork.data.artist_title.clone())?;
+        Some((id, name, agent))
+    }
+
+    fn linked_art_artist(&self) -> Option<Value> {
+        let (id, name, agent) = self.artist()?;
+        let mut person = json!({
+            "id": self.artwork.config.artist_url(id),
+            "type": "Person",
+            "_label": name,
+        });
+        if let Some(year) = agent.and_then(|agent| agent.birth_date) {
+            person["born"] = json!({ "type": "Birth", "timespan": year_timespan(year) });
+        }
+        if let Some(year) = agent.and_then(|agent| agent.death_date) {
+            person["died"] = json!({ "type": "Death", "timespan": year_timespan(year) });
+        }
+        Some(person)
+    }
+
+    fn schema_org_artist(&self) -> Option<Value> {
+        let (id, name, agent) = self.artist()?;
+        let mut person = json!({
+            "@type": "Person",
+            "@id": self.artwork.config.artist_url(id),
+            "name": name,
+        });
+        if let Some(year) = agent.and_then(|agent| agent.birth_date) {
+            person["birthDate"] = json!(iso_year(year));
+        }
+        if let Some(year) = agent.and_then(|agent| agent.death_date) {
+            person["deathDate"] = json!(iso_year(year));
+        }
+        Some(person)
+    }
+}
+
+fn aat(id: &str, label: &str) -> Value {
+    json!({ "id": id, "type": "Type", "_label": label })
+}
+
+fn push(object: &mut Value, key: &str, value: Value) {
+    match object[key].as_array_mut() {
+        Some(values) => values.push(value),
+        None => object[key] = json!([value]),
+    }
+}
+
+fn image_url(uri: &iiif::Uri, width: u32) -> String {
+    iiif::ImageRequest::new(
+        uri.clone(),
+        iiif::Region::default(),
+        iiif::Size::Width(width),
+        iiif::Rotation::default(),
+        iiif::Quality::default(),
+        iiif::Format::default(),
+    )
+    .to_string()
+}
+
+/// Formats a year for ISO 8601, which numbers years astronomically: 1 BCE is year 0.
+fn iso_year(year: i64) -> String {
+    if year < 0 {
+        format!("-{:04}", -year - 1)
+    } else {
+        format!("{year:04}")
+    }
+}
+
+fn year_timespan(year: i64) -> Value {
+    json!({
+        "type": "TimeSpan",
+        "begin_of_the_begin": format!("{}-01-01T00:00:00Z", iso_year(year)),
+        "end_of_the_end": format!("{}-12-31T23:59:59Z", iso_year(year)),
+    })
+}
+
+fn timespan(display: Option<&str>, start: Option<i64>, end: Option<i64>) -> Option<Value> {
+    if display.is_none() && start.is_none() && end.is_none() {
+        return None;
+    }
+    let mut timespan = json!({ "type": "TimeSpan" });
+    if let Some(display) = display {
+        timespan["identified_by"] = json!([{ "type": "Name", "content": display }]);
+    }
+    if let Some(start) = start.or(end) {
+        timespan["begin_of_the_begin"] = json!(format!("{}-01-01T00:00:00Z", iso_year(start)));
+    }
+    if let Some(end) = end.or(start) {
+        timespan["end_of_the_end"] = json!(format!("{}-12-31T23:59:59Z", iso_year(end)));
+    }
+    Some(timespan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::envelope;
+
+    fn artwork() -> Artwork {
+        serde_json::from_value(envelope(json!({
+            "id": 28560,
+            "title": "The Bedroom",
+            "image_id": "25c31d8d-21a4-9ea1-1d73-6a2eca4dda7e",
+            "artist_id": 40610,
+            "artist_title": "Vincent van Gogh",
+            "artist_display": "Vincent van Gogh (Dutch, 1853–1890)",
+            "date_display": "1889",
+            "date_start": 1889,
+            "date_end": 1889,
+            "medium_display": "Oil on canvas",
+            "dimensions": "73.6 × 92.3 cm (29 × 36 5/8 in.)",
+            "credit_line": "Helen Birch Bartlett Memorial Collection",
+            "main_reference_number": "1926.417",
+            "classification_title": "painting",
+            "thumbnail": {
+                "alt_text": "Painting of bedroom, blue walls, green window, tan bed.",
+                "width": 3000,
+                "height": 2381
+            }
+        })))
+        .unwrap()
+    }
+
+    fn agents() -> Vec<agents::Data> {
+        vec![agents::Data {
+            id: 40610,
+            title: "Vincent van Gogh".to_string(),
+            birth_date: Some(1853),
+            death_date: Some(1890),
+            is_artist: Some(true),
+        }]
+    }
+
+    #[test]
+    fn linked_art() {
+        let artwork = artwork();
+        let agents = agents();
+
+        let object = JsonLd::new(&artwork).agents(&agents).linked_art();
+
+        assert_eq!(object["type"], "HumanMadeObject");
+        assert_eq!(object["id"], "https://www.artic.edu/artworks/28560#object");
+        assert_eq!(
+            object["subject_of"][0]["digitally_carried_by"][0]["access_point"][0]["id"],
+            "https://www.artic.edu/artworks/28560"
+        );
+        assert_eq!(object["identified_by"][1]["content"], "1926.417");
+        assert_eq!(
+            object["identified_by"][1]["classified_as"][0]["id"],
+            AAT_ACCESSION_NUMBER
+        );
+        assert_eq!(object["referred_to_by"][1]["content"], "Oil on canvas");
+        assert_eq!(
+            object["produced_by"]["timespan"],
+            json!({
+                "type": "TimeSpan",
+                "identified_by": [{ "type": "Name", "content": "1889" }],
+                "begin_of_the_begin": "1889-01-01T00:00:00Z",
+                "end_of_the_end": "1889-12-31T23:59:59Z",
+            })
+        );
+        assert_eq!(
+            object["produced_by"]["carried_out_by"][0]["born"]["timespan"]["begin_of_the_begin"],
+            "1853-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            object["representation"][0]["digitally_shown_by"][0]["digitally_available_via"][0]["access_point"]
+                [0]["id"],
+            "https://www.artic.edu/iiif/2/25c31d8d-21a4-9ea1-1d73-6a2eca4dda7e"
+        );
+    }
+
+    #[test]
+    fn schema_org() {
+        let artwork = artwork();
+        let agents = agents();
+
+        let object = JsonLd::new(&artwork).agents(&agents).schema_org();
+
+        assert_eq!(
+            object["creator"],
+            json!([{
+                "@type": "Person",
+                "@id": "https://www.artic.edu/artists/40610",
+                "name": "Vincent van Gogh",
+                "birthDate": "1853",
+                "deathDate": "1890",
+            }])
+        );
+        assert_eq!(
+            object["image"],
+            json!({
+                "@type": "ImageObject",
+                "contentUrl": "https://www.artic.edu/iiif/2/25c31d8d-21a4-9ea1-1d73-6a2eca4dda7e/full/843,/0/default.jpg",
+                "thumbnailUrl": "https://www.artic.edu/iiif/2/25c31d8d-21a4-9ea1-1d73-6a2eca4dda7e/full/200,/0/default.jpg",
+                "encodingFormat": "image/jpeg",
+                "sameAs": "https://www.artic.edu/iiif/2/25c31d8d-21a4-9ea1-1d73-6a2eca4dda7e/info.json",
+                "caption": "Painting of bedroom, blue walls, green window, tan bed.",
+                "width": 3000,
+                "height": 2381,
+            })
+        );
+        assert_eq!(object["identifier"], "1926.417");
+        assert_eq!(object["artMedium"], "Oil on canvas");
+    }
+
+    #[test]
+    fn works_without_image_or_agents() {
+        let mut artwork = artwork();
+        artwork.data.image_id = None;
+        artwork.data.date_start = Some(-500);
+        artwork.data.date_end = Some(-450);
+
+        let linked_art = JsonLd::new(&artwork).linked_art();
+        let schema_org = JsonLd::new(&artwork).schema_org();
+
+        assert!(linked_art.get("representation").is_none());
+        assert_eq!(
+            linked_art["produced_by"]["timespan"]["begin_of_the_begin"],
+            "-0499-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            linked_art["produced_by"]["carried_out_by"][0]["_label"],
+            "Vincent van Gogh"
+        );
+        assert!(
+            linked_art["produced_by"]["carried_out_by"][0]
+                .get("born")
+                .is_none()
+        );
+        assert!(schema_org.get("image").is_none());
+    }
+}
@@ -128,6 +128,38 @@
 //! # }
 //! ```
 //!
+//! If you'd rather not thread the base URI through by hand, the [fluent entrypoints] on [`Api`]
+//! do that for you. Each request also knows its [`Endpoint`] response type, so you get back an
+//! [`Artwork`], [`Artworks`], [`Search`], or [`Manifest`] instead of having to pick one.
+//!
+//! ```rust
+//! # use serde_json::json;
+//! # use anyhow::Result;
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! # let body = json!(
+//! # {
+//! #     "data": { "id": 4, "title": "Priest and Boy", "image_id": null },
+//! #     "info": { "license_text": "", "license_links": [], "version": "1.13" },
+//! #     "config": { "iiif_url": "https://www.artic.edu/iiif/2", "website_url": "https://www.artic.edu" }
+//! # }
+//! # );
+//! # let mock_server = wiremock::MockServer::start().await;
+//! # let mock_uri = format!("{}/api/v1", mock_server.uri());
+//! # wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/4"))
+//! #     .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(body))
+//! #     .expect(1)
+//! #     .mount(&mock_server)
+//! #     .await;
+//! let api = acres::Api::new();
+//! # let api = acres::Api::builder().base_uri(&mock_uri).use_cache(false).build();
+//!
+//! let artwork = api.artworks().get(4).send().await?;
+//! assert_eq!(artwork.data.title, "Priest and Boy");
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! We currently support the following endpoints under the Artworks collection:
 //!
 //! - [`GET /artworks`] via [`acres::artworks::request::artworks`]
@@ -135,6 +167,41 @@
 //! - [`GET /artworks/{id}`] via [`acres::artworks::request::artwork`]
 //! - [`GET /artworks/{id}/manifest.json`] via [`acres::artworks::request::manifest`]
 //!
+//! Self-guided [`tours`] are available too, through [`GET /tours`] and the recordings played at
+//! each stop from [`GET /mobile-sounds`]. A tour's [`Guide`] lists its artworks in order, with
+//! their IIIF URIs and transcripts.
+//!
+//! To work with the collection offline, [`sync`] keeps a local copy up to date, and, with the
+//! `sqlite` feature, [`mirror`] keeps that copy in a SQLite database you can query with SQL.
+//!
+//! IIIF images are kept in their own [`images`] store, keyed by the image request. Fetch an
+//! artwork's image with [`Api::fetch_image`], which checks that what comes back is an image and
+//! falls back to smaller sizes when the server refuses large ones, or any request with
+//! [`Api::image`].
+//!
+//! For large pages or data dumps, [`stream`] decodes the items in `data` one at a time as the
+//! response arrives, rather than holding the whole response in memory.
+//!
+//! Instead of writing search JSON by hand, you can write a compact [`query`] such as
+//! `artist:monet date:1870..1890 -type:print`.
+//!
+//! Label text such as dates and sizes can be read into structured [`dating`] and [`dimensions`],
+//! say to lay out works at their true relative size, and provenance, exhibition and publication
+//! texts into a [`history`] of entries.
+//!
+//! Artworks refer to departments, styles, classifications and subjects by id. A [`vocabulary`]
+//! turns those ids into labels, and labels back into ids for search filters.
+//!
+//! To see how works connect, a [`graph`] follows artists, exhibitions, galleries and terms out
+//! from a few artworks, and writes the network as DOT, GraphML or JSON.
+//!
+//! The typed models were written against one version of the API. A [`schema`] check compares
+//! live or cached responses against them, and reports fields that are new, missing or have
+//! changed type, and responses from another API version.
+//!
+//! Before showing or saving an artwork's image, check its [`rights`]. Only public domain works
+//! may be downloaded or shown at full size, and every image needs its attribution.
+//!
 //! [`GET /artworks`]: https://api.artic.edu/docs/#get-artworks
 //! [`acres::artworks::request::artworks`]: artworks/request/artworks/index.html
 //! [`GET /artworks/search`]: https://api.artic.edu/docs/#get-artworks-search
@@ -143,6 +210,10 @@
 //! [`acres::artworks::request::artwork`]: artworks/request/artwork/index.html
 //! [`GET /artworks/{id}/manifest.json`]: https://api.artic.edu/docs/#get-artworks-id-manifest-json
 //! [`acres::artworks::request::manifest`]: artworks/request/manifest/index.html
+//! [`GET /tours`]: https://api.artic.edu/docs/#get-tours
+//! [`GET /mobile-sounds`]: https://api.artic.edu/docs/#get-mobile-sounds
+//! [`tours`]: tours/index.html
+//! [`Guide`]: tours/struct.Guide.html
 //! [Artworks collection]: https://api.artic.edu/docs/#artworks
 //! [API helper]: struct.Api.html
 //! [fetch function]: fn.fetch.html
@@ -152,13 +223,67 @@
 //! [`acres-cli`]: ../acres_cli/index.html
 //! [`acres-tui`]: ../acres_tui/index.html
 //! [builders]: https://rust-unofficial.github.io/patterns/patterns/creational/builder.html
+//! [fluent entrypoints]: client/index.html
+//! [`sync`]: sync/index.html
+//! [`mirror`]: mirror/index.html
+//! [`rights`]: rights/index.html
+//! [`dating`]: dating/index.html
+//! [`dimensions`]: dimensions/index.html
+//! [`history`]: history/index.html
+//! [`vocabulary`]: vocabulary/index.html
+//! [`graph`]: graph/index.html
+//! [`schema`]: schema/index.html
+//! [`query`]: query/index.html
+//! [`stream`]: stream/index.html
+//! [`images`]: images/index.html
+//! [`Api::image`]: struct.Api.html#method.image
+//! [`Api::fetch_image`]: struct.Api.html#method.fetch_image
+//! [`Api`]: struct.Api.html
+//! [`Endpoint`]: trait.Endpoint.html
+//! [`Artwork`]: artworks/struct.Artwork.html
+//! [`Artworks`]: artworks/struct.Artworks.html
+//! [`Search`]: artworks/struct.Search.html
+//! [`Manifest`]: artworks/struct.Manifest.html
 
+pub mod agents;
 mod api;
 pub mod artworks;
+mod cache;
+mod cassette;
+pub mod citation;
+pub mod client;
+pub mod color;
 mod config;
+pub mod dating;
+pub mod dimensions;
+mod endpoint;
+pub mod exhibitions;
+pub mod export;
+#[cfg(test)]
+mod fixtures;
+pub mod galleries;
+pub mod graph;
+pub mod history;
+pub mod images;
+pub mod jsonld;
+#[cfg(feature = "sqlite")]
+pub mod mirror;
+pub mod query;
+pub mod random;
+pub mod rights;
+pub mod schema;
+pub mod similar;
+pub mod sounds;
+pub mod stream;
+pub mod sync;
+pub mod tours;
+pub mod vocabulary;
 
+pub use self::config::{AsciiConfig, Config, RightsConfig};
 pub use api::fetch;
-pub use api::{Api, Cached};
+pub use api::{Api, ApiBuilder, Cached};
+pub use cassette::{Cassette, CassetteMode};
+pub use endpoint::Endpoint;
 
 /// An Acres error.
 #[derive(Debug, thiserror::Error)]
@@ -172,6 +297,33 @@ pub enum AcresError {
     /// A search query parameter error
     #[error("search query parameters error: {0}")]
     InvalidSearchQueryParams(String),
+    /// Unable to read a color
+    #[error("unable to read color: {0}")]
+    InvalidColor(String),
+    /// Unable to read a date
+    #[error("unable to read date: {0}")]
+    InvalidDate(String),
+    /// An image server refused a request, with its status
+    #[error("image request refused with status {0}: {1}")]
+    ImageRefused(u16, String),
+    /// A response that should have been an image wasn't one
+    #[error("not an image: {0}")]
+    InvalidImage(String),
+    /// Unable to read dimensions
+    #[error("unable to read dimensions: {0}")]
+    InvalidDimensions(String),
+    /// A query has a syntax error
+    #[error("query error: {0}")]
+    InvalidQuery(#[from] query::ParseError),
+    /// Unable to load settings
+    #[error("config error: {0}")]
+    Config(#[from] ::config::ConfigError),
+    /// A sync would delete records without a complete listing to go by
+    #[error("refusing to delete synced records: {0}")]
+    UnsafeDeletion(String),
+    /// A cassette is replaying and has no recording for this request
+    #[error("no recorded interaction for {0}")]
+    MissingInteraction(String),
     /// An unexpected error.
     #[error(transparent)]
     Unexpected(#[from] anyhow::Error),
@@ -0,0 +1,835 @@
+//! A local SQLite mirror of the [Artworks collection].
+//!
+//! A [`Mirror`] keeps artworks, and the agents and galleries they refer to, in normalised tables
+//! so that you can run SQL joins over the collection. Every row also keeps the raw API record in
+//! a JSON `raw` column, so anything not broken out into a column is still one `->>` away.
+//!
+//! | Table             | Key                     | Notable columns                                          |
+//! |-------------------|-------------------------|----------------------------------------------------------|
+//! | `artworks`        | `id`                    | `title`, `artist_id`, `date_start`, `date_end`, `gallery_id`, `is_public_domain`, `image_id`, `timestamp` |
+//! | `artwork_artists` | `artwork_id, agent_id`  |                                                          |
+//! | `agents`          | `id`                    | `title`, `birth_date`, `death_date`                      |
+//! | `galleries`       | `id`                    | `title`, `floor`, `is_closed`                            |
+//!
+//! This module is only available with the `sqlite` feature.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! # use serde_json::json;
+//! use acres::mirror::Mirror;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! # let mock_server = wiremock::MockServer::start().await;
+//! # let mock_uri = format!("{}/api/v1", mock_server.uri());
+//! # wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+//! #     .and(wiremock::matchers::query_param_contains("query", "gte"))
+//! #     .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({"data": [
+//! #         {"id": 4, "title": "Priest and Boy", "artist_id": 34, "artist_ids": [34], "date_start": 1885,
+//! #          "timestamp": "2024-05-01T10:00:00-05:00"}
+//! #     ]})))
+//! #     .mount(&mock_server)
+//! #     .await;
+//! # wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+//! #     .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({"data": []})))
+//! #     .mount(&mock_server)
+//! #     .await;
+//! # wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+//! #     .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+//! #         "data": [{"id": 4}],
+//! #         "pagination": { "total": 1, "limit": 100, "offset": 0, "total_pages": 1, "current_page": 1 },
+//! #         "info": { "license_text": "", "license_links": [], "version": "1.13" },
+//! #         "config": { "iiif_url": "https://www.artic.edu/iiif/2", "website_url": "https://www.artic.edu" }
+//! #     })))
+//! #     .mount(&mock_server)
+//! #     .await;
+//! # wiremock::Mock::given(wiremock::matchers::path("/api/v1/agents"))
+//! #     .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({"data": [
+//! #         {"id": 34, "title": "William Merritt Chase", "birth_date": 1849, "death_date": 1916}
+//! #     ]})))
+//! #     .mount(&mock_server)
+//! #     .await;
+//! let api = acres::Api::new();
+//! # let api = acres::Api::builder().base_uri(&mock_uri).use_cache(false).build();
+//! let mut mirror = Mirror::open_in_memory()?;
+//! mirror.refresh(&api).await?;
+//!
+//! let artworks = mirror.artworks().artist(34).dated_between(1880, 1890).all()?;
+//! assert_eq!(artworks[0].title, "Priest and Boy");
+//!
+//! let (title, artist): (String, String) = mirror.connection().query_row(
+//!     "SELECT artworks.title, agents.title FROM artworks JOIN agents ON agents.id = artworks.artist_id",
+//!     [],
+//!     |row| Ok((row.get(0)?, row.get(1)?)),
+//! )?;
+//! assert_eq!(artist, "William Merritt Chase");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Artworks collection]: https://api.artic.edu/docs/#artworks
+
+use std::{collections::BTreeSet, path::Path};
+
+use anyhow::{Context, anyhow};
+use rusqlite::{Connection, OptionalExtension, Row, ToSql, params};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    AcresError, Api,
+    agents::request::agents as agents_request,
+    galleries::request::galleries as galleries_request,
+    sync::{Cursor, Report, Store, Syncer},
+};
+
+/// The artwork fields requested when refreshing a mirror.
+pub const ARTWORK_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "timestamp",
+    "artist_display",
+    "artist_id",
+    "artist_ids",
+    "artist_titles",
+    "date_start",
+    "date_end",
+    "date_display",
+    "medium_display",
+    "credit_line",
+    "classification_title",
+    "style_title",
+    "department_title",
+    "gallery_id",
+    "gallery_title",
+    "is_public_domain",
+    "image_id",
+];
+
+/// Fields retrieved for each agent.
+const AGENT_FIELDS: [&str; 4] = ["id", "title", "birth_date", "death_date"];
+
+/// Fields retrieved for each gallery.
+const GALLERY_FIELDS: [&str; 4] = ["id", "title", "floor", "is_closed"];
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS artworks (
+    id INTEGER PRIMARY KEY,
+    title TEXT NOT NULL,
+    artist_display TEXT,
+    artist_id INTEGER,
+    date_start INTEGER,
+    date_end INTEGER,
+    department_title TEXT,
+    gallery_id INTEGER,
+    is_public_domain INTEGER,
+    image_id TEXT,
+    timestamp TEXT,
+    raw TEXT NOT NULL CHECK (json_valid(raw))
+);
+CREATE INDEX IF NOT EXISTS artworks_artist_id ON artworks (artist_id);
+CREATE INDEX IF NOT EXISTS artworks_gallery_id ON artworks (gallery_id);
+CREATE INDEX IF NOT EXISTS artworks_date ON artworks (date_start, date_end);
+CREATE INDEX IF NOT EXISTS artworks_timestamp ON artworks (timestamp);
+
+CREATE TABLE IF NOT EXISTS artwork_artists (
+    artwork_id INTEGER NOT NULL REFERENCES artworks (id) ON DELETE CASCADE,
+    agent_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (artwork_id, agent_id)
+);
+CREATE INDEX IF NOT EXISTS artwork_artists_agent_id ON artwork_artists (agent_id);
+
+CREATE TABLE IF NOT EXISTS agents (
+    id INTEGER PRIMARY KEY,
+    title TEXT NOT NULL,
+    birth_date INTEGER,
+    death_date INTEGER,
+    raw TEXT NOT NULL CHECK (json_valid(raw))
+);
+
+CREATE TABLE IF NOT EXISTS galleries (
+    id INTEGER PRIMARY KEY,
+    title TEXT NOT NULL,
+    floor TEXT,
+    is_closed INTEGER,
+    raw TEXT NOT NULL CHECK (json_valid(raw))
+);
+
+CREATE TABLE IF NOT EXISTS sync_state (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    cursor TEXT NOT NULL
+);
+";
+
+/// A SQLite database mirroring the collection.
+#[derive(Debug)]
+pub struct Mirror {
+    connection: Connection,
+}
+
+impl Mirror {
+    /// Opens (or creates) a mirror at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AcresError> {
+        let path = path.as_ref();
+        let connection = Connection::open(path)
+            .with_context(|| format!("opening mirror at {}", path.display()))?;
+        Mirror::from_connection(connection)
+    }
+
+    /// Opens a mirror that only lives in memory.
+    pub fn open_in_memory() -> Result<Self, AcresError> {
+        let connection = Connection::open_in_memory().context("opening in-memory mirror")?;
+        Mirror::from_connection(connection)
+    }
+
+    fn from_connection(connection: Connection) -> Result<Self, AcresError> {
+        connection
+            .execute_batch("PRAGMA foreign_keys = ON;")
+            .context("enabling foreign keys")?;
+        connection
+            .execute_batch(SCHEMA)
+            .context("creating mirror schema")?;
+        Ok(Self { connection })
+    }
+
+    /// Returns the underlying connection, for running your own SQL.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Brings the mirror up to date.
+    ///
+    /// Changed artworks are synced as described in the [`sync`] module, then any agents and
+    /// galleries they refer to that aren't in the mirror yet are fetched.
+    ///
+    /// [`sync`]: ../sync/index.html
+    pub async fn refresh(&mut self, api: &Api) -> Result<Report, AcresError> {
+        let fields = ARTWORK_FIELDS.iter().map(|f| f.to_string()).collect();
+        let report = Syncer::new(api, &mut *self).fields(fields).run().await?;
+        self.refresh_related(api).await?;
+        Ok(report)
+    }
+
+    /// Fetches agents and galleries that artworks refer to but the mirror doesn't have.
+    pub async fn refresh_related(&mut self, api: &Api) -> Result<(), AcresError> {
+        let agents = self.missing_ids(
+            "SELECT DISTINCT agent_id FROM artwork_artists
+             WHERE agent_id NOT IN (SELECT id FROM agents)",
+        )?;
+        for chunk in agents.chunks(100) {
+            let request = agents_request::Builder::new()
+                .base_uri(api.base_uri())
+                .ids(Some(request_ids(chunk)))
+                .limit(Some(chunk.len() as u32))
+                .fields(Some(AGENT_FIELDS.map(String::from).to_vec()))
+                .build()?;
+            self.upsert_agents(&records(&api.send(&request).await?.data)?)?;
+        }
+        let galleries = self.missing_ids(
+            "SELECT DISTINCT gallery_id FROM artworks
+             WHERE gallery_id IS NOT NULL AND gallery_id NOT IN (SELECT id FROM galleries)",
+        )?;
+        for chunk in galleries.chunks(100) {
+            let request = galleries_request::Builder::new()
+                .base_uri(api.base_uri())
+                .ids(Some(request_ids(chunk)))
+                .limit(Some(chunk.len() as u32))
+                .fields(Some(GALLERY_FIELDS.map(String::from).to_vec()))
+                .build()?;
+            self.upsert_galleries(&records(&api.send(&request).await?.data)?)?;
+        }
+        Ok(())
+    }
+
+    /// Starts a query over artworks.
+    pub fn artworks(&self) -> ArtworkQuery<'_> {
+        ArtworkQuery {
+            connection: &self.connection,
+            clauses: vec![],
+            params: vec![],
+            limit: None,
+        }
+    }
+
+    /// Loads an artwork.
+    pub fn artwork(&self, id: u64) -> Result<Option<ArtworkRow>, AcresError> {
+        let mut artworks = self.artworks().id(id).all()?;
+        Ok(artworks.pop())
+    }
+
+    /// Loads an agent.
+    pub fn agent(&self, id: u64) -> Result<Option<AgentRow>, AcresError> {
+        Ok(self
+            .connection
+            .query_row(
+                "SELECT id, title, birth_date, death_date, raw FROM agents WHERE id = ?1",
+                [id],
+                AgentRow::from_row,
+            )
+            .optional()
+            .with_context(|| format!("loading agent {id}"))?)
+    }
+
+    /// Loads a gallery.
+    pub fn gallery(&self, id: u64) -> Result<Option<GalleryRow>, AcresError> {
+        Ok(self
+            .connection
+            .query_row(
+                "SELECT id, title, floor, is_closed, raw FROM galleries WHERE id = ?1",
+                [id],
+                GalleryRow::from_row,
+            )
+            .optional()
+            .with_context(|| format!("loading gallery {id}"))?)
+    }
+
+    /// Inserts or replaces agents from raw API records.
+    pub fn upsert_agents(&mut self, records: &[Value]) -> Result<(), AcresError> {
+        let tx = self
+            .connection
+            .transaction()
+            .context("starting transaction")?;
+        for record in records {
+            tx.execute(
+                "INSERT OR REPLACE INTO agents (id, title, birth_date, death_date, raw)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    record_id(record)?,
+                    record["title"].as_str().unwrap_or_default(),
+                    record["birth_date"].as_i64(),
+                    record["death_date"].as_i64(),
+                    record.to_string(),
+                ],
+            )
+            .context("upserting agent")?;
+        }
+        tx.commit().context("committing agents")?;
+        Ok(())
+    }
+
+    /// Inserts or replaces galleries from raw API records.
+    pub fn upsert_galleries(&mut self, records: &[Value]) -> Result<(), AcresError> {
+        let tx = self
+            .connection
+            .transaction()
+            .context("starting transaction")?;
+        for record in records {
+            tx.execute(
+                "INSERT OR REPLACE INTO galleries (id, title, floor, is_closed, raw)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    record_id(record)?,
+                    record["title"].as_str().unwrap_or_default(),
+                    match &record["floor"] {
+                        Value::String(floor) => Some(floor.clone()),
+                        Value::Number(floor) => Some(floor.to_string()),
+                        _ => None,
+                    },
+                    record["is_closed"].as_bool(),
+                    record.to_string(),
+                ],
+            )
+            .context("upserting gallery")?;
+        }
+        tx.commit().context("committing galleries")?;
+        Ok(())
+    }
+
+    fn missing_ids(&self, sql: &str) -> Result<Vec<u64>, AcresError> {
+        let mut statement = self.connection.prepare(sql).context("preparing query")?;
+        let ids = statement
+            .query_map([], |row| row.get(0))
+            .context("finding missing ids")?
+            .collect::<Result<Vec<u64>, _>>()
+            .context("reading missing ids")?;
+        Ok(ids)
+    }
+}
+
+impl Store for Mirror {
+    fn cursor(&self) -> Result<Option<Cursor>, AcresError> {
+        let cursor: Option<String> = self
+            .connection
+            .query_row("SELECT cursor FROM sync_state WHERE id = 0", [], |row| {
+                row.get(0)
+            })
+            .optional()
+            .context("loading cursor")?;
+        match cursor {
+            Some(cursor) => Ok(Some(
+                serde_json::from_str(&cursor).context("parsing cursor")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn save_cursor(&mut self, cursor: &Cursor) -> Result<(), AcresError> {
+        let cursor = serde_json::to_string(cursor).context("serializing cursor")?;
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO sync_state (id, cursor) VALUES (0, ?1)",
+                [cursor],
+            )
+            .context("saving cursor")?;
+        Ok(())
+    }
+
+    fn upsert(&mut self, records: &[Value]) -> Result<(), AcresError> {
+        let tx = self
+            .connection
+            .transaction()
+            .context("starting transaction")?;
+        for record in records {
+            let id = record_id(record)?;
+            tx.execute(
+                "INSERT OR REPLACE INTO artworks (
+                    id, title, artist_display, artist_id, date_start, date_end, department_title,
+                    gallery_id, is_public_domain, image_id, timestamp, raw
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    id,
+                    record["title"].as_str().unwrap_or_default(),
+                    record["artist_display"].as_str(),
+                    record["artist_id"].as_u64(),
+                    record["date_start"].as_i64(),
+                    record["date_end"].as_i64(),
+                    record["department_title"].as_str(),
+                    record["gallery_id"].as_u64(),
+                    record["is_public_domain"].as_bool(),
+                    record["image_id"].as_str(),
+                    record["timestamp"].as_str(),
+                    record.to_string(),
+                ],
+            )
+            .with_context(|| format!("upserting artwork {id}"))?;
+            tx.execute("DELETE FROM artwork_artists WHERE artwork_id = ?1", [id])
+                .with_context(|| format!("clearing artists of artwork {id}"))?;
+            let artist_ids = record["artist_ids"].as_array().cloned().unwrap_or_default();
+            for (position, agent_id) in artist_ids.iter().filter_map(Value::as_u64).enumerate() {
+                tx.execute(
+                    "INSERT OR IGNORE INTO artwork_artists (artwork_id, agent_id, position)
+                     VALUES (?1, ?2, ?3)",
+                    params![id, agent_id, position],
+                )
+                .with_context(|| format!("linking artwork {id} to agent {agent_id}"))?;
+            }
+        }
+        tx.commit().context("committing artworks")?;
+        Ok(())
+    }
+
+    fn delete(&mut self, ids: &[u64]) -> Result<(), AcresError> {
+        let tx = self
+            .connection
+            .transaction()
+            .context("starting transaction")?;
+        for id in ids {
+            tx.execute("DELETE FROM artworks WHERE id = ?1", [id])
+                .with_context(|| format!("deleting artwork {id}"))?;
+        }
+        tx.commit().context("committing deletions")?;
+        Ok(())
+    }
+
+    fn ids(&self) -> Result<BTreeSet<u64>, AcresError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT id FROM artworks")
+            .context("preparing query")?;
+        let ids = statement
+            .query_map([], |row| row.get(0))
+            .context("listing artworks")?
+            .collect::<Result<BTreeSet<u64>, _>>()
+            .context("reading artwork ids")?;
+        Ok(ids)
+    }
+}
+
+/// An artwork in the mirror.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArtworkRow {
+    /// Artwork id.
+    pub id: u64,
+    /// Title.
+    pub title: String,
+    /// Artist, as it's displayed on the label.
+    pub artist_display: Option<String>,
+    /// Preferred artist id.
+    pub artist_id: Option<u64>,
+    /// Earliest year the artwork may have been made.
+    pub date_start: Option<i64>,
+    /// Latest year the artwork may have been made.
+    pub date_end: Option<i64>,
+    /// Department.
+    pub department_title: Option<String>,
+    /// Gallery id.
+    pub gallery_id: Option<u64>,
+    /// Whether the artwork is in the public domain.
+    pub is_public_domain: Option<bool>,
+    /// IIIF image id.
+    pub image_id: Option<String>,
+    /// The raw API record.
+    pub raw: Value,
+}
+
+impl ArtworkRow {
+    const COLUMNS: &str = "artworks.id, artworks.title, artworks.artist_display, artworks.artist_id, \
+        artworks.date_start, artworks.date_end, artworks.department_title, artworks.gallery_id, \
+        artworks.is_public_domain, artworks.image_id, artworks.raw";
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            artist_display: row.get(2)?,
+            artist_id: row.get(3)?,
+            date_start: row.get(4)?,
+            date_end: row.get(5)?,
+            department_title: row.get(6)?,
+            gallery_id: row.get(7)?,
+            is_public_domain: row.get(8)?,
+            image_id: row.get(9)?,
+            raw: row.get(10)?,
+        })
+    }
+}
+
+/// An agent (artist, maker, etc.) in the mirror.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AgentRow {
+    /// Agent id.
+    pub id: u64,
+    /// Name.
+    pub title: String,
+    /// Year of birth.
+    pub birth_date: Option<i64>,
+    /// Year of death.
+    pub death_date: Option<i64>,
+    /// The raw API record.
+    pub raw: Value,
+}
+
+impl AgentRow {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            birth_date: row.get(2)?,
+            death_date: row.get(3)?,
+            raw: row.get(4)?,
+        })
+    }
+}
+
+/// A gallery in the mirror.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GalleryRow {
+    /// Gallery id.
+    pub id: u64,
+    /// Name.
+    pub title: String,
+    /// Floor.
+    pub floor: Option<String>,
+    /// Whether the gallery is closed.
+    pub is_closed: Option<bool>,
+    /// The raw API record.
+    pub raw: Value,
+}
+
+impl GalleryRow {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            floor: row.get(2)?,
+            is_closed: row.get(3)?,
+            raw: row.get(4)?,
+        })
+    }
+}
+
+/// A query over the artworks in a [`Mirror`].
+///
+/// Filters are combined with `AND`.
+pub struct ArtworkQuery<'a> {
+    connection: &'a Connection,
+    clauses: Vec<&'static str>,
+    params: Vec<Box<dyn ToSql>>,
+    limit: Option<u32>,
+}
+
+impl std::fmt::Debug for ArtworkQuery<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArtworkQuery")
+            .field("clauses", &self.clauses)
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ArtworkQuery<'_> {
+    fn filter(mut self, clause: &'static str, param: impl ToSql + 'static) -> Self {
+        self.clauses.push(clause);
+        self.params.push(Box::new(param));
+        self
+    }
+
+    /// Keeps the artwork with this id.
+    pub fn id(self, id: u64) -> Self {
+        self.filter("artworks.id = ?", id)
+    }
+
+    /// Keeps artworks by this agent, whether or not they're the preferred artist.
+    pub fn artist(self, agent_id: u64) -> Self {
+        self.filter(
+            "artworks.id IN (SELECT artwork_id FROM artwork_artists WHERE agent_id = ?)",
+            agent_id,
+        )
+    }
+
+    /// Keeps artworks in this gallery.
+    pub fn gallery(self, gallery_id: u64) -> Self {
+        self.filter("artworks.gallery_id = ?", gallery_id)
+    }
+
+    /// Keeps artworks from this department.
+    pub fn department(self, title: &str) -> Self {
+        self.filter("artworks.department_title = ?", title.to_string())
+    }
+
+    /// Keeps artworks that are (or aren't) in the public domain.
+    pub fn public_domain(self, is_public_domain: bool) -> Self {
+        self.filter("artworks.is_public_domain = ?", is_public_domain)
+    }
+
+    /// Keeps artworks that have an image.
+    pub fn has_image(mut self) -> Self {
+        self.clauses.push("artworks.image_id IS NOT NULL");
+        self
+    }
+
+    /// Keeps artworks whose date range overlaps `start..=end`.
+    pub fn dated_between(self, start: i64, end: i64) -> Self {
+        self.filter(
+            "COALESCE(artworks.date_end, artworks.date_start) >= ?",
+            start,
+        )
+        .filter("COALESCE(artworks.date_start, artworks.date_end) <= ?", end)
+    }
+
+    /// Keeps artworks whose title contains `text`, ignoring ASCII case.
+    pub fn title_contains(self, text: &str) -> Self {
+        let pattern = format!("%{}%", text.replace('%', "\\%").replace('_', "\\_"));
+        self.filter("artworks.title LIKE ? ESCAPE '\\'", pattern)
+    }
+
+    /// Limits the number of artworks returned.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Runs the query, returning artworks ordered by id.
+    pub fn all(self) -> Result<Vec<ArtworkRow>, AcresError> {
+        let mut sql = format!("SELECT {} FROM artworks", ArtworkRow::COLUMNS);
+        if !self.clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.clauses.join(" AND "));
+        }
+        sql.push_str(" ORDER BY artworks.id");
+        if let Some(limit) = self.limit {
+            sql.push_str(&format!(" LIMIT {limit}"));
+        }
+        let mut statement = self.connection.prepare(&sql).context("preparing query")?;
+        let params: Vec<&dyn ToSql> = self.params.iter().map(|p| p.as_ref()).collect();
+        let rows = statement
+            .query_map(params.as_slice(), ArtworkRow::from_row)
+            .context("querying artworks")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("reading artworks")?;
+        Ok(rows)
+    }
+
+    /// Counts the matching artworks.
+    pub fn count(self) -> Result<u64, AcresError> {
+        let mut sql = "SELECT COUNT(*) FROM artworks".to_string();
+        if !self.clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.clauses.join(" AND "));
+        }
+        let params: Vec<&dyn ToSql> = self.params.iter().map(|p| p.as_ref()).collect();
+        Ok(self
+            .connection
+            .query_row(&sql, params.as_slice(), |row| row.get(0))
+            .context("counting artworks")?)
+    }
+}
+
+/// Returns the ids that fit in a request.
+fn request_ids(ids: &[u64]) -> Vec<u32> {
+    ids.iter()
+        .filter_map(|id| u32::try_from(*id).ok())
+        .collect()
+}
+
+/// Returns typed records as the raw records the tables keep.
+fn records<T: Serialize>(data: &[T]) -> Result<Vec<Value>, AcresError> {
+    Ok(data
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<_, _>>()
+        .context("serializing records")?)
+}
+
+fn record_id(record: &Value) -> Result<u64, AcresError> {
+    record["id"]
+        .as_u64()
+        .ok_or_else(|| AcresError::Unexpected(anyhow!("record without an id: {record}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::fixtures::page;
+
+    fn artworks() -> Vec<Value> {
+        vec![
+            json!({"id": 1, "title": "Water Lilies", "artist_id": 10, "artist_ids": [10],
+                   "date_start": 1906, "date_end": 1906, "gallery_id": 243, "is_public_domain": true,
+                   "image_id": "abc", "timestamp": "2024-05-01T10:00:00-05:00"}),
+            json!({"id": 2, "title": "Stacks of Wheat", "artist_id": 10, "artist_ids": [10, 11],
+                   "date_start": 1890, "date_end": 1891, "is_public_domain": true,
+                   "timestamp": "2024-05-01T10:00:00-05:00"}),
+            json!({"id": 3, "title": "Nighthawks", "artist_id": 12, "artist_ids": [12],
+                   "date_start": 1942, "date_end": 1942, "gallery_id": 262, "is_public_domain": false,
+                   "timestamp": "2024-05-01T10:00:00-05:00"}),
+        ]
+    }
+
+    #[test]
+    fn queries_combine_filters() {
+        let mut mirror = Mirror::open_in_memory().unwrap();
+        mirror.upsert(&artworks()).unwrap();
+
+        let ids = |rows: Vec<ArtworkRow>| rows.iter().map(|row| row.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(mirror.artworks().artist(11).all().unwrap()), vec![2]);
+        assert_eq!(
+            ids(mirror
+                .artworks()
+                .public_domain(true)
+                .has_image()
+                .all()
+                .unwrap()),
+            vec![1]
+        );
+        assert_eq!(
+            ids(mirror.artworks().dated_between(1891, 1910).all().unwrap()),
+            vec![1, 2]
+        );
+        assert_eq!(
+            ids(mirror.artworks().title_contains("hawk").all().unwrap()),
+            vec![3]
+        );
+        assert_eq!(mirror.artworks().artist(10).count().unwrap(), 2);
+    }
+
+    #[test]
+    fn upsert_replaces_and_delete_cascades() {
+        let mut mirror = Mirror::open_in_memory().unwrap();
+        mirror.upsert(&artworks()).unwrap();
+
+        mirror
+            .upsert(&[json!({"id": 2, "title": "Stack of Wheat", "artist_ids": [10]})])
+            .unwrap();
+        mirror.delete(&[1]).unwrap();
+
+        assert_eq!(mirror.ids().unwrap(), BTreeSet::from([2, 3]));
+        assert_eq!(mirror.artwork(2).unwrap().unwrap().title, "Stack of Wheat");
+        assert_eq!(mirror.artworks().artist(11).count().unwrap(), 0);
+        let links: u64 = mirror
+            .connection()
+            .query_row("SELECT COUNT(*) FROM artwork_artists", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(links, 2);
+    }
+
+    #[test]
+    fn cursor_round_trips() {
+        let mut mirror = Mirror::open_in_memory().unwrap();
+        let cursor = Cursor {
+            updated_since: Some("2024-05-02T15:00:00Z".parse().unwrap()),
+            after_id: Some(3),
+            deletions_checked: None,
+        };
+
+        mirror.save_cursor(&cursor).unwrap();
+
+        assert_eq!(mirror.cursor().unwrap(), Some(cursor));
+    }
+
+    #[tokio::test]
+    async fn refresh_fetches_related_agents_and_galleries() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/agents"))
+            .and(wiremock::matchers::query_param("ids", "10,11,12"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(json!({"data": [
+                    {"id": 10, "title": "Claude Monet", "birth_date": 1840, "death_date": 1926},
+                    {"id": 12, "title": "Edward Hopper", "birth_date": 1882, "death_date": 1967},
+                ]})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/galleries"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(page(
+                json!([
+                    {"id": 243, "title": "Gallery 243", "floor": "2", "is_closed": false},
+                    {"id": 262, "title": "Gallery 262", "floor": 2, "is_closed": true},
+                ]),
+                2,
+                1,
+                1,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .use_cache(false)
+            .build();
+        let mut mirror = Mirror::open_in_memory().unwrap();
+        mirror.upsert(&artworks()).unwrap();
+
+        mirror.refresh_related(&api).await.unwrap();
+
+        assert_eq!(mirror.agent(10).unwrap().unwrap().title, "Claude Monet");
+        assert_eq!(mirror.agent(11).unwrap(), None);
+        assert_eq!(
+            mirror.gallery(262).unwrap().unwrap().floor.as_deref(),
+            Some("2")
+        );
+        let by_gallery: Vec<(String, String)> = mirror
+            .connection()
+            .prepare(
+                "SELECT galleries.title, artworks.title FROM artworks
+                 JOIN galleries ON galleries.id = artworks.gallery_id ORDER BY galleries.id",
+            )
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            by_gallery,
+            vec![
+                ("Gallery 243".to_string(), "Water Lilies".to_string()),
+                ("Gallery 262".to_string(), "Nighthawks".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,587 @@
+//! A compact query syntax for searching artworks.
+//!
+//! Rather than writing Elasticsearch JSON by hand, you can write
+//!
+//! ```text
+//! artist:monet date:1870..1890 color:blue is:public-domain dept:"Painting and Sculpture of Europe" -type:print
+//! ```
+//!
+//! and [`parse`] it into a [`Query`] that compiles to the search JSON. Words without a key are
+//! searched for in the title, artist, description and subject terms, and any term can be negated
+//! with a leading `-`.
+//!
+//! | Key                      | Matches                                                     |
+//! |--------------------------|-------------------------------------------------------------|
+//! | `artist:`                | artist name                                                 |
+//! | `title:`                 | title                                                       |
+//! | `date:`                  | dates overlapping a [span], e.g. `1870..1890`, `..1500`, `"19th century"` |
+//! | `color:`                 | dominant [color] close to a CSS color, hex, `rgb()` or `hsl()` |
+//! | `is:`                    | `public-domain`, `on-view` or `boosted`                     |
+//! | `dept:`, `department:`   | department                                                  |
+//! | `type:`                  | artwork type, e.g. `painting` or `print`                    |
+//! | `classification:`        | classification                                              |
+//! | `style:`                 | style                                                       |
+//! | `medium:`                | medium                                                      |
+//! | `place:`                 | place of origin                                             |
+//! | `provenance:`            | ownership history, e.g. `provenance:"Durand-Ruel"`          |
+//! | `exhibition:`            | exhibition history                                          |
+//! | `publication:`           | publication history                                         |
+//! | `id:`                    | artwork id                                                  |
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use acres::query;
+//! use serde_json::json;
+//!
+//! # fn main() -> Result<()> {
+//! let query = query::parse(r#"artist:monet -type:print is:public-domain"#)?;
+//! assert_eq!(
+//!     query.to_json(),
+//!     json!({
+//!         "bool": {
+//!             "must": [
+//!                 { "match": { "artist_title": "monet" } },
+//!                 { "term": { "is_public_domain": true } }
+//!             ],
+//!             "must_not": [
+//!                 { "match": { "artwork_type_title": "print" } }
+//!             ]
+//!         }
+//!     })
+//! );
+//!
+//! let error = query::parse("artist:monet when:1890").unwrap_err();
+//! assert_eq!(error.position(), 13);
+//! assert_eq!(error.to_string(), "unknown key 'when' at column 14");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [span]: ../dating/struct.Span.html
+//! [color]: ../color/index.html
+
+use std::{fmt::Display, iter::Peekable, str::Chars};
+
+use serde_json::{Value, json};
+
+use crate::{
+    artworks::request::search,
+    color::{self, Hsl},
+    dating::Span,
+};
+
+/// Fields searched for words without a key.
+const TEXT_FIELDS: [&str; 4] = ["title", "artist_title", "description", "term_titles"];
+
+/// An error in a query, and where it is.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{message} at column {}", .position + 1)]
+pub struct ParseError {
+    position: usize,
+    message: String,
+}
+
+impl ParseError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            position,
+            message: message.into(),
+        }
+    }
+
+    /// Returns where the error is, as a count of characters from the start of the query.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns what's wrong, without the position.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the query with a caret under the error, for showing to people.
+    ///
+    /// ```rust
+    /// use acres::query;
+    ///
+    /// let input = "artist:monet date:soon";
+    /// let error = query::parse(input).unwrap_err();
+    /// assert_eq!(error.pointer(input), "artist:monet date:soon\n                  ^");
+    /// ```
+    pub fn pointer(&self, input: &str) -> String {
+        format!("{input}\n{}^", " ".repeat(self.position))
+    }
+}
+
+/// One condition in a query.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Term {
+    /// Words or a quoted phrase to search for.
+    Text(String),
+    /// A field that should match some text.
+    Match {
+        /// The field.
+        field: &'static str,
+        /// The text.
+        text: String,
+        /// Whether the text should match as a phrase.
+        phrase: bool,
+    },
+    /// A flag that should be set, like `is_public_domain`.
+    Flag(&'static str),
+    /// An artwork id.
+    Id(u64),
+    /// Dates overlapping a span.
+    Date(Span),
+    /// A dominant color close to another.
+    Color(color::Query),
+}
+
+impl Term {
+    fn to_json(&self) -> Value {
+        match self {
+            Term::Text(text) => json!({ "multi_match": { "query": text, "fields": TEXT_FIELDS } }),
+            Term::Match {
+                field,
+                text,
+                phrase: false,
+            } => json!({ "match": { *field: text } }),
+            Term::Match {
+                field,
+                text,
+                phrase: true,
+            } => json!({ "match_phrase": { *field: text } }),
+            Term::Flag(field) => json!({ "term": { *field: true } }),
+            Term::Id(id) => json!({ "term": { "id": id } }),
+            Term::Date(span) => span.query(),
+            Term::Color(color) => color.query(),
+        }
+    }
+}
+
+/// A parsed query.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    include: Vec<Term>,
+    exclude: Vec<Term>,
+}
+
+impl Query {
+    /// Returns the terms artworks must match.
+    pub fn include(&self) -> &[Term] {
+        &self.include
+    }
+
+    /// Returns the terms artworks must not match.
+    pub fn exclude(&self) -> &[Term] {
+        &self.exclude
+    }
+
+    /// Returns true if the query has no terms.
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Returns the first color searched for, which results can be [ranked] by.
+    ///
+    /// [ranked]: ../color/struct.Query.html#method.rank
+    pub fn color(&self) -> Option<color::Query> {
+        self.include.iter().find_map(|term| match term {
+            Term::Color(color) => Some(*color),
+            _ => None,
+        })
+    }
+
+    /// Sets how far every color searched for may stray.
+    pub fn tolerance(mut self, tolerance: color::Tolerance) -> Self {
+        for term in self.include.iter_mut().chain(self.exclude.iter_mut()) {
+            if let Term::Color(color) = term {
+                *color = color.tolerance(tolerance);
+            }
+        }
+        self
+    }
+
+    /// Compiles the query into search JSON.
+    pub fn to_json(&self) -> Value {
+        let mut query = json!({ "bool": {} });
+        if !self.include.is_empty() {
+            query["bool"]["must"] = self.include.iter().map(Term::to_json).collect();
+        }
+        if !self.exclude.is_empty() {
+            query["bool"]["must_not"] = self.exclude.iter().map(Term::to_json).collect();
+        }
+        query
+    }
+
+    /// Sets up a search builder to run the query.
+    ///
+    /// The first color searched for is handed to [`color`] so that the `color` field is
+    /// retrieved for ranking, and the rest becomes the builder's query.
+    ///
+    /// [`color`]: ../artworks/request/search/struct.Builder.html#method.color
+    pub fn apply(&self, builder: search::Builder) -> search::Builder {
+        let color = self.color();
+        let mut rest = self.clone();
+        if color.is_some()
+            && let Some(index) = rest
+                .include
+                .iter()
+                .position(|term| matches!(term, Term::Color(_)))
+        {
+            rest.include.remove(index);
+        }
+        let query = (!rest.is_empty()).then(|| rest.to_json().to_string());
+        builder.query(query).color(color)
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_json().to_string())
+    }
+}
+
+/// Parses a query.
+pub fn parse(input: &str) -> Result<Query, ParseError> {
+    let mut query = Query::default();
+    let mut lexer = Lexer::new(input);
+    while let Some(token) = lexer.next_token()? {
+        let term = token.term()?;
+        if token.negated {
+            query.exclude.push(term);
+        } else {
+            query.include.push(term);
+        }
+    }
+    Ok(query)
+}
+
+#[derive(Debug)]
+struct Token {
+    negated: bool,
+    key: Option<(String, usize)>,
+    value: String,
+    quoted: bool,
+    position: usize,
+}
+
+impl Token {
+    fn term(&self) -> Result<Term, ParseError> {
+        let Some((key, key_position)) = &self.key else {
+            return Ok(Term::Text(self.value.clone()));
+        };
+        let text = |field| Term::Match {
+            field,
+            text: self.value.clone(),
+            phrase: self.quoted,
+        };
+        let phrase = |field| Term::Match {
+            field,
+            text: self.value.clone(),
+            phrase: true,
+        };
+        let invalid = |message: String| ParseError::new(self.position, message);
+        Ok(match key.to_lowercase().as_str() {
+            "artist" => text("artist_title"),
+            "title" => text("title"),
+            "dept" | "department" => phrase("department_title"),
+            "type" => text("artwork_type_title"),
+            "classification" => text("classification_title"),
+            "style" => text("style_title"),
+            "medium" => text("medium_display"),
+            "place" => text("place_of_origin"),
+            "provenance" => text("provenance_text"),
+            "exhibition" => text("exhibition_history"),
+            "publication" => text("publication_history"),
+            "id" => {
+                Term::Id(self.value.parse().map_err(|_| {
+                    invalid(format!("expected an artwork id, not '{}'", self.value))
+                })?)
+            }
+            "is" => match self.value.to_lowercase().as_str() {
+                "public-domain" => Term::Flag("is_public_domain"),
+                "on-view" => Term::Flag("is_on_view"),
+                "boosted" => Term::Flag("is_boosted"),
+                other => {
+                    return Err(invalid(format!(
+                        "expected public-domain, on-view or boosted, not '{other}'"
+                    )));
+                }
+            },
+            "date" => Term::Date(date_span(&self.value).ok_or_else(|| {
+                invalid(format!(
+                    "expected a date range like 1870..1890, not '{}'",
+                    self.value
+                ))
+            })?),
+            "color" => Term::Color(color::Query::new(self.value.parse::<Hsl>().map_err(
+                |_| {
+                    invalid(format!(
+                        "expected a color like blue or #336699, not '{}'",
+                        self.value
+                    ))
+                },
+            )?)),
+            _ => {
+                return Err(ParseError::new(
+                    *key_position,
+                    format!("unknown key '{key}'"),
+                ));
+            }
+        })
+    }
+}
+
+fn date_span(value: &str) -> Option<Span> {
+    match value.split_once("..") {
+        Some((start, end)) => {
+            let start = match start.trim() {
+                "" => i64::MIN,
+                start => start.parse::<Span>().ok()?.start(),
+            };
+            let end = match end.trim() {
+                "" => i64::MAX,
+                end => end.parse::<Span>().ok()?.end(),
+            };
+            (start <= end).then(|| Span::new(start, end))
+        }
+        None => value.parse().ok(),
+    }
+}
+
+/// Splits a query into tokens, keeping track of the column in characters.
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Lexer {
+            chars: input.chars().peekable(),
+            column: 0,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.column += 1;
+        Some(c)
+    }
+
+    /// Reads up to the next whitespace, or character that should `stop` the word.
+    fn word(&mut self, stop: impl Fn(char) -> bool) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.peek().filter(|c| !c.is_whitespace() && !stop(*c)) {
+            word.push(c);
+            self.advance();
+        }
+        word
+    }
+
+    /// Reads a quoted phrase, after the opening quote.
+    fn quoted(&mut self, position: usize) -> Result<String, ParseError> {
+        let mut phrase = String::new();
+        loop {
+            match self.advance() {
+                Some('"') => return Ok(phrase),
+                Some('\\') => match self.advance() {
+                    Some(c) => phrase.push(c),
+                    None => break,
+                },
+                Some(c) => phrase.push(c),
+                None => break,
+            }
+        }
+        Err(ParseError::new(position, "unterminated quote"))
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, ParseError> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.advance();
+        }
+        let Some(first) = self.peek() else {
+            return Ok(None);
+        };
+        let negated = first == '-';
+        if negated {
+            let position = self.column;
+            self.advance();
+            if self.peek().is_none_or(char::is_whitespace) {
+                return Err(ParseError::new(position, "expected a term after '-'"));
+            }
+        }
+
+        let mut key = None;
+        if self.peek() != Some('"') {
+            let key_position = self.column;
+            let word = self.word(|c| c == ':');
+            if self.peek() != Some(':') {
+                return Ok(Some(Token {
+                    negated,
+                    key: None,
+                    value: word,
+                    quoted: false,
+                    position: key_position,
+                }));
+            }
+            self.advance();
+            key = Some((word, key_position));
+        }
+
+        let position = self.column;
+        let quoted = self.peek() == Some('"');
+        let value = if quoted {
+            self.advance();
+            self.quoted(position)?
+        } else {
+            self.word(|_| false)
+        };
+        if value.is_empty() {
+            let message = match &key {
+                Some((key, _)) => format!("expected a value for '{key}'"),
+                None => "expected a phrase".to_string(),
+            };
+            return Err(ParseError::new(position, message));
+        }
+        Ok(Some(Token {
+            negated,
+            key,
+            value,
+            quoted,
+            position,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compiles_the_full_syntax() {
+        let query = parse(
+            r#"artist:monet date:1870..1890 color:blue is:public-domain dept:"Painting and Sculpture of Europe" -type:print"#,
+        )
+        .unwrap();
+
+        let json = query.to_json();
+        let must = json["bool"]["must"].as_array().unwrap();
+        assert_eq!(must.len(), 5);
+        assert_eq!(must[1], Span::new(1870, 1890).query());
+        assert!(must[2]["bool"]["must"][0]["range"]["color.h"].is_object());
+        assert_eq!(
+            must[4],
+            json!({ "match_phrase": { "department_title": "Painting and Sculpture of Europe" } })
+        );
+        assert_eq!(
+            json["bool"]["must_not"],
+            json!([{ "match": { "artwork_type_title": "print" } }])
+        );
+    }
+
+    #[test]
+    fn free_text_and_phrases() {
+        let query = parse(r#"water "lilies at dusk" -haystack"#).unwrap();
+
+        assert_eq!(
+            query.include(),
+            &[
+                Term::Text("water".into()),
+                Term::Text("lilies at dusk".into())
+            ]
+        );
+        assert_eq!(query.exclude(), &[Term::Text("haystack".into())]);
+    }
+
+    #[test]
+    fn history_keys() {
+        assert_eq!(
+            parse(r#"provenance:"Durand-Ruel" -exhibition:venice"#)
+                .unwrap()
+                .to_json(),
+            json!({
+                "bool": {
+                    "must": [{ "match_phrase": { "provenance_text": "Durand-Ruel" } }],
+                    "must_not": [{ "match": { "exhibition_history": "venice" } }]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn open_date_ranges() {
+        assert_eq!(
+            parse("date:..1500").unwrap().include(),
+            &[Term::Date(Span::new(i64::MIN, 1500))]
+        );
+        assert_eq!(
+            parse(r#"date:"19th century""#).unwrap().include(),
+            &[Term::Date(Span::new(1800, 1899))]
+        );
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        let error = |input| parse(input).unwrap_err();
+
+        assert_eq!(error("título:x when:now").position(), 0);
+        assert_eq!(error("título:x when:now").message(), "unknown key 'título'");
+        assert_eq!(error("artist:monet is:famous").position(), 16);
+        assert_eq!(error("artist:").position(), 7);
+        assert_eq!(error(r#"dept:"Arts of"#).position(), 5);
+        assert_eq!(error("monet - lilies").position(), 6);
+        assert_eq!(
+            error("id:abc").to_string(),
+            "expected an artwork id, not 'abc' at column 4"
+        );
+    }
+
+    #[test]
+    fn free_text_survives_the_url() {
+        let request = parse(r#"title:"Mother & Child #2" monet+"#)
+            .unwrap()
+            .apply(search::Builder::new().base_uri("https://example.org/api/v1".into()))
+            .build()
+            .unwrap();
+
+        let url = url::Url::parse(&request.to_string()).unwrap();
+        let (_, query) = url.query_pairs().find(|(key, _)| key == "query").unwrap();
+        assert!(query.contains(r#"{"match_phrase":{"title":"Mother & Child #2"}}"#));
+        assert!(query.contains(r#""query":"monet+""#));
+    }
+
+    #[test]
+    fn tolerance_applies_to_colors() {
+        let query = parse("color:blue")
+            .unwrap()
+            .tolerance(color::Tolerance::uniform(10.0));
+
+        assert_eq!(
+            query.color(),
+            Some(
+                color::Query::new("blue".parse().unwrap())
+                    .tolerance(color::Tolerance::uniform(10.0))
+            )
+        );
+    }
+
+    #[test]
+    fn apply_hands_color_to_the_builder() {
+        let builder = parse("color:red artist:monet")
+            .unwrap()
+            .apply(search::Builder::new().base_uri("https://example.org/api/v1".into()));
+
+        let request = builder.build().unwrap();
+        assert!(request.to_string().contains("fields=id,"));
+        let url = url::Url::parse(&request.to_string()).unwrap();
+        let (_, query) = url.query_pairs().find(|(key, _)| key == "query").unwrap();
+        assert!(query.contains(r#"{"match":{"artist_title":"monet"}}"#));
+        assert_eq!(query.matches("color.h").count(), 2);
+    }
+}
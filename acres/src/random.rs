@@ -0,0 +1,292 @@
+//! Random artworks and the artwork of the day.
+//!
+//! [`Random`] picks an artwork with a [`random_score`] search, optionally limited to public domain
+//! works, works with an image, or a department. The scores come from a seed, so the same seed and
+//! filters always pick the same artwork. [`Random::daily`] seeds the pick with the date, which
+//! gives every machine the same artwork of the day.
+//!
+//! ```no_run
+//! # use anyhow::Result;
+//! use acres::random::Random;
+//! use chrono::Utc;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let api = acres::Api::new();
+//! let today = Random::artwork_of_the_day(Utc::now().date_naive());
+//! if let Some(artwork) = today.pick(&api).await? {
+//!     println!("{artwork}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`random_score`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-function-score-query.html#function-random
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use chrono::{Datelike, NaiveDate};
+use serde_json::{Value, json};
+
+use crate::{
+    AcresError, Api,
+    artworks::{Artwork, request::search},
+};
+
+/// Fields retrieved for the picked artwork unless others are chosen.
+const DEFAULT_FIELDS: [&str; 9] = [
+    "id",
+    "title",
+    "image_id",
+    "artist_display",
+    "artist_title",
+    "date_display",
+    "department_title",
+    "is_public_domain",
+    "thumbnail",
+];
+
+/// Picks a random artwork.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Random {
+    public_domain: bool,
+    has_image: bool,
+    department: Option<String>,
+    seed: Option<u64>,
+    fields: Vec<String>,
+}
+
+impl Random {
+    /// Creates a pick from the whole collection.
+    pub fn new() -> Self {
+        Random::default()
+    }
+
+    /// Creates the artwork of the day: a public domain work with an image, seeded by the date.
+    pub fn artwork_of_the_day(date: NaiveDate) -> Self {
+        Random::new()
+            .public_domain(true)
+            .has_image(true)
+            .daily(date)
+    }
+
+    /// Sets whether to pick only works in the public domain.
+    pub fn public_domain(mut self, public_domain: bool) -> Self {
+        self.public_domain = public_domain;
+        self
+    }
+
+    /// Sets whether to pick only works with an image.
+    pub fn has_image(mut self, has_image: bool) -> Self {
+        self.has_image = has_image;
+        self
+    }
+
+    /// Sets the department to pick from, e.g. "Photography and Media".
+    pub fn department(mut self, department: Option<String>) -> Self {
+        self.department = department;
+        self
+    }
+
+    /// Sets the seed, so that the same seed and filters always pick the same artwork.
+    ///
+    /// Without one, the seed comes from the clock.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Seeds the pick with a date, so that every machine picks the same artwork that day.
+    pub fn daily(self, date: NaiveDate) -> Self {
+        self.seed(seed_for(date))
+    }
+
+    /// Sets the artwork fields to retrieve.
+    pub fn fields(mut self, fields: Vec<String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Returns the search query.
+    ///
+    /// ```rust
+    /// use acres::random::Random;
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(
+    ///     Random::new().public_domain(true).seed(42).query(),
+    ///     json!({
+    ///         "function_score": {
+    ///             "query": { "bool": { "filter": [{ "term": { "is_public_domain": true } }] } },
+    ///             "random_score": { "seed": 42, "field": "id" },
+    ///             "boost_mode": "replace"
+    ///         }
+    ///     })
+    /// );
+    /// ```
+    pub fn query(&self) -> Value {
+        let mut filter = vec![];
+        if self.public_domain {
+            filter.push(json!({ "term": { "is_public_domain": true } }));
+        }
+        if self.has_image {
+            filter.push(json!({ "exists": { "field": "image_id" } }));
+        }
+        if let Some(department) = &self.department {
+            filter.push(json!({ "match_phrase": { "department_title": department } }));
+        }
+        let query = if filter.is_empty() {
+            json!({ "match_all": {} })
+        } else {
+            json!({ "bool": { "filter": filter } })
+        };
+        json!({
+            "function_score": {
+                "query": query,
+                "random_score": { "seed": self.seed.unwrap_or_else(clock_seed), "field": "id" },
+                "boost_mode": "replace"
+            }
+        })
+    }
+
+    /// Picks an artwork, or `None` if nothing matches the filters.
+    ///
+    /// Picks seeded by the clock are never repeated, so they skip the response cache.
+    pub async fn pick(&self, api: &Api) -> Result<Option<Artwork>, AcresError> {
+        let fields = if self.fields.is_empty() {
+            DEFAULT_FIELDS.iter().map(|f| f.to_string()).collect()
+        } else {
+            self.fields.clone()
+        };
+        let request = search::Builder::new()
+            .base_uri(api.base_uri())
+            .query(Some(self.query().to_string()))
+            .size(Some(1))
+            .fields(Some(fields))
+            .build()?;
+        let response = match self.seed {
+            Some(_) => api.send(&request).await?,
+            None => api.uncached().send(&request).await?,
+        }
+        .0;
+        let Some(data) = response["data"].get(0) else {
+            return Ok(None);
+        };
+        let artwork = serde_json::from_value(json!({
+            "config": response["config"],
+            "info": response["info"],
+            "data": data,
+        }))
+        .context("unexpected artwork in search results")?;
+        Ok(Some(artwork))
+    }
+}
+
+/// Returns the seed used for a date's pick.
+pub fn seed_for(date: NaiveDate) -> u64 {
+    u64::try_from(date.num_days_from_ce()).unwrap_or_default()
+}
+
+fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::envelope;
+
+    #[test]
+    fn daily_seed_depends_only_on_the_date() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let next = date.succ_opt().unwrap();
+
+        assert_eq!(
+            Random::artwork_of_the_day(date).query(),
+            Random::artwork_of_the_day(date).query()
+        );
+        assert_ne!(seed_for(date), seed_for(next));
+        assert_eq!(
+            Random::artwork_of_the_day(date).query()["function_score"]["query"]["bool"]["filter"],
+            json!([
+                { "term": { "is_public_domain": true } },
+                { "exists": { "field": "image_id" } }
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn picks_the_first_result() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .and(wiremock::matchers::query_param_contains(
+                "query",
+                r#"{"match_phrase":{"department_title":"Arts of Asia"}}"#,
+            ))
+            .and(wiremock::matchers::query_param("size", "1"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(envelope(json!([
+                    { "id": 24645, "title": "Under the Wave off Kanagawa" }
+                ]))),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+
+        let artwork = Random::new()
+            .department(Some("Arts of Asia".into()))
+            .seed(7)
+            .pick(&api)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(artwork.data.id, 24645);
+    }
+
+    #[tokio::test]
+    async fn garbage_responses_are_errors() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_string("<html>"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+
+        assert!(Random::new().seed(7).pick(&api).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn unseeded_picks_skip_the_cache() {
+        let cache_dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(envelope(json!([
+                    { "id": 24645, "title": "Under the Wave off Kanagawa" }
+                ]))),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .base_uri(&mock_uri)
+            .use_cache(true)
+            .cache_dir(cache_dir.path())
+            .build();
+
+        Random::new().pick(&api).await.unwrap().unwrap();
+        assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 0);
+
+        Random::new().seed(7).pick(&api).await.unwrap().unwrap();
+        assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 1);
+    }
+}
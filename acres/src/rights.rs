@@ -0,0 +1,340 @@
+//! Rights and licensing for artworks and their images.
+//!
+//! [`Rights`] tells you whether an artwork's image may be downloaded or shown at full size, and
+//! produces the attribution to show alongside it. Only works in the public domain are cleared for
+//! either; anything else, including works whose status wasn't returned, is treated as restricted.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use acres::{artworks::Artwork, rights::Rights};
+//!
+//! # fn main() -> Result<()> {
+//! let artwork: Artwork = serde_json::from_str(r#"{
+//!     "data": {
+//!         "id": 27992,
+//!         "title": "A Sunday on La Grande Jatte — 1884",
+//!         "artist_title": "Georges Seurat",
+//!         "is_public_domain": true
+//!     },
+//!     "info": { "license_text": "", "license_links": [], "version": "1.13" },
+//!     "config": { "iiif_url": "https://www.artic.edu/iiif/2", "website_url": "https://www.artic.edu" }
+//! }"#)?;
+//!
+//! let rights = Rights::of(&artwork);
+//! assert!(rights.may_download());
+//! assert_eq!(
+//!     rights.attribution(),
+//!     "A Sunday on La Grande Jatte — 1884, Georges Seurat. CC0 Public Domain Designation. \
+//!      Art Institute of Chicago."
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A [`Policy`] applies the same rules to IIIF image requests, shrinking the size asked for when
+//! the work isn't in the public domain. The tools enforce it when `rights.enforce` is set in the
+//! [`Config`].
+//!
+//! [`Config`]: ../struct.Config.html
+
+use crate::{RightsConfig, artworks::Artwork};
+
+const INSTITUTION: &str = "Art Institute of Chicago";
+const PUBLIC_DOMAIN: &str = "CC0 Public Domain Designation";
+const ALL_RIGHTS_RESERVED: &str = "All rights reserved.";
+
+/// Whether an artwork is in the public domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Status {
+    /// In the public domain, released under CC0.
+    PublicDomain,
+    /// Still under copyright, or otherwise restricted.
+    Restricted,
+    /// The response didn't include `is_public_domain`.
+    Unknown,
+}
+
+impl From<Option<bool>> for Status {
+    fn from(is_public_domain: Option<bool>) -> Self {
+        match is_public_domain {
+            Some(true) => Status::PublicDomain,
+            Some(false) => Status::Restricted,
+            None => Status::Unknown,
+        }
+    }
+}
+
+/// The rights attached to an artwork.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rights {
+    status: Status,
+    title: String,
+    artist: Option<String>,
+    copyright_notice: Option<String>,
+    license_text: String,
+    license_links: Vec<String>,
+}
+
+impl Rights {
+    /// Creates the rights for an artwork.
+    pub fn of(artwork: &Artwork) -> Self {
+        let data = &artwork.data;
+        Rights {
+            status: data.is_public_domain.into(),
+            title: data.title.clone(),
+            artist: data.artist_title.clone(),
+            copyright_notice: data
+                .copyright_notice
+                .as_ref()
+                .map(|notice| notice.trim().to_string())
+                .filter(|notice| !notice.is_empty()),
+            license_text: artwork.info.license_text().to_string(),
+            license_links: artwork.info.license_links().to_vec(),
+        }
+    }
+
+    /// Returns whether the artwork is in the public domain.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Returns true if the artwork is in the public domain.
+    pub fn is_public_domain(&self) -> bool {
+        self.status == Status::PublicDomain
+    }
+
+    /// Returns true if the artwork's image may be downloaded.
+    pub fn may_download(&self) -> bool {
+        self.is_public_domain()
+    }
+
+    /// Returns true if the artwork's image may be rendered at full size.
+    pub fn may_render_full_size(&self) -> bool {
+        self.is_public_domain()
+    }
+
+    /// Returns the attribution to show alongside the artwork's image.
+    ///
+    /// This names the work and artist, then either the public domain designation or the
+    /// copyright notice, then the museum.
+    pub fn attribution(&self) -> String {
+        let work = match &self.artist {
+            Some(artist) => format!("{}, {artist}.", self.title),
+            None => format!("{}.", self.title),
+        };
+        let rights = match (&self.status, &self.copyright_notice) {
+            (Status::PublicDomain, _) => format!("{PUBLIC_DOMAIN}."),
+            (_, Some(notice)) => notice.clone(),
+            (_, None) => ALL_RIGHTS_RESERVED.to_string(),
+        };
+        format!("{work} {rights} {INSTITUTION}.")
+    }
+
+    /// Returns the license covering the artwork's data, as opposed to its image.
+    pub fn data_license(&self) -> &str {
+        &self.license_text
+    }
+
+    /// Returns links to the licenses and terms covering the artwork's data.
+    pub fn data_license_links(&self) -> &[String] {
+        &self.license_links
+    }
+}
+
+/// A policy that limits image sizes for works that aren't in the public domain.
+///
+/// ```rust
+/// use acres::rights::Policy;
+///
+/// let policy = Policy::default();
+/// assert_eq!(policy.apply(Some(false), iiif::Size::Full), iiif::Size::BestFit(400, 400));
+/// assert_eq!(policy.apply(Some(true), iiif::Size::Full), iiif::Size::Full);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Policy {
+    restricted_width: u32,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy {
+            restricted_width: Policy::DEFAULT_RESTRICTED_WIDTH,
+        }
+    }
+}
+
+impl Policy {
+    /// Largest width or height, in pixels, requested for restricted works unless configured otherwise.
+    pub const DEFAULT_RESTRICTED_WIDTH: u32 = 400;
+
+    /// Creates a policy that limits restricted works to the given width and height.
+    pub fn new(restricted_width: u32) -> Self {
+        Policy { restricted_width }
+    }
+
+    /// Creates the policy described by the settings, if it's to be enforced.
+    pub fn from_config(config: &RightsConfig) -> Option<Self> {
+        config.enforce.then(|| {
+            Policy::new(
+                config
+                    .restricted_width
+                    .unwrap_or(Policy::DEFAULT_RESTRICTED_WIDTH),
+            )
+        })
+    }
+
+    /// Returns the largest width or height, in pixels, requested for restricted works.
+    pub fn restricted_width(&self) -> u32 {
+        self.restricted_width
+    }
+
+    /// Returns the size to request, given whether the work is in the public domain.
+    ///
+    /// Sizes for public domain works pass through. Otherwise, sizes that could exceed the
+    /// restricted width in either direction are scaled down to fit within a square of that
+    /// width, keeping smaller requests as they are.
+    pub fn apply(&self, is_public_domain: Option<bool>, size: iiif::Size) -> iiif::Size {
+        if Status::from(is_public_domain) == Status::PublicDomain {
+            return size;
+        }
+        let max = self.restricted_width;
+        match size {
+            iiif::Size::Width(width) => iiif::Size::BestFit(width.min(max), max),
+            iiif::Size::Height(height) => iiif::Size::BestFit(max, height.min(max)),
+            iiif::Size::Exactly(width, height) if width <= max && height <= max => size,
+            iiif::Size::BestFit(width, height) => {
+                iiif::Size::BestFit(width.min(max), height.min(max))
+            }
+            iiif::Size::Full | iiif::Size::Percentage(_) | iiif::Size::Exactly(_, _) => {
+                iiif::Size::BestFit(max, max)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn artwork(is_public_domain: Option<bool>, copyright_notice: Option<&str>) -> Artwork {
+        serde_json::from_value(json!({
+            "data": {
+                "id": 151363,
+                "title": "Reclining Figure",
+                "artist_title": "Henry Moore",
+                "is_public_domain": is_public_domain,
+                "copyright_notice": copyright_notice
+            },
+            "info": {
+                "license_text": "CC0 except description",
+                "license_links": ["https://creativecommons.org/publicdomain/zero/1.0/"],
+                "version": "1.13"
+            },
+            "config": { "iiif_url": "https://www.artic.edu/iiif/2", "website_url": "https://www.artic.edu" }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn restricted_works_use_the_copyright_notice() {
+        let rights = Rights::of(&artwork(
+            Some(false),
+            Some("© The Henry Moore Foundation. All Rights Reserved."),
+        ));
+
+        assert!(!rights.may_download());
+        assert!(!rights.may_render_full_size());
+        assert_eq!(
+            rights.attribution(),
+            "Reclining Figure, Henry Moore. © The Henry Moore Foundation. All Rights Reserved. \
+             Art Institute of Chicago."
+        );
+        assert_eq!(rights.data_license(), "CC0 except description");
+        assert_eq!(rights.data_license_links().len(), 1);
+    }
+
+    #[test]
+    fn unknown_status_is_restricted() {
+        let rights = Rights::of(&artwork(None, None));
+
+        assert_eq!(rights.status(), Status::Unknown);
+        assert!(!rights.may_download());
+        assert_eq!(
+            rights.attribution(),
+            "Reclining Figure, Henry Moore. All rights reserved. Art Institute of Chicago."
+        );
+    }
+
+    #[test]
+    fn policy_limits_restricted_sizes() {
+        let policy = Policy::new(200);
+        let pct = "pct:50".parse::<iiif::Size>().unwrap();
+
+        assert_eq!(
+            policy.apply(Some(false), iiif::Size::Full),
+            iiif::Size::BestFit(200, 200)
+        );
+        assert_eq!(
+            policy.apply(None, pct.clone()),
+            iiif::Size::BestFit(200, 200)
+        );
+        assert_eq!(
+            policy.apply(Some(false), iiif::Size::Width(843)),
+            iiif::Size::BestFit(200, 200)
+        );
+        assert_eq!(
+            policy.apply(Some(false), iiif::Size::Width(100)),
+            iiif::Size::BestFit(100, 200)
+        );
+        assert_eq!(
+            policy.apply(Some(false), iiif::Size::Height(600)),
+            iiif::Size::BestFit(200, 200)
+        );
+        assert_eq!(
+            policy.apply(Some(false), iiif::Size::Exactly(150, 100)),
+            iiif::Size::Exactly(150, 100)
+        );
+        assert_eq!(
+            policy.apply(Some(false), iiif::Size::Exactly(1000, 100)),
+            iiif::Size::BestFit(200, 200)
+        );
+        assert_eq!(
+            policy.apply(Some(false), iiif::Size::BestFit(1000, 100)),
+            iiif::Size::BestFit(200, 100)
+        );
+        assert_eq!(policy.apply(Some(true), pct.clone()), pct);
+    }
+
+    #[test]
+    fn policy_limits_the_height_of_portrait_images() {
+        let policy = Policy::new(200);
+        let fits = |size: iiif::Size| {
+            let size = policy.apply(Some(false), size);
+            crate::images::target_dimensions(&size, 1000, 3000).unwrap()
+        };
+
+        for size in [
+            iiif::Size::Full,
+            "pct:50".parse().unwrap(),
+            iiif::Size::Exactly(1000, 3000),
+            iiif::Size::Width(150),
+        ] {
+            let (width, height) = fits(size);
+            assert!(width <= 200 && height <= 200, "{width}x{height}");
+        }
+    }
+
+    #[test]
+    fn policy_from_config() {
+        assert_eq!(Policy::from_config(&RightsConfig::default()), None);
+        assert_eq!(
+            Policy::from_config(&RightsConfig {
+                enforce: true,
+                restricted_width: None
+            }),
+            Some(Policy::default())
+        );
+    }
+}
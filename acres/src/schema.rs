@@ -0,0 +1,802 @@
+//! Checks for API responses drifting away from the typed models.
+//!
+//! The types in this crate were written against version [`MODELS_VERSION`] of the API. When the
+//! API changes, a field can quietly turn from a number into a string, or disappear, and records
+//! that used to load start failing, or worse, load with gaps. A [`Checker`] looks at the records
+//! in live or cached responses and compares the fields it sees against a [`Model`], then
+//! [reports](Report) fields that are new, missing or of a different type, and any response from
+//! a different API version.
+//!
+//! ```rust
+//! use acres::schema::{Checker, Drift, FieldType, Model};
+//! use serde_json::json;
+//!
+//! let mut checker = Checker::new(Model::Artworks);
+//! checker.observe(&json!({
+//!     "data": [{ "id": 4, "title": "Priest and Boy", "date_start": "1880" }],
+//!     "info": { "version": "1.13" }
+//! }));
+//! let report = checker.report();
+//!
+//! assert!(report.is_breaking());
+//! assert!(report.drift.contains(&Drift::TypeChanged {
+//!     field: "date_start".into(),
+//!     expected: FieldType::Integer,
+//!     found: FieldType::String,
+//! }));
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::{Context, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    AcresError, Api, Cached,
+    agents::request::agents,
+    artworks::request::artworks,
+    sounds::request::sounds,
+    tours::request::tours,
+    vocabulary::request::{artwork_types, category_terms},
+};
+
+/// The API version the typed models were written against.
+pub const MODELS_VERSION: &str = "1.13";
+
+/// The JSON type of a field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Boolean,
+    /// A whole number.
+    Integer,
+    /// Any number.
+    Number,
+    /// A string.
+    String,
+    /// An array.
+    Array,
+    /// An object.
+    Object,
+}
+
+impl FieldType {
+    /// Returns the type of a value.
+    pub fn of(value: &Value) -> FieldType {
+        match value {
+            Value::Null => FieldType::Null,
+            Value::Bool(_) => FieldType::Boolean,
+            Value::Number(number) if number.is_i64() || number.is_u64() => FieldType::Integer,
+            Value::Number(_) => FieldType::Number,
+            Value::String(_) => FieldType::String,
+            Value::Array(_) => FieldType::Array,
+            Value::Object(_) => FieldType::Object,
+        }
+    }
+
+    /// Returns whether a value of type `found` fits a field of this type.
+    fn accepts(self, found: FieldType) -> bool {
+        self == found || (self == FieldType::Number && found == FieldType::Integer)
+    }
+}
+
+impl Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FieldType::Null => "null",
+            FieldType::Boolean => "boolean",
+            FieldType::Integer => "integer",
+            FieldType::Number => "number",
+            FieldType::String => "string",
+            FieldType::Array => "array",
+            FieldType::Object => "object",
+        })
+    }
+}
+
+/// A field of a typed model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Field {
+    /// Name.
+    pub name: &'static str,
+    /// Type.
+    pub ty: FieldType,
+    /// Whether the field may be `null`.
+    pub nullable: bool,
+}
+
+const fn field(name: &'static str, ty: FieldType) -> Field {
+    Field {
+        name,
+        ty,
+        nullable: false,
+    }
+}
+
+const fn nullable(name: &'static str, ty: FieldType) -> Field {
+    Field {
+        name,
+        ty,
+        nullable: true,
+    }
+}
+
+/// Fields of [`artworks::Data`](crate::artworks::Data).
+const ARTWORK_FIELDS: [Field; 29] = [
+    field("id", FieldType::Integer),
+    nullable("image_id", FieldType::String),
+    field("title", FieldType::String),
+    nullable("artist_display", FieldType::String),
+    nullable("date_display", FieldType::String),
+    nullable("date_start", FieldType::Integer),
+    nullable("date_end", FieldType::Integer),
+    nullable("medium_display", FieldType::String),
+    nullable("credit_line", FieldType::String),
+    nullable("artist_id", FieldType::Integer),
+    nullable("artist_title", FieldType::String),
+    nullable("main_reference_number", FieldType::String),
+    nullable("dimensions", FieldType::String),
+    nullable("classification_title", FieldType::String),
+    nullable("department_id", FieldType::String),
+    nullable("artwork_type_id", FieldType::Integer),
+    nullable("style_id", FieldType::String),
+    field("style_ids", FieldType::Array),
+    nullable("classification_id", FieldType::String),
+    field("classification_ids", FieldType::Array),
+    field("subject_ids", FieldType::Array),
+    field("material_ids", FieldType::Array),
+    field("technique_ids", FieldType::Array),
+    nullable("thumbnail", FieldType::Object),
+    nullable("is_public_domain", FieldType::Boolean),
+    nullable("copyright_notice", FieldType::String),
+    nullable("provenance_text", FieldType::String),
+    nullable("exhibition_history", FieldType::String),
+    nullable("publication_history", FieldType::String),
+];
+
+/// Fields of [`agents::Data`](crate::agents::Data).
+const AGENT_FIELDS: [Field; 5] = [
+    field("id", FieldType::Integer),
+    field("title", FieldType::String),
+    nullable("birth_date", FieldType::Integer),
+    nullable("death_date", FieldType::Integer),
+    nullable("is_artist", FieldType::Boolean),
+];
+
+/// Fields of [`tours::Data`](crate::tours::Data).
+const TOUR_FIELDS: [Field; 10] = [
+    field("id", FieldType::Integer),
+    field("title", FieldType::String),
+    nullable("image", FieldType::String),
+    nullable("description", FieldType::String),
+    nullable("intro", FieldType::String),
+    nullable("intro_link", FieldType::String),
+    nullable("intro_transcript", FieldType::String),
+    field("artwork_titles", FieldType::Array),
+    field("artist_titles", FieldType::Array),
+    field("tour_stops", FieldType::Array),
+];
+
+/// Fields of [`sounds::Data`](crate::sounds::Data).
+const SOUND_FIELDS: [Field; 4] = [
+    field("id", FieldType::Integer),
+    field("title", FieldType::String),
+    nullable("web_url", FieldType::String),
+    nullable("transcript", FieldType::String),
+];
+
+/// Fields of [`vocabulary::CategoryTermData`](crate::vocabulary::CategoryTermData).
+const CATEGORY_TERM_FIELDS: [Field; 4] = [
+    field("id", FieldType::String),
+    field("title", FieldType::String),
+    nullable("subtype", FieldType::String),
+    nullable("parent_id", FieldType::String),
+];
+
+/// Fields of [`vocabulary::ArtworkTypeData`](crate::vocabulary::ArtworkTypeData).
+const ARTWORK_TYPE_FIELDS: [Field; 2] = [
+    field("id", FieldType::Integer),
+    field("title", FieldType::String),
+];
+
+/// A typed model that responses can be checked against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Model {
+    /// Artwork records.
+    Artworks,
+    /// Agent records.
+    Agents,
+    /// Tour records.
+    Tours,
+    /// Audio recording records.
+    Sounds,
+    /// Category term records.
+    CategoryTerms,
+    /// Artwork type records.
+    ArtworkTypes,
+}
+
+impl Model {
+    /// Every model.
+    pub const ALL: [Model; 6] = [
+        Model::Artworks,
+        Model::Agents,
+        Model::Tours,
+        Model::Sounds,
+        Model::CategoryTerms,
+        Model::ArtworkTypes,
+    ];
+
+    /// Returns the fields the model expects.
+    pub fn fields(&self) -> &'static [Field] {
+        match self {
+            Model::Artworks => &ARTWORK_FIELDS,
+            Model::Agents => &AGENT_FIELDS,
+            Model::Tours => &TOUR_FIELDS,
+            Model::Sounds => &SOUND_FIELDS,
+            Model::CategoryTerms => &CATEGORY_TERM_FIELDS,
+            Model::ArtworkTypes => &ARTWORK_TYPE_FIELDS,
+        }
+    }
+
+    /// Returns the `api_model` the API gives these records, which is also their path.
+    pub fn api_model(&self) -> &'static str {
+        match self {
+            Model::Artworks => "artworks",
+            Model::Agents => "agents",
+            Model::Tours => "tours",
+            Model::Sounds => "mobile-sounds",
+            Model::CategoryTerms => "category-terms",
+            Model::ArtworkTypes => "artwork-types",
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<&'static Field> {
+        self.fields().iter().find(|field| field.name == name)
+    }
+}
+
+impl Display for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.api_model())
+    }
+}
+
+impl FromStr for Model {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Model::ALL
+            .into_iter()
+            .find(|model| model.api_model() == s)
+            .ok_or_else(|| {
+                AcresError::Unexpected(anyhow!(
+                    "{s} is not a model; expected one of {}",
+                    Model::ALL.map(|model| model.api_model()).join(", ")
+                ))
+            })
+    }
+}
+
+/// A difference between what a model expects and what responses hold.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "drift")]
+pub enum Drift {
+    /// A field the model doesn't have.
+    New {
+        /// Name.
+        field: String,
+        /// Types seen.
+        found: Vec<FieldType>,
+    },
+    /// A field the model expects that no record had.
+    Missing {
+        /// Name.
+        field: String,
+    },
+    /// A field whose values aren't of the type the model expects.
+    TypeChanged {
+        /// Name.
+        field: String,
+        /// Type the model expects.
+        expected: FieldType,
+        /// Type seen.
+        found: FieldType,
+    },
+}
+
+impl Drift {
+    /// Returns whether the drift can break loading or lose data.
+    ///
+    /// New fields are only informational.
+    pub fn is_breaking(&self) -> bool {
+        !matches!(self, Drift::New { .. })
+    }
+}
+
+impl Display for Drift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Drift::New { field, found } => {
+                let found: Vec<String> = found.iter().map(ToString::to_string).collect();
+                write!(f, "new field {field} ({})", found.join(" or "))
+            }
+            Drift::Missing { field } => write!(f, "missing field {field}"),
+            Drift::TypeChanged {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "field {field} changed type (expected {expected}, found {found})"
+            ),
+        }
+    }
+}
+
+/// What a [`Checker`] found.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    /// The model checked against.
+    pub model: Model,
+    /// How many records were checked.
+    pub records: usize,
+    /// The API versions responses came from.
+    pub versions: BTreeSet<String>,
+    /// Differences from the model, breaking ones first.
+    pub drift: Vec<Drift>,
+}
+
+impl Report {
+    /// Returns the API versions seen that differ from [`MODELS_VERSION`].
+    pub fn other_versions(&self) -> Vec<&str> {
+        self.versions
+            .iter()
+            .map(String::as_str)
+            .filter(|version| *version != MODELS_VERSION)
+            .collect()
+    }
+
+    /// Returns whether any drift can break loading or lose data.
+    pub fn is_breaking(&self) -> bool {
+        self.drift.iter().any(Drift::is_breaking)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}: checked {} records", self.model, self.records)?;
+        for version in self.other_versions() {
+            writeln!(
+                f,
+                "  warning: API version {version} differs from {MODELS_VERSION}, which the models were written against"
+            )?;
+        }
+        for drift in &self.drift {
+            let level = if drift.is_breaking() { "error" } else { "info" };
+            writeln!(f, "  {level}: {drift}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the records in responses against a [`Model`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checker {
+    model: Model,
+    records: usize,
+    seen: BTreeMap<String, BTreeSet<FieldType>>,
+    versions: BTreeSet<String>,
+}
+
+impl Checker {
+    /// Creates a checker for a model.
+    pub fn new(model: Model) -> Self {
+        Checker {
+            model,
+            records: 0,
+            seen: BTreeMap::new(),
+            versions: BTreeSet::new(),
+        }
+    }
+
+    /// Checks a response, with one record or a list of them in `data`.
+    ///
+    /// Responses asked for with `fields` only hold those fields, so other fields will look
+    /// missing. Check whole records where you can.
+    pub fn observe(&mut self, response: &Value) {
+        if let Some(version) = response["info"]["version"].as_str()
+            && self.versions.insert(version.to_string())
+            && version != MODELS_VERSION
+        {
+            tracing::warn!(
+                model = %self.model,
+                "API version {version} differs from {MODELS_VERSION}, which the models were written against"
+            );
+        }
+        match &response["data"] {
+            Value::Array(records) => records
+                .iter()
+                .for_each(|record| self.observe_record(record)),
+            record @ Value::Object(_) => self.observe_record(record),
+            _ => {}
+        }
+    }
+
+    /// Checks a single record.
+    pub fn observe_record(&mut self, record: &Value) {
+        let Value::Object(record) = record else {
+            return;
+        };
+        self.records += 1;
+        for (name, value) in record {
+            self.seen
+                .entry(name.clone())
+                .or_default()
+                .insert(FieldType::of(value));
+        }
+    }
+
+    /// Checks the cached responses in a directory, and returns how many had records of the model.
+    ///
+    /// Only records that say which model they are, with `api_model`, are checked.
+    pub fn observe_dir(&mut self, dir: impl AsRef<Path>) -> Result<usize, AcresError> {
+        let dir = dir.as_ref();
+        let mut responses = 0;
+        let entries =
+            std::fs::read_dir(dir).with_context(|| format!("listing {}", dir.display()))?;
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let Ok(response) = std::fs::read(&path)
+                .ok()
+                .map(|data| serde_json::from_slice::<Value>(&data))
+                .transpose()
+            else {
+                continue;
+            };
+            let Some(mut response) = response else {
+                continue;
+            };
+            let model = self.model.api_model();
+            let matches = |record: &Value| record["api_model"].as_str() == Some(model);
+            match &mut response["data"] {
+                Value::Array(records) => records.retain(matches),
+                record if !matches(record) => *record = Value::Null,
+                _ => {}
+            }
+            let found = match &response["data"] {
+                Value::Array(records) => !records.is_empty(),
+                record => record.is_object(),
+            };
+            if found {
+                self.observe(&response);
+                responses += 1;
+            }
+        }
+        Ok(responses)
+    }
+
+    /// Fetches a page of whole records from the API and checks them.
+    ///
+    /// The request is built with the model's list builder, but the response is kept as raw JSON
+    /// so fields the typed records would drop still show up as drift.
+    pub async fn sample(&mut self, api: &Api, limit: u32) -> Result<(), AcresError> {
+        let base_uri = api.base_uri();
+        let limit = Some(limit);
+        let request = match self.model {
+            Model::Artworks => artworks::Builder::new()
+                .base_uri(base_uri)
+                .limit(limit)
+                .build()?
+                .to_string(),
+            Model::Agents => agents::Builder::new()
+                .base_uri(base_uri)
+                .limit(limit)
+                .build()?
+                .to_string(),
+            Model::Tours => tours::Builder::new()
+                .base_uri(base_uri)
+                .limit(limit)
+                .build()?
+                .to_string(),
+            Model::Sounds => sounds::Builder::new()
+                .base_uri(base_uri)
+                .limit(limit)
+                .build()?
+                .to_string(),
+            Model::CategoryTerms => category_terms::Builder::new()
+                .base_uri(base_uri)
+                .limit(limit)
+                .build()?
+                .to_string(),
+            Model::ArtworkTypes => artwork_types::Builder::new()
+                .base_uri(base_uri)
+                .limit(limit)
+                .build()?
+                .to_string(),
+        };
+        let response: Cached = api.fetch(request).await?;
+        self.observe(&response.0);
+        Ok(())
+    }
+
+    /// Reports the differences found so far.
+    pub fn report(&self) -> Report {
+        let mut drift = vec![];
+        for (name, types) in &self.seen {
+            let Some(field) = self.model.field(name) else {
+                drift.push(Drift::New {
+                    field: name.clone(),
+                    found: types.iter().copied().collect(),
+                });
+                continue;
+            };
+            for found in types {
+                let fits =
+                    field.ty.accepts(*found) || (field.nullable && *found == FieldType::Null);
+                if !fits {
+                    drift.push(Drift::TypeChanged {
+                        field: name.clone(),
+                        expected: field.ty,
+                        found: *found,
+                    });
+                }
+            }
+        }
+        if self.records > 0 {
+            for field in self.model.fields() {
+                if !self.seen.contains_key(field.name) {
+                    drift.push(Drift::Missing {
+                        field: field.name.to_string(),
+                    });
+                }
+            }
+        }
+        drift.sort_by_key(|drift| !drift.is_breaking());
+        Report {
+            model: self.model,
+            records: self.records,
+            versions: self.versions.clone(),
+            drift,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// The fields a type writes out, which its model has to know about.
+    fn serialized_fields<T: Serialize + Default>() -> Vec<String> {
+        match serde_json::to_value(T::default()).unwrap() {
+            Value::Object(object) => object.keys().cloned().collect(),
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn models_cover_the_typed_fields() {
+        for (model, fields) in [
+            (
+                Model::Artworks,
+                serialized_fields::<crate::artworks::Data>(),
+            ),
+            (Model::Agents, serialized_fields::<crate::agents::Data>()),
+            (Model::Tours, serialized_fields::<crate::tours::Data>()),
+            (Model::Sounds, serialized_fields::<crate::sounds::Data>()),
+            (
+                Model::CategoryTerms,
+                serialized_fields::<crate::vocabulary::CategoryTermData>(),
+            ),
+            (
+                Model::ArtworkTypes,
+                serialized_fields::<crate::vocabulary::ArtworkTypeData>(),
+            ),
+        ] {
+            for name in fields {
+                assert!(model.field(&name).is_some(), "{model} is missing {name}");
+            }
+        }
+    }
+
+    /// Returns a value of a field's declared type.
+    fn sample(ty: FieldType) -> Value {
+        match ty {
+            FieldType::Null => Value::Null,
+            FieldType::Boolean => json!(true),
+            FieldType::Integer => json!(1),
+            FieldType::Number => json!(1.5),
+            FieldType::String => json!("x"),
+            FieldType::Array => json!([]),
+            FieldType::Object => json!({}),
+        }
+    }
+
+    /// Loads a record into `T` and writes it out again.
+    fn round_trip<T: Serialize + serde::de::DeserializeOwned>(
+        record: Value,
+    ) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(serde_json::from_value::<T>(record)?)
+    }
+
+    #[test]
+    fn model_tables_match_the_typed_fields() {
+        for model in Model::ALL {
+            let round_trip = match model {
+                Model::Artworks => round_trip::<crate::artworks::Data>,
+                Model::Agents => round_trip::<crate::agents::Data>,
+                Model::Tours => round_trip::<crate::tours::Data>,
+                Model::Sounds => round_trip::<crate::sounds::Data>,
+                Model::CategoryTerms => round_trip::<crate::vocabulary::CategoryTermData>,
+                Model::ArtworkTypes => round_trip::<crate::vocabulary::ArtworkTypeData>,
+            };
+            let record: Value = model
+                .fields()
+                .iter()
+                .map(|field| (field.name.to_string(), sample(field.ty)))
+                .collect::<serde_json::Map<_, _>>()
+                .into();
+            let with = |name: &str, value: Value| {
+                let mut record = record.clone();
+                record[name] = value;
+                record
+            };
+
+            let written = round_trip(record.clone())
+                .unwrap_or_else(|error| panic!("{model} doesn't load its declared types: {error}"));
+            for field in model.fields() {
+                if let Some(value) = written.get(field.name) {
+                    assert!(
+                        field.ty.accepts(FieldType::of(value)),
+                        "{model} {} is declared {} but written as {value}",
+                        field.name,
+                        field.ty
+                    );
+                }
+                let wrong = if field.ty == FieldType::Object {
+                    json!("x")
+                } else {
+                    json!({ "not": "a field" })
+                };
+                assert!(
+                    round_trip(with(field.name, wrong)).is_err(),
+                    "{model} {} isn't a field, or takes any type",
+                    field.name
+                );
+                if field.nullable {
+                    assert!(
+                        round_trip(with(field.name, Value::Null)).is_ok(),
+                        "{model} {} is declared nullable but can't be null",
+                        field.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reports_new_missing_and_changed_fields() {
+        let mut checker = Checker::new(Model::Agents);
+        checker.observe(&json!({
+            "data": [
+                { "id": 34, "title": "Claude Monet", "birth_date": 1840, "death_date": 1926, "is_artist": true, "ulan_id": 500019484 },
+                { "id": 35, "title": "Anonymous", "birth_date": null, "death_date": "c. 1900", "is_artist": true, "ulan_id": null }
+            ],
+            "info": { "version": "1.14" }
+        }));
+        checker.observe(
+            &json!({ "data": { "id": 36, "title": "Unknown" }, "info": { "version": "1.13" } }),
+        );
+
+        let report = checker.report();
+
+        assert_eq!(report.records, 3);
+        assert_eq!(report.other_versions(), vec!["1.14"]);
+        assert!(report.is_breaking());
+        assert_eq!(
+            report.drift,
+            vec![
+                Drift::TypeChanged {
+                    field: "death_date".into(),
+                    expected: FieldType::Integer,
+                    found: FieldType::String,
+                },
+                Drift::New {
+                    field: "ulan_id".into(),
+                    found: vec![FieldType::Null, FieldType::Integer],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_fields_no_record_had() {
+        let mut checker = Checker::new(Model::ArtworkTypes);
+        assert!(checker.report().drift.is_empty());
+
+        checker.observe(&json!({ "data": [{ "id": 1 }] }));
+
+        assert_eq!(
+            checker.report().drift,
+            vec![Drift::Missing {
+                field: "title".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn checks_cached_responses_of_the_model() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("1"),
+            json!({ "data": { "id": 1, "title": "Painting", "api_model": "artwork-types" } })
+                .to_string(),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("2"),
+            json!({ "data": [{ "id": 34, "title": "Claude Monet", "api_model": "agents" }] })
+                .to_string(),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("3"), b"\xff\xd8 not json").unwrap();
+        let mut checker = Checker::new(Model::ArtworkTypes);
+
+        assert_eq!(checker.observe_dir(dir.path()).unwrap(), 1);
+
+        let report = checker.report();
+        assert_eq!(report.records, 1);
+        assert_eq!(
+            report.drift,
+            vec![Drift::New {
+                field: "api_model".into(),
+                found: vec![FieldType::String]
+            }]
+        );
+        assert!(!report.is_breaking());
+    }
+
+    #[tokio::test]
+    async fn samples_live_records() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/mobile-sounds"))
+            .and(wiremock::matchers::query_param("limit", "5"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{ "id": 226, "title": "Intro", "web_url": null, "transcript": 42 }],
+                "info": { "version": "1.13" }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+        let mut checker = Checker::new(Model::Sounds);
+
+        checker.sample(&api, 5).await.unwrap();
+
+        let report = checker.report();
+        assert!(report.other_versions().is_empty());
+        assert_eq!(
+            report.drift,
+            vec![Drift::TypeChanged {
+                field: "transcript".into(),
+                expected: FieldType::String,
+                found: FieldType::Integer,
+            }]
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! "More like this" discovery of related artworks.
+//!
+//! [`Similar`] looks up an artwork and searches for others like it. Text similarity comes from an
+//! Elasticsearch [`more_like_this`] query over the title, description and subject terms, and is
+//! combined with structured signals: the same artist, style and classification, the same [era],
+//! and a dominant [color] close to the artwork's own. Each signal has a weight in [`Weights`], so
+//! you can, for example, favor works by the same artist over works that merely share a palette.
+//!
+//! ```no_run
+//! # use anyhow::Result;
+//! use acres::similar::{Similar, Weights};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let api = acres::Api::new();
+//! let weights = Weights { artist: 5.0, color: 0.0, ..Weights::default() };
+//! let related = Similar::new().weights(weights).size(6).find(&api, 27992).await?;
+//! for artwork in related.data() {
+//!     println!("{}", artwork["title"]);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`more_like_this`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-mlt-query.html
+//! [era]: ../dating/enum.Era.html
+//! [color]: ../color/index.html
+
+use std::str::FromStr;
+
+use serde_json::{Value, json};
+
+use crate::{
+    AcresError, Api, Cached,
+    artworks::{
+        Search,
+        request::{artwork, search},
+    },
+    color::{self, Hsl, Tolerance},
+    dating::Era,
+};
+
+/// Fields used to describe the artwork that related works are found for.
+const SOURCE_FIELDS: [&str; 9] = [
+    "id",
+    "title",
+    "description",
+    "term_titles",
+    "artist_id",
+    "style_id",
+    "classification_id",
+    "date_start",
+    "color",
+];
+
+/// Fields compared for text similarity.
+const TEXT_FIELDS: [&str; 3] = ["title", "description", "term_titles"];
+
+/// How much each signal counts toward similarity.
+///
+/// A weight of zero leaves that signal out.
+///
+/// You can create one from a string of `signal=weight` pairs, starting from the defaults.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::similar::Weights;
+///
+/// # fn main() -> Result<()> {
+/// let weights: Weights = "artist=5,color=0".parse()?;
+/// assert_eq!(weights.artist, 5.0);
+/// assert_eq!(weights.color, 0.0);
+/// assert_eq!(weights.text, Weights::default().text);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Weights {
+    /// Similar title, description and subject terms.
+    pub text: f64,
+    /// Same artist.
+    pub artist: f64,
+    /// Same style.
+    pub style: f64,
+    /// Same classification (e.g., "painting").
+    pub classification: f64,
+    /// Made in the same era.
+    pub era: f64,
+    /// A dominant color close to the artwork's own.
+    pub color: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights {
+            text: 1.0,
+            artist: 3.0,
+            style: 2.0,
+            classification: 1.5,
+            era: 1.0,
+            color: 1.0,
+        }
+    }
+}
+
+impl FromStr for Weights {
+    type Err = AcresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |pair: &str| {
+            AcresError::InvalidSearchQueryParams(format!(
+                "expected signal=weight, where signal is one of text, artist, style, \
+                 classification, era or color, but got '{pair}'"
+            ))
+        };
+        let mut weights = Weights::default();
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (signal, weight) = pair.split_once('=').ok_or_else(|| invalid(pair))?;
+            let weight: f64 = weight
+                .trim()
+                .parse()
+                .ok()
+                .filter(|weight: &f64| *weight >= 0.0)
+                .ok_or_else(|| invalid(pair))?;
+            match signal.trim() {
+                "text" => weights.text = weight,
+                "artist" => weights.artist = weight,
+                "style" => weights.style = weight,
+                "classification" => weights.classification = weight,
+                "era" => weights.era = weight,
+                "color" => weights.color = weight,
+                _ => return Err(invalid(pair)),
+            }
+        }
+        Ok(weights)
+    }
+}
+
+/// Finds artworks related to another.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Similar {
+    weights: Weights,
+    tolerance: Tolerance,
+    size: u32,
+    fields: Vec<String>,
+}
+
+impl Default for Similar {
+    fn default() -> Self {
+        Similar {
+            weights: Weights::default(),
+            tolerance: Tolerance::default(),
+            size: 10,
+            fields: vec![],
+        }
+    }
+}
+
+impl Similar {
+    /// Creates a finder with the default weights.
+    pub fn new() -> Self {
+        Similar::default()
+    }
+
+    /// Sets how much each signal counts.
+    pub fn weights(mut self, weights: Weights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Sets how close a color must be to count as similar.
+    pub fn tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets how many related artworks to return.
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the artwork fields to retrieve for each related artwork.
+    pub fn fields(mut self, fields: Vec<String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Returns the search query for artworks related to a record.
+    ///
+    /// The record needs an `id`, and uses whichever of `title`, `description`, `term_titles`,
+    /// `artist_id`, `style_id`, `classification_id`, `date_start` and `color` it has. The record
+    /// itself is never a match.
+    pub fn query(&self, record: &Value) -> Value {
+        let weights = self.weights;
+        let mut should = vec![];
+        let like = like_text(record);
+        if weights.text > 0.0 && !like.is_empty() {
+            should.push(json!({
+                "more_like_this": {
+                    "fields": TEXT_FIELDS,
+                    "like": like,
+                    "min_term_freq": 1,
+                    "min_doc_freq": 2,
+                    "max_query_terms": 25,
+                    "boost": weights.text
+                }
+            }));
+        }
+        for (field, weight) in [
+            ("artist_id", weights.artist),
+            ("style_id", weights.style),
+            ("classification_id", weights.classification),
+        ] {
+            if let Some(value) = record.get(field).filter(|value| !value.is_null())
+                && weight > 0.0
+            {
+                should.push(json!({ "term": { field: { "value": value, "boost": weight } } }));
+            }
+        }
+        if let Some(year) = record["date_start"].as_i64()
+            && weights.era > 0.0
+        {
+            should.push(era_query(Era::of(year), weights.era));
+        }
+        if let Some(hsl) = Hsl::of(record)
+            && weights.color > 0.0
+        {
+            let query = color::Query::new(hsl).tolerance(self.tolerance).query();
+            should.push(json!({ "bool": { "must": query, "boost": weights.color } }));
+        }
+        json!({
+            "bool": {
+                "should": should,
+                "minimum_should_match": 1,
+                "must_not": [{ "term": { "id": record["id"] } }]
+            }
+        })
+    }
+
+    /// Looks up an artwork and finds artworks related to it, most similar first.
+    pub async fn find(&self, api: &Api, id: u32) -> Result<Search, AcresError> {
+        let request = format!(
+            "{}?fields={}",
+            artwork::Request::new(api.base_uri(), id),
+            SOURCE_FIELDS.join(",")
+        );
+        let artwork: Cached = api.fetch(request).await?;
+        let request = search::Builder::new()
+            .base_uri(api.base_uri())
+            .query(Some(self.query(&artwork.0["data"]).to_string()))
+            .size(Some(self.size))
+            .fields(Some(self.fields.clone()))
+            .build()?;
+        api.send(&request).await
+    }
+}
+
+/// Returns the text that related works should be like.
+fn like_text(record: &Value) -> String {
+    TEXT_FIELDS
+        .iter()
+        .flat_map(|field| match &record[field] {
+            Value::String(text) => vec![text.as_str()],
+            Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn era_query(era: Era, boost: f64) -> Value {
+    let span = era.span();
+    let mut range = json!({ "boost": boost });
+    if span.start() > i64::MIN {
+        range["gte"] = json!(span.start());
+    }
+    if span.end() < i64::MAX {
+        range["lte"] = json!(span.end());
+    }
+    json!({ "range": { "date_start": range } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Value {
+        json!({
+            "id": 27992,
+            "title": "A Sunday on La Grande Jatte — 1884",
+            "description": "<p>Seurat's most famous painting.</p>",
+            "term_titles": ["Pointillism", "leisure"],
+            "artist_id": 40482,
+            "style_id": "TM-7543",
+            "classification_id": null,
+            "date_start": 1884,
+            "color": { "h": 44, "s": 34, "l": 51 }
+        })
+    }
+
+    #[test]
+    fn combines_text_and_structured_signals() {
+        let query = Similar::new().query(&record());
+        let should = query["bool"]["should"].as_array().unwrap();
+
+        assert_eq!(should.len(), 5);
+        assert_eq!(
+            should[0]["more_like_this"]["like"],
+            "A Sunday on La Grande Jatte — 1884\n<p>Seurat's most famous painting.</p>\n\
+             Pointillism\nleisure"
+        );
+        assert_eq!(
+            should[1],
+            json!({ "term": { "artist_id": { "value": 40482, "boost": 3.0 } } })
+        );
+        assert_eq!(
+            should[2],
+            json!({ "term": { "style_id": { "value": "TM-7543", "boost": 2.0 } } })
+        );
+        assert_eq!(
+            should[3],
+            json!({ "range": { "date_start": { "gte": 1800, "lte": 1944, "boost": 1.0 } } })
+        );
+        assert_eq!(should[4]["bool"]["boost"], 1.0);
+        assert_eq!(
+            query["bool"]["must_not"],
+            json!([{ "term": { "id": 27992 } }])
+        );
+    }
+
+    #[test]
+    fn zero_weights_leave_signals_out() {
+        let weights: Weights = "text=0, color=0, era=0".parse().unwrap();
+        let query = Similar::new().weights(weights).query(&record());
+        let should = query["bool"]["should"].as_array().unwrap();
+
+        assert_eq!(should.len(), 2);
+        assert!(should.iter().all(|clause| clause.get("term").is_some()));
+    }
+
+    #[test]
+    fn rejects_unknown_signals() {
+        assert!(matches!(
+            "mood=2".parse::<Weights>(),
+            Err(AcresError::InvalidSearchQueryParams(_))
+        ));
+        assert!("artist=-1".parse::<Weights>().is_err());
+    }
+
+    #[tokio::test]
+    async fn finds_related_artworks() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/27992"))
+            .and(wiremock::matchers::query_param(
+                "fields",
+                SOURCE_FIELDS.join(","),
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(json!({ "data": record() })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .and(wiremock::matchers::query_param_contains(
+                "query",
+                r#""must_not":[{"term":{"id":27992}}]"#,
+            ))
+            .and(wiremock::matchers::query_param("size", "3"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(json!({ "data": [{ "id": 28067 }] })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+
+        let related = Similar::new().size(3).find(&api, 27992).await.unwrap();
+
+        assert_eq!(related.data()[0]["id"], 28067);
+    }
+
+    #[tokio::test]
+    async fn titles_with_url_characters_reach_the_query_whole() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        let mut source = record();
+        source["title"] = json!("Mother & Child #2 + 1");
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/27992"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(json!({ "data": source })),
+            )
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .and(wiremock::matchers::query_param_contains(
+                "query",
+                "Mother & Child #2 + 1",
+            ))
+            .and(wiremock::matchers::query_param_contains(
+                "query",
+                r#""must_not":[{"term":{"id":27992}}]"#,
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(json!({ "data": [{ "id": 28067 }] })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+
+        let related = Similar::new().find(&api, 27992).await.unwrap();
+
+        assert_eq!(related.data()[0]["id"], 28067);
+    }
+}
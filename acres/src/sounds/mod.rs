@@ -0,0 +1,28 @@
+//! Modules and types for working with the [Mobile Sounds Collection].
+//!
+//! These are the audio recordings that go with tour stops and the museum's audio guide.
+//!
+//! [Mobile Sounds Collection]: https://api.artic.edu/docs/#mobile-sounds
+
+mod sound;
+
+pub use sound::{Data, Sound, Sounds};
+
+/// Modules for requesting items from the [Mobile Sounds Collection].
+///
+/// [Mobile Sounds Collection]: https://api.artic.edu/docs/#mobile-sounds
+pub mod request {
+    /// A [`GET /mobile-sounds/{id}`] request.
+    ///
+    /// [`GET /mobile-sounds/{id}`]: https://api.artic.edu/docs/#get-mobile-sounds-id
+    pub mod sound {
+        pub use crate::sounds::sound::Request;
+    }
+
+    /// A [`GET /mobile-sounds`] request.
+    ///
+    /// [`GET /mobile-sounds`]: https://api.artic.edu/docs/#get-mobile-sounds
+    pub mod sounds {
+        pub use crate::sounds::sound::{Builder, ListRequest as Request};
+    }
+}
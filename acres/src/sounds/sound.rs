@@ -0,0 +1,180 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AcresError,
+    artworks::{CollectionQueryParams, Config, Info, Pagination},
+    endpoint::json_response,
+};
+
+/// An audio recording, as returned by [`GET /mobile-sounds/{id}`].
+///
+/// [`GET /mobile-sounds/{id}`]: https://api.artic.edu/docs/#get-mobile-sounds-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Sound {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Data,
+    /// Info.
+    pub info: Info,
+}
+
+impl Display for Sound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} ({})", self.data.title, self.data.id))
+    }
+}
+
+json_response!(Sound, Sounds);
+
+/// A page of audio recordings, as returned by [`GET /mobile-sounds`].
+///
+/// [`GET /mobile-sounds`]: https://api.artic.edu/docs/#get-mobile-sounds
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Sounds {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Vec<Data>,
+    /// Info.
+    pub info: Info,
+    /// Pagination.
+    pub pagination: Pagination,
+}
+
+/// Audio recording record.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Data {
+    /// ID.
+    pub id: u64,
+    /// Title.
+    #[serde(default)]
+    pub title: String,
+    /// URL of the audio file.
+    #[serde(default)]
+    pub web_url: Option<String>,
+    /// What's said in the recording.
+    #[serde(default)]
+    pub transcript: Option<String>,
+}
+
+/// A [`GET /mobile-sounds/{id}`] request.
+///
+/// ```rust
+/// use acres::{Api, sounds::request::sound::Request};
+///
+/// let request = Request::new(Api::new().base_uri(), 226);
+/// ```
+///
+/// [`GET /mobile-sounds/{id}`]: https://api.artic.edu/docs/#get-mobile-sounds-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Request {
+    base_uri: String,
+    id: u32,
+}
+
+impl Display for Request {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}/mobile-sounds/{}", self.base_uri, self.id))
+    }
+}
+
+impl Request {
+    /// Constructs a new audio recording request.
+    pub fn new(base_uri: String, id: u32) -> Self {
+        Self { base_uri, id }
+    }
+}
+
+/// A [`GET /mobile-sounds`] request.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::{Api, sounds::request::sounds};
+///
+/// # fn main() -> Result<()> {
+/// let request = sounds::Builder::new()
+///     .base_uri(Api::new().base_uri())
+///     .ids(Some(vec![226, 227]))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`GET /mobile-sounds`]: https://api.artic.edu/docs/#get-mobile-sounds
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ListRequest(String);
+
+impl Display for ListRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+/// A [`GET /mobile-sounds`] request builder.
+///
+/// [`GET /mobile-sounds`]: https://api.artic.edu/docs/#get-mobile-sounds
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    base_uri: String,
+    ids: Option<Vec<u32>>,
+    limit: Option<u32>,
+    page: Option<u32>,
+    fields: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Sets the base URI.
+    pub fn base_uri(mut self, base_uri: String) -> Self {
+        self.base_uri = base_uri;
+        self
+    }
+
+    /// Sets the recording ids to retrieve.
+    pub fn ids(mut self, ids: Option<Vec<u32>>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Sets limit on number of recordings to return per page.
+    pub fn limit(mut self, limit: Option<u32>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets page number to return.
+    pub fn page(mut self, page: Option<u32>) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Sets the recording fields to retrieve.
+    pub fn fields(mut self, fields: Option<Vec<String>>) -> Self {
+        if let Some(fields) = fields {
+            self.fields = fields;
+        }
+        self
+    }
+
+    /// Builds request for the recordings collection.
+    pub fn build(&self) -> Result<ListRequest, AcresError> {
+        let query_params = CollectionQueryParams {
+            ids: self.ids.clone(),
+            limit: self.limit,
+            page: self.page,
+            fields: self.fields.clone(),
+            include: vec![],
+        };
+        Ok(ListRequest(format!(
+            "{}/mobile-sounds{}",
+            self.base_uri, query_params
+        )))
+    }
+}
@@ -0,0 +1,562 @@
+//! Streaming decoding of response `data`.
+//!
+//! [`Api::fetch`] buffers the whole body and parses it into one tree, which is fine for a single
+//! artwork but wasteful for a page of 100 artworks with every field, or for a data dump. A
+//! [`Decoder`] is fed the body as it arrives and hands back each item in `data` as soon as it is
+//! complete, so only one item is held in memory at a time. The other members of the response,
+//! like `pagination` and `info`, are kept in the [envelope].
+//!
+//! [`Api::stream`] does this over the network, and [`items`] does it for anything you can read,
+//! like a file.
+//!
+//! ```no_run
+//! # use anyhow::Result;
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let api = acres::Api::new();
+//! let mut stream = api
+//!     .stream("https://api.artic.edu/api/v1/artworks?limit=100".into())
+//!     .await?;
+//! while let Some(artwork) = stream.next_item().await? {
+//!     println!("{}", artwork["title"]);
+//! }
+//! println!("{}", stream.envelope()["pagination"]["total_pages"]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Api::fetch`]: ../struct.Api.html#method.fetch
+//! [`Api::stream`]: ../struct.Api.html#method.stream
+//! [envelope]: struct.Decoder.html#method.envelope
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{Read, Write},
+    path::PathBuf,
+};
+
+use anyhow::{Context, anyhow};
+use bytes::Bytes;
+use serde_json::{Map, Value};
+
+use crate::{AcresError, cache};
+
+/// How much of a reader is decoded at a time.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Where the decoder is in the response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum State {
+    /// Before the opening brace.
+    Start,
+    /// Reading a member's key.
+    Key,
+    /// Reading a member's value.
+    Value(String),
+    /// Reading the items in `data`.
+    Items,
+    /// Past the items in `data`, before the next member.
+    AfterItems,
+    /// Past the closing brace.
+    End,
+}
+
+/// Decodes the items in a response's `data` as the body arrives.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::stream::Decoder;
+///
+/// # fn main() -> Result<()> {
+/// let mut decoder = Decoder::new();
+/// decoder.push(br#"{"pagination": {"total": 2}, "data": [{"id": 1}, {"i"#)?;
+/// assert_eq!(decoder.next_item().unwrap()["id"], 1);
+/// assert!(decoder.next_item().is_none());
+///
+/// decoder.push(br#"d": 2}]}"#)?;
+/// decoder.finish()?;
+/// assert_eq!(decoder.next_item().unwrap()["id"], 2);
+/// assert_eq!(decoder.envelope()["pagination"]["total"], 2);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// A `data` that is a single object, as for `/artworks/{id}`, is decoded as one item.
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    state: State,
+    buffer: Vec<u8>,
+    nesting: usize,
+    in_string: bool,
+    escaped: bool,
+    expect_item: bool,
+    offset: usize,
+    items: VecDeque<Value>,
+    envelope: Map<String, Value>,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Decoder {
+            state: State::Start,
+            buffer: vec![],
+            nesting: 0,
+            in_string: false,
+            escaped: false,
+            expect_item: false,
+            offset: 0,
+            items: VecDeque::new(),
+            envelope: Map::new(),
+        }
+    }
+}
+
+impl Decoder {
+    /// Creates a decoder for a new response.
+    pub fn new() -> Self {
+        Decoder::default()
+    }
+
+    /// Decodes the next part of the body.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), AcresError> {
+        for &byte in chunk {
+            self.byte(byte)?;
+            self.offset += 1;
+        }
+        Ok(())
+    }
+
+    /// Checks that the whole response has been decoded.
+    pub fn finish(&self) -> Result<(), AcresError> {
+        if self.state != State::End {
+            return Err(self.error("unexpected end of response"));
+        }
+        Ok(())
+    }
+
+    /// Returns the next decoded item, if one is ready.
+    pub fn next_item(&mut self) -> Option<Value> {
+        self.items.pop_front()
+    }
+
+    /// Returns the members of the response other than `data` that have been decoded so far.
+    pub fn envelope(&self) -> &Map<String, Value> {
+        &self.envelope
+    }
+
+    fn error(&self, message: &str) -> AcresError {
+        anyhow!("{message} at byte {} of response", self.offset).into()
+    }
+
+    fn byte(&mut self, byte: u8) -> Result<(), AcresError> {
+        if self.in_string {
+            self.buffer.push(byte);
+            if self.escaped {
+                self.escaped = false;
+            } else if byte == b'\\' {
+                self.escaped = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+            }
+            return Ok(());
+        }
+        if self.nesting > 0 {
+            match byte {
+                b'{' | b'[' => self.nesting += 1,
+                b'}' | b']' => self.nesting -= 1,
+                b'"' => self.in_string = true,
+                _ => {}
+            }
+            self.buffer.push(byte);
+            return Ok(());
+        }
+        match (&self.state, byte) {
+            (State::Start | State::AfterItems | State::End, byte) if byte.is_ascii_whitespace() => {
+            }
+            (State::Start, b'{') => self.state = State::Key,
+            (State::Start, _) => return Err(self.error("expected a JSON object")),
+            (State::End, _) => return Err(self.error("unexpected data after response")),
+            (State::Key, b':') => {
+                let key = serde_json::from_slice(&self.buffer)
+                    .map_err(|_| self.error("expected a member name"))?;
+                self.buffer.clear();
+                self.state = State::Value(key);
+            }
+            (State::Key, b'}') if self.is_blank() => self.state = State::End,
+            (State::Value(key), b'[') if key == "data" && self.is_blank() => {
+                self.buffer.clear();
+                self.state = State::Items;
+            }
+            (State::Value(_), b',' | b'}') => {
+                let State::Value(key) = std::mem::replace(&mut self.state, State::Key) else {
+                    unreachable!("matched above");
+                };
+                let value = self.take_value()?;
+                if key != "data" {
+                    self.envelope.insert(key, value);
+                } else if !value.is_null() {
+                    self.items.push_back(value);
+                }
+                if byte == b'}' {
+                    self.state = State::End;
+                }
+            }
+            (State::Items, b',' | b']') => {
+                if !self.is_blank() {
+                    let item = self.take_value()?;
+                    self.items.push_back(item);
+                } else if byte == b',' || self.expect_item {
+                    return Err(self.error("expected an item"));
+                }
+                self.expect_item = byte == b',';
+                if byte == b']' {
+                    self.state = State::AfterItems;
+                }
+            }
+            (State::AfterItems, b',') => self.state = State::Key,
+            (State::AfterItems, b'}') => self.state = State::End,
+            (State::AfterItems, _) => return Err(self.error("expected ',' or '}'")),
+            (_, byte) => {
+                match byte {
+                    b'{' | b'[' => self.nesting += 1,
+                    b'}' | b']' => return Err(self.error("unbalanced brackets")),
+                    b'"' => self.in_string = true,
+                    _ => {}
+                }
+                self.buffer.push(byte);
+            }
+        }
+        Ok(())
+    }
+
+    fn is_blank(&self) -> bool {
+        self.buffer.iter().all(u8::is_ascii_whitespace)
+    }
+
+    fn take_value(&mut self) -> Result<Value, AcresError> {
+        let value = serde_json::from_slice(&self.buffer)
+            .map_err(|error| self.error(&format!("invalid JSON ({error})")))?;
+        self.buffer.clear();
+        Ok(value)
+    }
+}
+
+/// Decodes the items in a response's `data` from a reader, one at a time.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let dump = br#"{"data": [{"id": 1}, {"id": 2}, {"id": 3}]}"#;
+/// let ids = acres::stream::items(&dump[..])
+///     .map(|item| item.map(|item| item["id"].clone()))
+///     .collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(ids, [1, 2, 3]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn items<R: Read>(reader: R) -> Items<R> {
+    Items {
+        reader,
+        decoder: Decoder::new(),
+        chunk: vec![0; CHUNK_SIZE],
+        done: false,
+    }
+}
+
+/// An iterator over the items in a response's `data`, created with [`items`].
+#[derive(Debug)]
+pub struct Items<R> {
+    reader: R,
+    decoder: Decoder,
+    /// Read into whenever the decoder runs out of items.
+    chunk: Vec<u8>,
+    done: bool,
+}
+
+impl<R> Items<R> {
+    /// Returns the members of the response other than `data` that have been decoded so far.
+    pub fn envelope(&self) -> &Map<String, Value> {
+        self.decoder.envelope()
+    }
+}
+
+impl<R: Read> Iterator for Items<R> {
+    type Item = Result<Value, AcresError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.decoder.next_item() {
+                return Some(Ok(item));
+            }
+            if self.done {
+                return None;
+            }
+            let result = match self.reader.read(&mut self.chunk) {
+                Ok(0) => {
+                    self.done = true;
+                    self.decoder.finish()
+                }
+                Ok(read) => self.decoder.push(&self.chunk[..read]),
+                Err(error) => Err(anyhow!(error).context("reading response").into()),
+            };
+            if let Err(error) = result {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+/// Where a stream's body comes from.
+#[derive(Debug)]
+pub(crate) enum Source {
+    /// A response coming off the wire, copied into the cache as it arrives.
+    Response(reqwest::Response, Option<CacheWriter>),
+    /// A cached response, and where it is.
+    File(File, PathBuf),
+    /// A response that has already arrived, e.g. from a cassette.
+    Bytes(Option<Bytes>),
+}
+
+/// Copies a response into the cache, only moving it into place once it's complete.
+#[derive(Debug)]
+pub(crate) struct CacheWriter {
+    path: PathBuf,
+    partial: PathBuf,
+    file: File,
+}
+
+impl CacheWriter {
+    pub(crate) fn create(path: PathBuf) -> Result<Self, AcresError> {
+        let partial = cache::temp_path(&path);
+        let file =
+            File::create(&partial).with_context(|| format!("creating {}", partial.display()))?;
+        Ok(CacheWriter {
+            path,
+            partial,
+            file,
+        })
+    }
+
+    fn write(&mut self, chunk: &[u8]) -> Result<(), AcresError> {
+        self.file
+            .write_all(chunk)
+            .with_context(|| format!("writing to {}", self.partial.display()))?;
+        Ok(())
+    }
+
+    fn complete(self) -> Result<(), AcresError> {
+        std::fs::rename(&self.partial, &self.path)
+            .with_context(|| format!("moving {} into the cache", self.partial.display()))?;
+        tracing::info!("Wrote '{}' to cache", self.path.display());
+        Ok(())
+    }
+}
+
+impl Drop for CacheWriter {
+    /// Cleans up after responses that weren't read to the end.
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.partial);
+    }
+}
+
+/// The items in a response's `data`, decoded as the body arrives.
+///
+/// Created with [`Api::stream`].
+///
+/// [`Api::stream`]: ../struct.Api.html#method.stream
+#[derive(Debug)]
+pub struct DataStream {
+    source: Source,
+    decoder: Decoder,
+    done: bool,
+}
+
+impl DataStream {
+    pub(crate) fn new(source: Source) -> Self {
+        DataStream {
+            source,
+            decoder: Decoder::new(),
+            done: false,
+        }
+    }
+
+    /// Returns the next item, or `None` once they've all been returned.
+    ///
+    /// A cached response that turns out to be corrupt is quarantined, so that it's fetched again
+    /// next time.
+    pub async fn next_item(&mut self) -> Result<Option<Value>, AcresError> {
+        loop {
+            if let Some(item) = self.decoder.next_item() {
+                return Ok(Some(item));
+            }
+            if self.done {
+                return Ok(None);
+            }
+            let decoded = match self.next_chunk().await? {
+                Some(chunk) => self.decoder.push(&chunk),
+                None => {
+                    self.done = true;
+                    self.decoder.finish()
+                }
+            };
+            if let Err(error) = decoded {
+                self.done = true;
+                if let Source::File(_, path) = &self.source {
+                    cache::quarantine(path)?;
+                }
+                return Err(error);
+            }
+            if self.done
+                && let Source::Response(_, cache) = &mut self.source
+                && let Some(cache) = cache.take()
+            {
+                cache.complete()?;
+            }
+        }
+    }
+
+    /// Returns the members of the response other than `data` that have been decoded so far.
+    ///
+    /// Members that come after `data` are only here once every item has been returned.
+    pub fn envelope(&self) -> &Map<String, Value> {
+        self.decoder.envelope()
+    }
+
+    async fn next_chunk(&mut self) -> Result<Option<Bytes>, AcresError> {
+        match &mut self.source {
+            Source::Response(response, cache) => {
+                let chunk = response
+                    .chunk()
+                    .await
+                    .with_context(|| format!("awaiting body from {}", response.url()))?;
+                if let (Some(chunk), Some(cache)) = (&chunk, cache) {
+                    cache.write(chunk)?;
+                }
+                Ok(chunk)
+            }
+            Source::File(file, _) => {
+                let mut chunk = vec![0; CHUNK_SIZE];
+                let read = file.read(&mut chunk).context("reading cached response")?;
+                chunk.truncate(read);
+                Ok((read > 0).then(|| chunk.into()))
+            }
+            Source::Bytes(bytes) => Ok(bytes.take()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn decode_in_chunks(body: &[u8], size: usize) -> (Vec<Value>, Map<String, Value>) {
+        let mut decoder = Decoder::new();
+        let mut items = vec![];
+        for chunk in body.chunks(size) {
+            decoder.push(chunk).unwrap();
+            items.extend(std::iter::from_fn(|| decoder.next_item()));
+        }
+        decoder.finish().unwrap();
+        (items, decoder.envelope().clone())
+    }
+
+    #[test]
+    fn any_chunking_decodes_the_same() {
+        let response = json!({
+            "pagination": { "total": 3, "limit": 3 },
+            "data": [
+                { "id": 1, "title": "A \"quoted\" [bracket] {brace}", "term_titles": ["a", "b"] },
+                { "id": 2, "title": "Ünïcödé — 東京", "color": null },
+                { "id": 3, "thumbnail": { "alt_text": "\\ back\\slash ,]}" } }
+            ],
+            "info": { "version": "1.13" },
+            "config": { "iiif_url": "https://www.artic.edu/iiif/2" }
+        });
+        let body = serde_json::to_vec_pretty(&response).unwrap();
+
+        for size in [1, 2, 7, 64, body.len()] {
+            let (items, envelope) = decode_in_chunks(&body, size);
+            assert_eq!(Value::Array(items), response["data"], "chunks of {size}");
+            assert_eq!(envelope["info"], response["info"]);
+            assert!(envelope.get("data").is_none());
+        }
+    }
+
+    #[test]
+    fn single_object_data_and_empty_data() {
+        let (items, _) = decode_in_chunks(br#"{"data": {"id": 4}, "info": {}}"#, 3);
+        assert_eq!(items, [json!({ "id": 4 })]);
+
+        let (items, envelope) = decode_in_chunks(br#"{"data": [], "pagination": null}"#, 3);
+        assert!(items.is_empty());
+        assert!(envelope["pagination"].is_null());
+    }
+
+    #[test]
+    fn reports_where_responses_break() {
+        let mut decoder = Decoder::new();
+        let error = decoder.push(br#"{"data": [{"id": 1}, ]}"#).unwrap_err();
+        assert_eq!(error.to_string(), "expected an item at byte 21 of response");
+
+        let mut decoder = Decoder::new();
+        decoder.push(br#"{"data": [{"id": 1}"#).unwrap();
+        assert_eq!(decoder.next_item(), None);
+        assert!(decoder.finish().is_err());
+
+        assert!(Decoder::new().push(b"[1, 2]").is_err());
+    }
+
+    #[test]
+    fn cache_writer_only_keeps_complete_responses() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("42");
+
+        let mut cache = CacheWriter::create(path.clone()).unwrap();
+        cache.write(br#"{"data": []}"#).unwrap();
+        assert!(!path.exists());
+        cache.complete().unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), br#"{"data": []}"#);
+
+        let abandoned = dir.path().join("43");
+        let mut cache = CacheWriter::create(abandoned.clone()).unwrap();
+        cache.write(br#"{"data": ["#).unwrap();
+        drop(cache);
+        assert!(!abandoned.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn items_reads_until_the_end() {
+        let body = br#"{"data": [1, "two", {"three": 3}], "info": {"version": "1.13"}}"#;
+        let mut items = items(&body[..]);
+
+        assert_eq!(items.next().unwrap().unwrap(), json!(1));
+        assert_eq!(items.next().unwrap().unwrap(), json!("two"));
+        assert_eq!(items.next().unwrap().unwrap(), json!({ "three": 3 }));
+        assert!(items.next().is_none());
+        assert_eq!(items.envelope()["info"]["version"], "1.13");
+    }
+
+    #[test]
+    fn items_only_reads_once_the_queue_is_empty() {
+        struct Counting<'a>(&'a [u8], usize);
+        impl Read for Counting<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.1 += 1;
+                self.0.read(buf)
+            }
+        }
+        let body = br#"{"data": [1, 2, 3]}"#;
+        let mut items = items(Counting(&body[..], 0));
+
+        items.next().unwrap().unwrap();
+        items.next().unwrap().unwrap();
+        items.next().unwrap().unwrap();
+
+        assert_eq!(items.reader.1, 1);
+    }
+}
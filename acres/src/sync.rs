@@ -0,0 +1,673 @@
+//! Incremental sync of the [Artworks collection] into a local store.
+//!
+//! Rather than re-downloading everything, a [`Syncer`] asks for the artworks that changed since
+//! the last run, sorted by a timestamp field, and upserts only those into a [`Store`]. The
+//! position reached is kept as a [`Cursor`] in the store, so the next run picks up from there.
+//!
+//! Deleted artworks never show up as changes, so they're found by comparing the ids in the store
+//! against the ids in the collection. That's a full listing, so it runs on a slower cadence. If
+//! the listing comes back short, or would remove too much of the store, nothing is deleted.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! # use serde_json::json;
+//! use acres::sync::{JsonStore, Syncer};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! # let mock_server = wiremock::MockServer::start().await;
+//! # let mock_uri = format!("{}/api/v1", mock_server.uri());
+//! # wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+//! #     .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({"data": []})))
+//! #     .mount(&mock_server)
+//! #     .await;
+//! # wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+//! #     .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(json!({
+//! #         "data": [],
+//! #         "pagination": { "total": 0, "limit": 100, "offset": 0, "total_pages": 0, "current_page": 1 },
+//! #         "info": { "license_text": "", "license_links": [], "version": "1.13" },
+//! #         "config": { "iiif_url": "https://www.artic.edu/iiif/2", "website_url": "https://www.artic.edu" }
+//! #     })))
+//! #     .mount(&mock_server)
+//! #     .await;
+//! # let dir = assert_fs::TempDir::new()?;
+//! let api = acres::Api::new();
+//! # let api = acres::Api::builder().base_uri(&mock_uri).build();
+//! let store = JsonStore::open(dir.path())?;
+//! let report = Syncer::new(&api, store).run().await?;
+//! println!("{} changed, {} deleted", report.upserted, report.deleted);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Artworks collection]: https://api.artic.edu/docs/#artworks
+
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, anyhow};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+use crate::{
+    AcresError, Api,
+    artworks::request::{artworks, search},
+    cache::{self, DirLock},
+};
+
+/// How far a sync has gotten.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// The latest change seen so far.
+    pub updated_since: Option<DateTime<Utc>>,
+    /// While the records changed at exactly `updated_since` are paged through by id, the last id
+    /// seen.
+    #[serde(default)]
+    pub after_id: Option<u64>,
+    /// When deletions were last checked for.
+    pub deletions_checked: Option<DateTime<Utc>>,
+}
+
+/// Somewhere to keep synced artwork records.
+///
+/// Records are the raw JSON objects from the API and are keyed by their `id`.
+pub trait Store {
+    /// Loads the cursor from the last run, if any.
+    fn cursor(&self) -> Result<Option<Cursor>, AcresError>;
+    /// Saves the cursor for the next run.
+    fn save_cursor(&mut self, cursor: &Cursor) -> Result<(), AcresError>;
+    /// Inserts or replaces records.
+    fn upsert(&mut self, records: &[Value]) -> Result<(), AcresError>;
+    /// Removes records.
+    fn delete(&mut self, ids: &[u64]) -> Result<(), AcresError>;
+    /// Returns the ids of every record in the store.
+    fn ids(&self) -> Result<BTreeSet<u64>, AcresError>;
+}
+
+impl<S: Store + ?Sized> Store for &mut S {
+    fn cursor(&self) -> Result<Option<Cursor>, AcresError> {
+        (**self).cursor()
+    }
+
+    fn save_cursor(&mut self, cursor: &Cursor) -> Result<(), AcresError> {
+        (**self).save_cursor(cursor)
+    }
+
+    fn upsert(&mut self, records: &[Value]) -> Result<(), AcresError> {
+        (**self).upsert(records)
+    }
+
+    fn delete(&mut self, ids: &[u64]) -> Result<(), AcresError> {
+        (**self).delete(ids)
+    }
+
+    fn ids(&self) -> Result<BTreeSet<u64>, AcresError> {
+        (**self).ids()
+    }
+}
+
+/// A [`Store`] that keeps one JSON file per record in a directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonStore {
+    dir: PathBuf,
+}
+
+impl JsonStore {
+    /// Opens (or creates) a store in `dir`.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, AcresError> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(dir.join("records"))
+            .with_context(|| format!("creating store at {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// Loads a record.
+    ///
+    /// A corrupt record is quarantined and treated as missing, so the next sync replaces it.
+    pub fn get(&self, id: u64) -> Result<Option<Value>, AcresError> {
+        read_or_quarantine(&self.record_path(id))
+    }
+
+    /// Iterates over every record.
+    pub fn records(&self) -> Result<impl Iterator<Item = Value> + '_, AcresError> {
+        let ids = self.ids()?;
+        Ok(ids.into_iter().filter_map(|id| self.get(id).ok().flatten()))
+    }
+
+    fn record_path(&self, id: u64) -> PathBuf {
+        self.dir.join("records").join(format!("{id}.json"))
+    }
+}
+
+/// Reads a JSON file, moving it aside if it's corrupt.
+fn read_or_quarantine<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, AcresError> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let data = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    match serde_json::from_slice(&data) {
+        Ok(value) => Ok(Some(value)),
+        Err(_) => {
+            cache::quarantine(path)?;
+            Ok(None)
+        }
+    }
+}
+
+impl Store for JsonStore {
+    fn cursor(&self) -> Result<Option<Cursor>, AcresError> {
+        // A corrupt cursor means starting over, which upserts make safe.
+        read_or_quarantine(&self.dir.join("cursor.json"))
+    }
+
+    fn save_cursor(&mut self, cursor: &Cursor) -> Result<(), AcresError> {
+        let data = serde_json::to_vec_pretty(cursor).context("serializing cursor")?;
+        let _lock = DirLock::exclusive(&self.dir)?;
+        cache::write_atomic(&self.dir.join("cursor.json"), &data)
+    }
+
+    fn upsert(&mut self, records: &[Value]) -> Result<(), AcresError> {
+        let _lock = DirLock::exclusive(&self.dir)?;
+        for record in records {
+            let id = record_id(record)?;
+            let data = serde_json::to_vec(record).context("serializing record")?;
+            cache::write_atomic(&self.record_path(id), &data)?;
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, ids: &[u64]) -> Result<(), AcresError> {
+        let _lock = DirLock::exclusive(&self.dir)?;
+        for id in ids {
+            let path = self.record_path(*id);
+            if path.is_file() {
+                std::fs::remove_file(&path).with_context(|| format!("removing record {id}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn ids(&self) -> Result<BTreeSet<u64>, AcresError> {
+        let entries =
+            std::fs::read_dir(self.dir.join("records")).context("listing store records")?;
+        Ok(entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                entry
+                    .file_name()
+                    .to_str()?
+                    .strip_suffix(".json")?
+                    .parse()
+                    .ok()
+            })
+            .collect())
+    }
+}
+
+/// What a sync run did.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// How many records were inserted or updated.
+    pub upserted: usize,
+    /// How many records were deleted.
+    pub deleted: usize,
+    /// Where the next run will start.
+    pub cursor: Cursor,
+}
+
+/// Keeps a [`Store`] in step with the collection.
+#[derive(Debug)]
+pub struct Syncer<S> {
+    api: Api,
+    store: S,
+    field: String,
+    fields: Vec<String>,
+    page_size: u32,
+    deletion_check_every: Duration,
+    max_deletion_percent: u8,
+}
+
+impl<S: Store> Syncer<S> {
+    /// Creates a syncer that uses `api` to fill `store`.
+    ///
+    /// Sync requests are never served from the response cache.
+    pub fn new(api: &Api, store: S) -> Self {
+        let mut api = api.clone();
+        api.use_cache = false;
+        Self {
+            api,
+            store,
+            field: "timestamp".to_string(),
+            fields: vec![],
+            page_size: 100,
+            deletion_check_every: Duration::from_secs(7 * 24 * 60 * 60),
+            max_deletion_percent: 5,
+        }
+    }
+
+    /// Sets the timestamp field to sync on.
+    ///
+    /// The default is `timestamp`, which changes whenever the API reindexes a record. Use
+    /// `last_updated` to follow changes in the source systems instead.
+    pub fn field(mut self, field: &str) -> Self {
+        self.field = field.to_string();
+        self
+    }
+
+    /// Sets the artwork fields to keep.
+    ///
+    /// The API's default fields are used if none are set.
+    pub fn fields(mut self, fields: Vec<String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Sets how many records to request at a time.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Sets how often to check for deleted records.
+    ///
+    /// The default is once a week.
+    pub fn deletion_check_every(mut self, every: Duration) -> Self {
+        self.deletion_check_every = every;
+        self
+    }
+
+    /// Sets the largest share of stored records, in percent, a deletion check may remove.
+    ///
+    /// A check that would remove more deletes nothing and fails instead, since that's more likely
+    /// a broken listing than the collection shrinking. The default is 5%.
+    pub fn max_deletion_percent(mut self, percent: u8) -> Self {
+        self.max_deletion_percent = percent;
+        self
+    }
+
+    /// Returns the store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Consumes the syncer, returning the store.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+
+    /// Syncs changes, and deletions if a check is due.
+    pub async fn run(&mut self) -> Result<Report, AcresError> {
+        let mut cursor = self.store.cursor()?.unwrap_or_default();
+        let upserted = self.sync_changes(&mut cursor).await?;
+        let due = match cursor.deletions_checked {
+            Some(checked) => {
+                Utc::now()
+                    .signed_duration_since(checked)
+                    .to_std()
+                    .unwrap_or_default()
+                    >= self.deletion_check_every
+            }
+            None => true,
+        };
+        let deleted = if due {
+            self.sync_deletions(&mut cursor).await?
+        } else {
+            0
+        };
+        Ok(Report {
+            upserted,
+            deleted,
+            cursor,
+        })
+    }
+
+    /// Syncs changes only.
+    ///
+    /// Changes are paged through by timestamp. Once a page fills up, the records changed at the
+    /// timestamp it ends on are paged through by id instead, so no request needs an offset and
+    /// any number of records can share a timestamp.
+    pub async fn sync_changes(&mut self, cursor: &mut Cursor) -> Result<usize, AcresError> {
+        let mut upserted = 0;
+        loop {
+            let since = cursor.updated_since.unwrap_or(DateTime::UNIX_EPOCH);
+            let since = since.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+            let by_id = cursor.after_id.is_some();
+            let (query, sort) = match cursor.after_id {
+                Some(after_id) => (
+                    json!({
+                        "bool": {
+                            "must": [
+                                { "range": { &self.field: { "gte": since, "lte": since } } },
+                                { "range": { "id": { "gt": after_id } } }
+                            ]
+                        }
+                    }),
+                    "id".to_string(),
+                ),
+                None => {
+                    let op = if cursor.updated_since.is_some() {
+                        "gt"
+                    } else {
+                        "gte"
+                    };
+                    (
+                        json!({ "range": { &self.field: { op: since } } }),
+                        self.field.clone(),
+                    )
+                }
+            };
+            let request = search::Builder::new()
+                .base_uri(self.api.base_uri())
+                .query(Some(query.to_string()))
+                .sort(Some(sort))
+                .size(Some(self.page_size))
+                .fields(self.request_fields())
+                .build()?;
+            let page = self.api.send(&request).await?;
+            let records = page.data();
+            let full = records.len() >= self.page_size as usize;
+            if by_id {
+                self.store.upsert(records)?;
+                upserted += records.len();
+                cursor.after_id = match records.last() {
+                    Some(last) if full => Some(record_id(last)?),
+                    _ => None,
+                };
+            } else {
+                let Some(last) = records.last() else {
+                    break;
+                };
+                let updated = record_timestamp(last, &self.field)?;
+                // The rest of the records at the last timestamp may be on the next page, so
+                // they're all left for paging by id.
+                let mut done = records.len();
+                if full {
+                    while done > 0 && record_timestamp(&records[done - 1], &self.field)? == updated
+                    {
+                        done -= 1;
+                    }
+                }
+                self.store.upsert(&records[..done])?;
+                upserted += done;
+                cursor.updated_since = Some(updated);
+                cursor.after_id = full.then_some(0);
+            }
+            self.store.save_cursor(cursor)?;
+            tracing::info!(upserted, ?cursor, "Synced page");
+            if !by_id && !full {
+                break;
+            }
+        }
+        Ok(upserted)
+    }
+
+    /// Checks for and removes records that are no longer in the collection.
+    pub async fn sync_deletions(&mut self, cursor: &mut Cursor) -> Result<usize, AcresError> {
+        let known = self.store.ids()?;
+        let mut live: BTreeSet<u64> = BTreeSet::new();
+        let mut total: u64;
+        let mut page = 1;
+        loop {
+            let request = artworks::Builder::new()
+                .base_uri(self.api.base_uri())
+                .fields(Some(vec!["id".to_string()]))
+                .limit(Some(self.page_size))
+                .page(Some(page))
+                .build()?;
+            let listing = self.api.send(&request).await?;
+            live.extend(listing.data.iter().map(|data| data.id));
+            total = listing.pagination.total;
+            if listing.data.is_empty() || u64::from(page) >= listing.pagination.total_pages {
+                break;
+            }
+            page += 1;
+        }
+        if live.len() as u64 != total {
+            return Err(AcresError::UnsafeDeletion(format!(
+                "listing has {} of {total} artworks",
+                live.len()
+            )));
+        }
+        let gone: Vec<u64> = known.difference(&live).copied().collect();
+        if gone.len() * 100 > known.len() * usize::from(self.max_deletion_percent) {
+            return Err(AcresError::UnsafeDeletion(format!(
+                "{} of {} records would go, more than {}%",
+                gone.len(),
+                known.len(),
+                self.max_deletion_percent
+            )));
+        }
+        self.store.delete(&gone)?;
+        cursor.deletions_checked = Some(Utc::now());
+        self.store.save_cursor(cursor)?;
+        tracing::info!(deleted = gone.len(), "Checked for deletions");
+        Ok(gone.len())
+    }
+
+    fn request_fields(&self) -> Option<Vec<String>> {
+        if self.fields.is_empty() {
+            return None;
+        }
+        let mut fields = self.fields.clone();
+        for required in ["id", self.field.as_str()] {
+            if !fields.iter().any(|field| field == required) {
+                fields.push(required.to_string());
+            }
+        }
+        Some(fields)
+    }
+}
+
+fn record_id(record: &Value) -> Result<u64, AcresError> {
+    record["id"]
+        .as_u64()
+        .ok_or_else(|| AcresError::Unexpected(anyhow!("record without an id: {record}")))
+}
+
+fn record_timestamp(record: &Value, field: &str) -> Result<DateTime<Utc>, AcresError> {
+    let value = record[field]
+        .as_str()
+        .ok_or_else(|| AcresError::Unexpected(anyhow!("record {} has no {field}", record["id"])))?;
+    Ok(DateTime::parse_from_rfc3339(value)
+        .with_context(|| format!("parsing {field} '{value}'"))?
+        .with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::page;
+
+    fn listing(ids: &[u64]) -> Value {
+        let data = ids.iter().map(|id| json!({"id": id})).collect();
+        page(data, ids.len() as u64, 1, 1)
+    }
+
+    /// Mounts a search response for requests whose query contains `query`.
+    async fn respond(mock_server: &wiremock::MockServer, query: &str, records: Value) {
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks/search"))
+            .and(wiremock::matchers::query_param_contains("query", query))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(json!({ "data": records })),
+            )
+            .expect(1)
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn upserts_changes_and_advances_cursor() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        respond(
+            &mock_server,
+            r#""gte":"1970-01-01T00:00:00Z""#,
+            json!([
+                {"id": 1, "title": "One", "timestamp": "2024-05-01T10:00:00-05:00"},
+                {"id": 2, "title": "Two", "timestamp": "2024-05-02T10:00:00-05:00"},
+            ]),
+        )
+        .await;
+        respond(
+            &mock_server,
+            r#""id":{"gt":0}"#,
+            json!([{"id": 2, "title": "Two", "timestamp": "2024-05-02T10:00:00-05:00"}]),
+        )
+        .await;
+        respond(
+            &mock_server,
+            r#""timestamp":{"gt":"2024-05-02T15:00:00Z"}"#,
+            json!([]),
+        )
+        .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .build();
+        let mut syncer = Syncer::new(&api, JsonStore::open(dir.path()).unwrap()).page_size(2);
+        let mut cursor = Cursor::default();
+
+        let upserted = syncer.sync_changes(&mut cursor).await.unwrap();
+
+        assert_eq!(upserted, 2);
+        assert_eq!(
+            cursor.updated_since,
+            Some("2024-05-02T15:00:00Z".parse().unwrap())
+        );
+        assert_eq!(cursor.after_id, None);
+        assert_eq!(syncer.store().get(2).unwrap().unwrap()["title"], "Two");
+        assert_eq!(syncer.store().cursor().unwrap(), Some(cursor));
+    }
+
+    #[tokio::test]
+    async fn pages_through_shared_timestamps_by_id() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        let at = |id: u64| json!({"id": id, "timestamp": "2024-05-01T10:00:00-05:00"});
+        respond(
+            &mock_server,
+            r#""gte":"1970-01-01T00:00:00Z""#,
+            json!([at(2), at(1)]),
+        )
+        .await;
+        respond(&mock_server, r#""id":{"gt":0}"#, json!([at(1), at(2)])).await;
+        respond(&mock_server, r#""id":{"gt":2}"#, json!([at(3)])).await;
+        respond(
+            &mock_server,
+            r#""timestamp":{"gt":"2024-05-01T15:00:00Z"}"#,
+            json!([]),
+        )
+        .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .build();
+        let mut syncer = Syncer::new(&api, JsonStore::open(dir.path()).unwrap()).page_size(2);
+        let mut cursor = Cursor::default();
+
+        let upserted = syncer.sync_changes(&mut cursor).await.unwrap();
+
+        assert_eq!(upserted, 3);
+        assert_eq!(syncer.store().ids().unwrap(), BTreeSet::from([1, 2, 3]));
+        let requests = mock_server.received_requests().await.unwrap();
+        let offsets = requests
+            .iter()
+            .filter(|request| request.url.query_pairs().any(|(name, _)| name == "from"));
+        assert_eq!(offsets.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn deletes_records_missing_from_collection() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+            .and(wiremock::matchers::query_param("fields", "id"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(listing(&[1, 2])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .build();
+        let mut store = JsonStore::open(dir.path()).unwrap();
+        store
+            .upsert(&[json!({"id": 1}), json!({"id": 2}), json!({"id": 3})])
+            .unwrap();
+        let mut syncer = Syncer::new(&api, store).max_deletion_percent(50);
+        let mut cursor = Cursor::default();
+
+        let deleted = syncer.sync_deletions(&mut cursor).await.unwrap();
+
+        assert_eq!(deleted, 1);
+        assert_eq!(syncer.store().ids().unwrap(), BTreeSet::from([1, 2]));
+        assert!(cursor.deletions_checked.is_some());
+    }
+
+    #[tokio::test]
+    async fn short_listing_deletes_nothing() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        let mut truncated = listing(&[1]);
+        truncated["pagination"]["total"] = json!(3);
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(truncated))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .build();
+        let mut store = JsonStore::open(dir.path()).unwrap();
+        store
+            .upsert(&[json!({"id": 1}), json!({"id": 2}), json!({"id": 3})])
+            .unwrap();
+        let mut syncer = Syncer::new(&api, store).max_deletion_percent(100);
+        let mut cursor = Cursor::default();
+
+        let result = syncer.sync_deletions(&mut cursor).await;
+
+        assert!(matches!(result, Err(AcresError::UnsafeDeletion(_))));
+        assert_eq!(syncer.store().ids().unwrap(), BTreeSet::from([1, 2, 3]));
+        assert!(cursor.deletions_checked.is_none());
+    }
+
+    #[tokio::test]
+    async fn deleting_too_much_deletes_nothing() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(listing(&[1])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder()
+            .base_uri(&format!("{}/api/v1", mock_server.uri()))
+            .build();
+        let mut store = JsonStore::open(dir.path()).unwrap();
+        store
+            .upsert(&[json!({"id": 1}), json!({"id": 2}), json!({"id": 3})])
+            .unwrap();
+        let mut syncer = Syncer::new(&api, store);
+
+        let result = syncer.sync_deletions(&mut Cursor::default()).await;
+
+        assert!(matches!(result, Err(AcresError::UnsafeDeletion(_))));
+        assert_eq!(syncer.store().ids().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn corrupt_records_are_quarantined() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mut store = JsonStore::open(dir.path()).unwrap();
+        store.upsert(&[json!({"id": 1}), json!({"id": 2})]).unwrap();
+        std::fs::write(store.record_path(2), b"{\"id\": 2, \"tit").unwrap();
+        std::fs::write(dir.path().join("cursor.json"), b"").unwrap();
+
+        assert_eq!(store.get(2).unwrap(), None);
+        assert_eq!(store.cursor().unwrap(), None);
+        assert_eq!(store.records().unwrap().count(), 1);
+        assert_eq!(store.ids().unwrap(), BTreeSet::from([1]));
+    }
+}
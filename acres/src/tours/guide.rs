@@ -0,0 +1,233 @@
+use std::collections::BTreeSet;
+
+use iiif::IiifError;
+
+use crate::{
+    AcresError, Api,
+    artworks::{self, Artworks, iiif_uri, request::artworks as artworks_request},
+    sounds::{self, Sounds, request::sounds as sounds_request},
+    tours::{Data, Tour, request::tour},
+};
+
+/// Fields retrieved for each artwork on a tour.
+const ARTWORK_FIELDS: [&str; 8] = [
+    "id",
+    "title",
+    "image_id",
+    "artist_display",
+    "date_display",
+    "medium_display",
+    "is_public_domain",
+    "thumbnail",
+];
+
+/// Most records the API returns per page.
+const MAX_LIMIT: usize = 100;
+
+/// A tour expanded into the artworks it visits, in order.
+///
+/// ```no_run
+/// # use anyhow::Result;
+/// use acres::tours::Guide;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let api = acres::Api::new();
+/// let guide = Guide::fetch(&api, 1023).await?;
+/// for (number, stop) in guide.stops.iter().enumerate() {
+///     println!("{}. {}", number + 1, stop.artwork.title);
+///     if let Some(uri) = &stop.iiif_uri {
+///         println!("   {uri}");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Guide {
+    /// The tour.
+    pub tour: Data,
+    /// The stops, in the order they're visited.
+    pub stops: Vec<GuideStop>,
+}
+
+/// A stop on a [`Guide`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuideStop {
+    /// The artwork at this stop.
+    pub artwork: artworks::Data,
+    /// The IIIF URI of the artwork's image, if it has one.
+    pub iiif_uri: Option<iiif::Uri>,
+    /// The recording played at this stop, with its transcript.
+    pub sound: Option<sounds::Data>,
+}
+
+impl Guide {
+    /// Looks up a tour and expands it.
+    pub async fn fetch(api: &Api, id: u32) -> Result<Guide, AcresError> {
+        let tour: Tour = api.send(&tour::Request::new(api.base_uri(), id)).await?;
+        Guide::expand(api, tour.data).await
+    }
+
+    /// Looks up the artworks and recordings on a tour.
+    pub async fn expand(api: &Api, tour: Data) -> Result<Guide, AcresError> {
+        let (artwork_ids, sound_ids) = stop_ids(&tour);
+        let mut artworks = Artworks::default();
+        for ids in artwork_ids.chunks(MAX_LIMIT) {
+            let request = artworks_request::Builder::new()
+                .base_uri(api.base_uri())
+                .ids(Some(ids.to_vec()))
+                .limit(Some(ids.len() as u32))
+                .fields(Some(ARTWORK_FIELDS.map(String::from).to_vec()))
+                .build()?;
+            let page = api.send(&request).await?;
+            artworks.config = page.config;
+            artworks.data.extend(page.data);
+        }
+        let mut sounds = Sounds::default();
+        for ids in sound_ids.chunks(MAX_LIMIT) {
+            let request = sounds_request::Builder::new()
+                .base_uri(api.base_uri())
+                .ids(Some(ids.to_vec()))
+                .limit(Some(ids.len() as u32))
+                .build()?;
+            sounds.data.extend(api.send(&request).await?.data);
+        }
+        Guide::assemble(tour, &artworks, &sounds)
+    }
+
+    /// Puts a tour's stops together from artworks and recordings already at hand.
+    ///
+    /// Stops whose artwork isn't among `artworks` are left out.
+    pub fn assemble(tour: Data, artworks: &Artworks, sounds: &Sounds) -> Result<Guide, AcresError> {
+        let iiif_url = match artworks.config.iiif_url.as_str() {
+            "" => None,
+            url => Some(
+                url::Url::parse(url)
+                    .map_err(IiifError::InvalidUri)
+                    .map_err(AcresError::Iiif)?,
+            ),
+        };
+        let mut stops = vec![];
+        for stop in tour.ordered_stops() {
+            let Some(artwork) = artworks
+                .data
+                .iter()
+                .find(|artwork| Some(artwork.id) == stop.artwork_id)
+            else {
+                continue;
+            };
+            let iiif_uri = match (&iiif_url, &artwork.image_id) {
+                (Some(iiif_url), Some(image_id)) => Some(iiif_uri(iiif_url, image_id)?),
+                _ => None,
+            };
+            let sound = sounds
+                .data
+                .iter()
+                .find(|sound| Some(sound.id) == stop.sound_id)
+                .cloned();
+            stops.push(GuideStop {
+                artwork: artwork.clone(),
+                iiif_uri,
+                sound,
+            });
+        }
+        Ok(Guide { tour, stops })
+    }
+}
+
+/// Returns the distinct artwork and recording ids on a tour.
+fn stop_ids(tour: &Data) -> (Vec<u32>, Vec<u32>) {
+    let ids = |id: fn(&super::Stop) -> Option<u64>| {
+        tour.stops
+            .iter()
+            .filter_map(id)
+            .filter_map(|id| u32::try_from(id).ok())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    };
+    (ids(|stop| stop.artwork_id), ids(|stop| stop.sound_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::fixtures::{envelope, page};
+
+    #[tokio::test]
+    async fn expands_tour_into_ordered_artworks() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/tours/1023"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(envelope(json!({
+                    "id": 1023,
+                    "title": "Impressionism",
+                    "tour_stops": [
+                        { "artwork_id": 16568, "mobile_sound_id": 228, "weight": 2 },
+                        { "artwork_id": 27992, "mobile_sound_id": 227, "weight": 1 },
+                        { "artwork_id": 404, "mobile_sound_id": null, "weight": 3 }
+                    ]
+                }))),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artworks"))
+            .and(wiremock::matchers::query_param("ids", "404,16568,27992"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(page(
+                json!([
+                    { "id": 16568, "title": "Water Lilies", "image_id": "3c27b499" },
+                    { "id": 27992, "title": "A Sunday on La Grande Jatte — 1884", "image_id": null }
+                ]),
+                2,
+                1,
+                1,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/mobile-sounds"))
+            .and(wiremock::matchers::query_param("ids", "227,228"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(page(json!([
+                { "id": 227, "title": "La Grande Jatte", "transcript": "Seurat spent two years..." },
+                { "id": 228, "title": "Water Lilies", "transcript": "Monet painted..." }
+            ]), 2, 1, 1)))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+
+        let guide = Guide::fetch(&api, 1023).await.unwrap();
+
+        let titles: Vec<_> = guide
+            .stops
+            .iter()
+            .map(|stop| stop.artwork.title.as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            vec!["A Sunday on La Grande Jatte — 1884", "Water Lilies"]
+        );
+        assert_eq!(guide.stops[0].iiif_uri, None);
+        assert_eq!(
+            guide.stops[1].iiif_uri.as_ref().unwrap().to_string(),
+            "https://www.artic.edu/iiif/2/3c27b499"
+        );
+        assert_eq!(
+            guide.stops[1].sound.as_ref().unwrap().transcript.as_deref(),
+            Some("Monet painted...")
+        );
+    }
+
+    #[test]
+    fn tours_without_stops_are_empty() {
+        let guide =
+            Guide::assemble(Data::default(), &Artworks::default(), &Sounds::default()).unwrap();
+
+        assert!(guide.stops.is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+//! Modules and types for working with the [Tours Collection].
+//!
+//! A tour is an ordered set of stops, each pairing an artwork with an audio recording from the
+//! [`sounds`] collection. [`Guide`] expands a tour into the artworks themselves, with their IIIF
+//! URIs and transcripts, ready to walk through.
+//!
+//! [Tours Collection]: https://api.artic.edu/docs/#tours
+//! [`sounds`]: ../sounds/index.html
+
+mod guide;
+mod tour;
+
+pub use guide::{Guide, GuideStop};
+pub use tour::{Data, Stop, Tour, Tours};
+
+/// Modules for requesting items from the [Tours Collection].
+///
+/// [Tours Collection]: https://api.artic.edu/docs/#tours
+pub mod request {
+    /// A [`GET /tours/{id}`] request.
+    ///
+    /// [`GET /tours/{id}`]: https://api.artic.edu/docs/#get-tours-id
+    pub mod tour {
+        pub use crate::tours::tour::Request;
+    }
+
+    /// A [`GET /tours`] request.
+    ///
+    /// [`GET /tours`]: https://api.artic.edu/docs/#get-tours
+    pub mod tours {
+        pub use crate::tours::tour::{Builder, ListRequest as Request};
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AcresError,
+    artworks::{CollectionQueryParams, Config, Info, Pagination},
+    endpoint::json_response,
+};
+
+/// A tour, as returned by [`GET /tours/{id}`].
+///
+/// [`GET /tours/{id}`]: https://api.artic.edu/docs/#get-tours-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Tour {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Data,
+    /// Info.
+    pub info: Info,
+}
+
+impl Display for Tour {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} ({})", self.data.title, self.data.id))
+    }
+}
+
+json_response!(Tour, Tours);
+
+/// A page of tours, as returned by [`GET /tours`].
+///
+/// [`GET /tours`]: https://api.artic.edu/docs/#get-tours
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Tours {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Vec<Data>,
+    /// Info.
+    pub info: Info,
+    /// Pagination.
+    pub pagination: Pagination,
+}
+
+/// Tour record.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Data {
+    /// ID.
+    pub id: u64,
+    /// Title.
+    #[serde(default)]
+    pub title: String,
+    /// URL of the tour's cover image.
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Description, as HTML.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Introduction, as HTML.
+    #[serde(default)]
+    pub intro: Option<String>,
+    /// URL of the introduction's audio file.
+    #[serde(default)]
+    pub intro_link: Option<String>,
+    /// What's said in the introduction.
+    #[serde(default)]
+    pub intro_transcript: Option<String>,
+    /// Titles of the artworks on the tour.
+    #[serde(default)]
+    pub artwork_titles: Vec<String>,
+    /// Names of the artists on the tour.
+    #[serde(default)]
+    pub artist_titles: Vec<String>,
+    /// Stops on the tour.
+    #[serde(default, rename = "tour_stops")]
+    pub stops: Vec<Stop>,
+}
+
+impl Data {
+    /// Returns the stops in the order they're visited.
+    ///
+    /// Stops are ordered by weight, and otherwise kept in the order they were listed.
+    ///
+    /// ```rust
+    /// use acres::tours::{Data, Stop};
+    ///
+    /// let tour = Data {
+    ///     stops: vec![
+    ///         Stop { artwork_id: Some(2), weight: Some(2), ..Default::default() },
+    ///         Stop { artwork_id: Some(1), weight: Some(1), ..Default::default() },
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    /// let order: Vec<_> = tour.ordered_stops().iter().map(|stop| stop.artwork_id).collect();
+    /// assert_eq!(order, vec![Some(1), Some(2)]);
+    /// ```
+    pub fn ordered_stops(&self) -> Vec<&Stop> {
+        let mut stops: Vec<&Stop> = self.stops.iter().collect();
+        stops.sort_by_key(|stop| stop.weight.unwrap_or(i64::MAX));
+        stops
+    }
+}
+
+/// A stop on a tour: an artwork and the recording played there.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Stop {
+    /// The artwork at this stop.
+    #[serde(default)]
+    pub artwork_id: Option<u64>,
+    /// The recording played at this stop.
+    #[serde(default, rename = "mobile_sound_id")]
+    pub sound_id: Option<u64>,
+    /// Where the stop comes in the tour, lowest first.
+    #[serde(default)]
+    pub weight: Option<i64>,
+}
+
+/// A [`GET /tours/{id}`] request.
+///
+/// ```rust
+/// use acres::{Api, tours::request::tour::Request};
+///
+/// let request = Request::new(Api::new().base_uri(), 1023);
+/// ```
+///
+/// [`GET /tours/{id}`]: https://api.artic.edu/docs/#get-tours-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Request {
+    base_uri: String,
+    id: u32,
+}
+
+impl Display for Request {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}/tours/{}", self.base_uri, self.id))
+    }
+}
+
+impl Request {
+    /// Constructs a new tour request.
+    pub fn new(base_uri: String, id: u32) -> Self {
+        Self { base_uri, id }
+    }
+}
+
+/// A [`GET /tours`] request.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::{Api, tours::request::tours};
+///
+/// # fn main() -> Result<()> {
+/// let request = tours::Builder::new()
+///     .base_uri(Api::new().base_uri())
+///     .limit(Some(5))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`GET /tours`]: https://api.artic.edu/docs/#get-tours
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ListRequest(String);
+
+impl Display for ListRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+/// A [`GET /tours`] request builder.
+///
+/// [`GET /tours`]: https://api.artic.edu/docs/#get-tours
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    base_uri: String,
+    ids: Option<Vec<u32>>,
+    limit: Option<u32>,
+    page: Option<u32>,
+    fields: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Sets the base URI.
+    pub fn base_uri(mut self, base_uri: String) -> Self {
+        self.base_uri = base_uri;
+        self
+    }
+
+    /// Sets the tour ids to retrieve.
+    pub fn ids(mut self, ids: Option<Vec<u32>>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Sets limit on number of tours to return per page.
+    pub fn limit(mut self, limit: Option<u32>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets page number to return.
+    pub fn page(mut self, page: Option<u32>) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Sets the tour fields to retrieve.
+    pub fn fields(mut self, fields: Option<Vec<String>>) -> Self {
+        if let Some(fields) = fields {
+            self.fields = fields;
+        }
+        self
+    }
+
+    /// Builds request for the tours collection.
+    pub fn build(&self) -> Result<ListRequest, AcresError> {
+        let query_params = CollectionQueryParams {
+            ids: self.ids.clone(),
+            limit: self.limit,
+            page: self.page,
+            fields: self.fields.clone(),
+            include: vec![],
+        };
+        Ok(ListRequest(format!(
+            "{}/tours{}",
+            self.base_uri, query_params
+        )))
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AcresError,
+    artworks::{CollectionQueryParams, Config, Info, Pagination},
+    endpoint::json_response,
+};
+
+/// An artwork type, as returned by [`GET /artwork-types/{id}`].
+///
+/// [`GET /artwork-types/{id}`]: https://api.artic.edu/docs/#get-artwork-types-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ArtworkType {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Data,
+    /// Info.
+    pub info: Info,
+}
+
+impl Display for ArtworkType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} ({})", self.data.title, self.data.id))
+    }
+}
+
+json_response!(ArtworkType, ArtworkTypes);
+
+/// A page of artwork types, as returned by [`GET /artwork-types`].
+///
+/// [`GET /artwork-types`]: https://api.artic.edu/docs/#get-artwork-types
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ArtworkTypes {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Vec<Data>,
+    /// Info.
+    pub info: Info,
+    /// Pagination.
+    pub pagination: Pagination,
+}
+
+/// Artwork type record.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Data {
+    /// ID.
+    pub id: u64,
+    /// Label, e.g. `Painting`.
+    #[serde(default)]
+    pub title: String,
+}
+
+/// A [`GET /artwork-types/{id}`] request.
+///
+/// ```rust
+/// use acres::{Api, vocabulary::request::artwork_type::Request};
+///
+/// let request = Request::new(Api::new().base_uri(), 1);
+/// ```
+///
+/// [`GET /artwork-types/{id}`]: https://api.artic.edu/docs/#get-artwork-types-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Request {
+    base_uri: String,
+    id: u32,
+}
+
+impl Display for Request {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}/artwork-types/{}", self.base_uri, self.id))
+    }
+}
+
+impl Request {
+    /// Constructs a new artwork type request.
+    pub fn new(base_uri: String, id: u32) -> Self {
+        Self { base_uri, id }
+    }
+}
+
+/// A [`GET /artwork-types`] request.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::{Api, vocabulary::request::artwork_types};
+///
+/// # fn main() -> Result<()> {
+/// let request = artwork_types::Builder::new()
+///     .base_uri(Api::new().base_uri())
+///     .ids(Some(vec![1, 18]))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`GET /artwork-types`]: https://api.artic.edu/docs/#get-artwork-types
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ListRequest(String);
+
+impl Display for ListRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+/// A [`GET /artwork-types`] request builder.
+///
+/// [`GET /artwork-types`]: https://api.artic.edu/docs/#get-artwork-types
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    base_uri: String,
+    ids: Option<Vec<u32>>,
+    limit: Option<u32>,
+    page: Option<u32>,
+    fields: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Sets the base URI.
+    pub fn base_uri(mut self, base_uri: String) -> Self {
+        self.base_uri = base_uri;
+        self
+    }
+
+    /// Sets the artwork type ids to retrieve.
+    pub fn ids(mut self, ids: Option<Vec<u32>>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Sets limit on number of artwork types to return per page.
+    pub fn limit(mut self, limit: Option<u32>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets page number to return.
+    pub fn page(mut self, page: Option<u32>) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Sets the artwork type fields to retrieve.
+    pub fn fields(mut self, fields: Option<Vec<String>>) -> Self {
+        if let Some(fields) = fields {
+            self.fields = fields;
+        }
+        self
+    }
+
+    /// Builds request for the artwork types collection.
+    pub fn build(&self) -> Result<ListRequest, AcresError> {
+        let query_params = CollectionQueryParams {
+            ids: self.ids.clone(),
+            limit: self.limit,
+            page: self.page,
+            fields: self.fields.clone(),
+            include: vec![],
+        };
+        Ok(ListRequest(format!(
+            "{}/artwork-types{}",
+            self.base_uri, query_params
+        )))
+    }
+}
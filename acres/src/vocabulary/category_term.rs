@@ -0,0 +1,191 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AcresError,
+    artworks::{Config, Info, Pagination},
+    endpoint::json_response,
+};
+
+/// A category term, as returned by [`GET /category-terms/{id}`].
+///
+/// [`GET /category-terms/{id}`]: https://api.artic.edu/docs/#get-category-terms-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CategoryTerm {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Data,
+    /// Info.
+    pub info: Info,
+}
+
+impl Display for CategoryTerm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} ({})", self.data.title, self.data.id))
+    }
+}
+
+json_response!(CategoryTerm, CategoryTerms);
+
+/// A page of category terms, as returned by [`GET /category-terms`].
+///
+/// [`GET /category-terms`]: https://api.artic.edu/docs/#get-category-terms
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CategoryTerms {
+    /// Config.
+    pub config: Config,
+    /// Data.
+    pub data: Vec<Data>,
+    /// Info.
+    pub info: Info,
+    /// Pagination.
+    pub pagination: Pagination,
+}
+
+/// Category term record.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Data {
+    /// ID, e.g. `TM-7543` or `PC-10`.
+    pub id: String,
+    /// Label.
+    #[serde(default)]
+    pub title: String,
+    /// What kind of term this is, e.g. `style` or `department`.
+    #[serde(default)]
+    pub subtype: Option<String>,
+    /// The broader term this one falls under.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+/// A [`GET /category-terms/{id}`] request.
+///
+/// ```rust
+/// use acres::{Api, vocabulary::request::category_term::Request};
+///
+/// let request = Request::new(Api::new().base_uri(), "TM-7543");
+/// ```
+///
+/// [`GET /category-terms/{id}`]: https://api.artic.edu/docs/#get-category-terms-id
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Request {
+    base_uri: String,
+    id: String,
+}
+
+impl Display for Request {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}/category-terms/{}", self.base_uri, self.id))
+    }
+}
+
+impl Request {
+    /// Constructs a new category term request.
+    pub fn new(base_uri: String, id: impl Into<String>) -> Self {
+        Self {
+            base_uri,
+            id: id.into(),
+        }
+    }
+}
+
+/// A [`GET /category-terms`] request.
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use acres::{Api, vocabulary::request::category_terms};
+///
+/// # fn main() -> Result<()> {
+/// let request = category_terms::Builder::new()
+///     .base_uri(Api::new().base_uri())
+///     .ids(Some(vec!["TM-7543".into(), "PC-10".into()]))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`GET /category-terms`]: https://api.artic.edu/docs/#get-category-terms
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ListRequest(String);
+
+impl Display for ListRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+/// A [`GET /category-terms`] request builder.
+///
+/// [`GET /category-terms`]: https://api.artic.edu/docs/#get-category-terms
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Builder {
+    base_uri: String,
+    ids: Option<Vec<String>>,
+    limit: Option<u32>,
+    page: Option<u32>,
+    fields: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Sets the base URI.
+    pub fn base_uri(mut self, base_uri: String) -> Self {
+        self.base_uri = base_uri;
+        self
+    }
+
+    /// Sets the term ids to retrieve.
+    pub fn ids(mut self, ids: Option<Vec<String>>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Sets limit on number of terms to return per page.
+    pub fn limit(mut self, limit: Option<u32>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets page number to return.
+    pub fn page(mut self, page: Option<u32>) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Sets the term fields to retrieve.
+    pub fn fields(mut self, fields: Option<Vec<String>>) -> Self {
+        if let Some(fields) = fields {
+            self.fields = fields;
+        }
+        self
+    }
+
+    /// Builds request for the category terms collection.
+    pub fn build(&self) -> Result<ListRequest, AcresError> {
+        let mut params: Vec<String> = vec![];
+        if let Some(ids) = &self.ids {
+            params.push(format!("ids={}", ids.join(",")));
+        }
+        if let Some(limit) = &self.limit {
+            params.push(format!("limit={limit}"));
+        }
+        if let Some(page) = &self.page {
+            params.push(format!("page={page}"));
+        }
+        if !self.fields.is_empty() {
+            params.push(format!("fields={}", self.fields.join(",")));
+        }
+        let mut request = format!("{}/category-terms", self.base_uri);
+        if !params.is_empty() {
+            request.push('?');
+            request.push_str(&params.join("&"));
+        }
+        Ok(ListRequest(request))
+    }
+}
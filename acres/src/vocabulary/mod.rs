@@ -0,0 +1,688 @@
+//! Controlled vocabularies: departments, category terms and artwork types.
+//!
+//! Artworks refer to their department, style, classifications, subjects and so on by id, such as
+//! `style_id: "TM-7543"`. A [`Vocabulary`] knows the label for each of those ids, so you can show
+//! "Impressionism" instead, and looks up the id for a label, so you can build a search filter
+//! without hard-coding ids.
+//!
+//! Labels come from [`GET /category-terms`], where departments are the terms with a `department`
+//! subtype, and [`GET /artwork-types`]. There are thousands of terms, so [`Vocabulary::cached`]
+//! keeps them in the cache directory once fetched.
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use acres::vocabulary::{Kind, Term, Vocabulary};
+//! use serde_json::json;
+//!
+//! # fn main() -> Result<()> {
+//! let mut vocabulary = Vocabulary::new();
+//! vocabulary.insert(Term::new(Kind::Style, "TM-7543", "Impressionism"));
+//!
+//! assert_eq!(vocabulary.label(Kind::Style, "TM-7543"), Some("Impressionism"));
+//! assert_eq!(vocabulary.id_of(Kind::Style, "impressionism"), Some("TM-7543"));
+//! assert_eq!(
+//!     vocabulary.filter(Kind::Style, "Impressionism"),
+//!     Some(json!({ "term": { "style_ids": "TM-7543" } }))
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`GET /category-terms`]: https://api.artic.edu/docs/#get-category-terms
+//! [`GET /artwork-types`]: https://api.artic.edu/docs/#get-artwork-types
+
+mod artwork_type;
+mod category_term;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use anyhow::{Context, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{AcresError, Api, artworks, cache};
+
+pub use artwork_type::{ArtworkType, ArtworkTypes, Data as ArtworkTypeData};
+pub use category_term::{CategoryTerm, CategoryTerms, Data as CategoryTermData};
+
+/// Modules for requesting controlled vocabularies.
+pub mod request {
+    /// A [`GET /category-terms/{id}`] request.
+    ///
+    /// [`GET /category-terms/{id}`]: https://api.artic.edu/docs/#get-category-terms-id
+    pub mod category_term {
+        pub use crate::vocabulary::category_term::Request;
+    }
+
+    /// A [`GET /category-terms`] request.
+    ///
+    /// [`GET /category-terms`]: https://api.artic.edu/docs/#get-category-terms
+    pub mod category_terms {
+        pub use crate::vocabulary::category_term::{Builder, ListRequest as Request};
+    }
+
+    /// A [`GET /artwork-types/{id}`] request.
+    ///
+    /// [`GET /artwork-types/{id}`]: https://api.artic.edu/docs/#get-artwork-types-id
+    pub mod artwork_type {
+        pub use crate::vocabulary::artwork_type::Request;
+    }
+
+    /// A [`GET /artwork-types`] request.
+    ///
+    /// [`GET /artwork-types`]: https://api.artic.edu/docs/#get-artwork-types
+    pub mod artwork_types {
+        pub use crate::vocabulary::artwork_type::{Builder, ListRequest as Request};
+    }
+}
+
+/// Name of the file the vocabulary is cached in.
+const CACHE_FILE: &str = "vocabulary.json";
+
+/// Most records the API returns per page.
+const PAGE_LIMIT: u32 = 100;
+
+/// Most records the API pages through, however they're split into pages.
+const RESULT_WINDOW: u64 = 10_000;
+
+/// Fields retrieved for each category term.
+const TERM_FIELDS: [&str; 4] = ["id", "title", "subtype", "parent_id"];
+
+/// What a term describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    /// The department that looks after an artwork.
+    Department,
+    /// The kind of object, e.g. "Painting" or "Print".
+    ArtworkType,
+    /// A classification, e.g. "oil on canvas".
+    Classification,
+    /// A style or movement, e.g. "Impressionism".
+    Style,
+    /// What an artwork depicts or is about.
+    Subject,
+    /// What an artwork is made of.
+    Material,
+    /// How an artwork was made.
+    Technique,
+}
+
+impl Kind {
+    /// Returns the artwork field that holds ids of this kind.
+    pub fn field(&self) -> &'static str {
+        match self {
+            Kind::Department => "department_id",
+            Kind::ArtworkType => "artwork_type_id",
+            Kind::Classification => "classification_ids",
+            Kind::Style => "style_ids",
+            Kind::Subject => "subject_ids",
+            Kind::Material => "material_ids",
+            Kind::Technique => "technique_ids",
+        }
+    }
+
+    /// Returns the kind of category term with a subtype, if it's one we know.
+    fn of_subtype(subtype: &str) -> Option<Kind> {
+        match subtype {
+            "department" => Some(Kind::Department),
+            "classification" => Some(Kind::Classification),
+            "style" => Some(Kind::Style),
+            "subject" => Some(Kind::Subject),
+            "material" => Some(Kind::Material),
+            "technique" => Some(Kind::Technique),
+            _ => None,
+        }
+    }
+}
+
+/// A labelled id.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Term {
+    /// What the term describes.
+    pub kind: Kind,
+    /// ID, e.g. `TM-7543` or, for artwork types, `1`.
+    pub id: String,
+    /// Label.
+    pub title: String,
+    /// The broader term this one falls under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+}
+
+impl Term {
+    /// Creates a term.
+    pub fn new(kind: Kind, id: impl Into<String>, title: impl Into<String>) -> Self {
+        Term {
+            kind,
+            id: id.into(),
+            title: title.into(),
+            parent_id: None,
+        }
+    }
+}
+
+impl TryFrom<CategoryTermData> for Term {
+    type Error = AcresError;
+
+    fn try_from(data: CategoryTermData) -> Result<Self, Self::Error> {
+        let kind = data
+            .subtype
+            .as_deref()
+            .and_then(Kind::of_subtype)
+            .ok_or_else(|| {
+                AcresError::Unexpected(anyhow::anyhow!(
+                    "category term {} has an unknown subtype {:?}",
+                    data.id,
+                    data.subtype
+                ))
+            })?;
+        Ok(Term {
+            kind,
+            id: data.id,
+            title: data.title,
+            parent_id: data.parent_id,
+        })
+    }
+}
+
+impl From<ArtworkTypeData> for Term {
+    fn from(data: ArtworkTypeData) -> Self {
+        Term::new(Kind::ArtworkType, data.id.to_string(), data.title)
+    }
+}
+
+/// The labels for an artwork's ids.
+///
+/// Ids that aren't in the vocabulary are left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Labels {
+    /// Department.
+    pub department: Option<String>,
+    /// Artwork type.
+    pub artwork_type: Option<String>,
+    /// Styles, preferred first.
+    pub styles: Vec<String>,
+    /// Classifications, preferred first.
+    pub classifications: Vec<String>,
+    /// Subjects.
+    pub subjects: Vec<String>,
+    /// Materials.
+    pub materials: Vec<String>,
+    /// Techniques.
+    pub techniques: Vec<String>,
+}
+
+/// Labels for departments, category terms and artwork types.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vocabulary {
+    terms: BTreeMap<Kind, BTreeMap<String, Term>>,
+}
+
+impl Vocabulary {
+    /// Creates an empty vocabulary.
+    pub fn new() -> Self {
+        Vocabulary::default()
+    }
+
+    /// Adds a term, replacing any with the same kind and id.
+    pub fn insert(&mut self, term: Term) {
+        self.terms
+            .entry(term.kind)
+            .or_default()
+            .insert(term.id.clone(), term);
+    }
+
+    /// Returns how many terms there are.
+    pub fn len(&self) -> usize {
+        self.terms.values().map(BTreeMap::len).sum()
+    }
+
+    /// Returns whether there are no terms.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the terms of a kind, ordered by id.
+    pub fn terms(&self, kind: Kind) -> impl Iterator<Item = &Term> {
+        self.terms.get(&kind).into_iter().flat_map(BTreeMap::values)
+    }
+
+    /// Returns the departments.
+    pub fn departments(&self) -> impl Iterator<Item = &Term> {
+        self.terms(Kind::Department)
+    }
+
+    /// Returns the term with an id.
+    pub fn term(&self, kind: Kind, id: &str) -> Option<&Term> {
+        self.terms.get(&kind)?.get(id)
+    }
+
+    /// Returns the label for an id.
+    pub fn label(&self, kind: Kind, id: &str) -> Option<&str> {
+        self.term(kind, id).map(|term| term.title.as_str())
+    }
+
+    /// Returns the id for a label.
+    ///
+    /// Labels are compared ignoring case and surrounding space. If several terms share a label,
+    /// one with exactly that spelling is preferred.
+    pub fn id_of(&self, kind: Kind, label: &str) -> Option<&str> {
+        let label = label.trim();
+        let mut matches = self
+            .terms(kind)
+            .filter(|term| term.title.trim().eq_ignore_ascii_case(label));
+        let first = matches.next()?;
+        let exact = std::iter::once(first)
+            .chain(matches)
+            .find(|term| term.title.trim() == label);
+        Some(exact.unwrap_or(first).id.as_str())
+    }
+
+    /// Returns a search filter for artworks with a label, if the label is known.
+    pub fn filter(&self, kind: Kind, label: &str) -> Option<Value> {
+        let id = self.id_of(kind, label)?;
+        let value = match kind {
+            Kind::ArtworkType => json!(id.parse::<u64>().ok()?),
+            _ => json!(id),
+        };
+        Some(json!({ "term": { kind.field(): value } }))
+    }
+
+    /// Returns the labels for an artwork's ids.
+    pub fn labels(&self, artwork: &artworks::Data) -> Labels {
+        let labels = |kind: Kind, ids: Vec<&String>| -> Vec<String> {
+            ids.into_iter()
+                .filter_map(|id| self.label(kind, id))
+                .map(str::to_string)
+                .collect()
+        };
+        Labels {
+            department: artwork
+                .department_id
+                .as_deref()
+                .and_then(|id| self.label(Kind::Department, id))
+                .map(str::to_string),
+            artwork_type: artwork
+                .artwork_type_id
+                .and_then(|id| self.label(Kind::ArtworkType, &id.to_string()))
+                .map(str::to_string),
+            styles: labels(
+                Kind::Style,
+                preferred_first(artwork.style_id.as_ref(), &artwork.style_ids),
+            ),
+            classifications: labels(
+                Kind::Classification,
+                preferred_first(
+                    artwork.classification_id.as_ref(),
+                    &artwork.classification_ids,
+                ),
+            ),
+            subjects: labels(Kind::Subject, artwork.subject_ids.iter().collect()),
+            materials: labels(Kind::Material, artwork.material_ids.iter().collect()),
+            techniques: labels(Kind::Technique, artwork.technique_ids.iter().collect()),
+        }
+    }
+
+    /// Fetches every category term and artwork type.
+    ///
+    /// Fails rather than return a partial vocabulary if there are more of either than the API
+    /// pages through.
+    pub async fn fetch(api: &Api) -> Result<Vocabulary, AcresError> {
+        let mut vocabulary = Vocabulary::new();
+        let mut page = 1;
+        loop {
+            let request = category_term::Builder::new()
+                .base_uri(api.base_uri())
+                .limit(Some(PAGE_LIMIT))
+                .page(Some(page))
+                .fields(Some(TERM_FIELDS.map(String::from).to_vec()))
+                .build()?;
+            let terms = api.send(&request).await?;
+            let done = terms.data.is_empty()
+                || terms.pagination.current_page >= terms.pagination.total_pages;
+            vocabulary.extend_terms(terms.data);
+            if done {
+                break;
+            }
+            page = next_page(page, "category terms", terms.pagination.total)?;
+        }
+        let mut page = 1;
+        loop {
+            let request = artwork_type::Builder::new()
+                .base_uri(api.base_uri())
+                .limit(Some(PAGE_LIMIT))
+                .page(Some(page))
+                .build()?;
+            let types = api.send(&request).await?;
+            let done = types.data.is_empty()
+                || types.pagination.current_page >= types.pagination.total_pages;
+            types
+                .data
+                .into_iter()
+                .for_each(|data| vocabulary.insert(data.into()));
+            if done {
+                break;
+            }
+            page = next_page(page, "artwork types", types.pagination.total)?;
+        }
+        Ok(vocabulary)
+    }
+
+    /// Fetches the terms an artwork refers to that aren't known yet.
+    pub async fn fetch_missing(
+        &mut self,
+        api: &Api,
+        artwork: &artworks::Data,
+    ) -> Result<(), AcresError> {
+        let mut missing = BTreeSet::new();
+        let mut check = |kind: Kind, id: &String| {
+            if self.term(kind, id).is_none() {
+                missing.insert(id.clone());
+            }
+        };
+        artwork
+            .department_id
+            .iter()
+            .for_each(|id| check(Kind::Department, id));
+        for (kind, ids) in [
+            (
+                Kind::Style,
+                preferred_first(artwork.style_id.as_ref(), &artwork.style_ids),
+            ),
+            (
+                Kind::Classification,
+                preferred_first(
+                    artwork.classification_id.as_ref(),
+                    &artwork.classification_ids,
+                ),
+            ),
+            (Kind::Subject, artwork.subject_ids.iter().collect()),
+            (Kind::Material, artwork.material_ids.iter().collect()),
+            (Kind::Technique, artwork.technique_ids.iter().collect()),
+        ] {
+            ids.into_iter().for_each(|id| check(kind, id));
+        }
+        let missing: Vec<String> = missing.into_iter().collect();
+        for ids in missing.chunks(PAGE_LIMIT as usize) {
+            let request = category_term::Builder::new()
+                .base_uri(api.base_uri())
+                .ids(Some(ids.to_vec()))
+                .limit(Some(ids.len() as u32))
+                .fields(Some(TERM_FIELDS.map(String::from).to_vec()))
+                .build()?;
+            self.extend_terms(api.send(&request).await?.data);
+        }
+        if let Some(id) = artwork.artwork_type_id
+            && self.term(Kind::ArtworkType, &id.to_string()).is_none()
+        {
+            let request = artwork_type::Request::new(
+                api.base_uri(),
+                u32::try_from(id).context("artwork type id")?,
+            );
+            self.insert(api.send(&request).await?.data.into());
+        }
+        Ok(())
+    }
+
+    /// Loads the vocabulary from the API client's cache directory, or fetches and caches it.
+    ///
+    /// When caching is off, this always fetches. Delete the cached file to pick up new terms.
+    pub async fn cached(api: &Api) -> Result<Vocabulary, AcresError> {
+        if !api.use_cache() {
+            return Vocabulary::fetch(api).await;
+        }
+        let path = api.cache_dir().join(CACHE_FILE);
+        if let Some(vocabulary) = Vocabulary::load(&path)? {
+            return Ok(vocabulary);
+        }
+        let vocabulary = Vocabulary::fetch(api).await?;
+        vocabulary.save(&path)?;
+        Ok(vocabulary)
+    }
+
+    /// Loads a vocabulary saved with [`Vocabulary::save`], if there is one.
+    ///
+    /// A corrupt file is quarantined and treated as missing.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Vocabulary>, AcresError> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let data = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        match serde_json::from_slice(&data) {
+            Ok(vocabulary) => Ok(Some(vocabulary)),
+            Err(_) => {
+                cache::quarantine(path)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Saves the vocabulary to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AcresError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let data = serde_json::to_vec(self).context("serializing vocabulary")?;
+        cache::write_atomic(path, &data)
+    }
+
+    /// Adds category terms, skipping any with a subtype we don't know.
+    fn extend_terms(&mut self, terms: Vec<CategoryTermData>) {
+        for data in terms {
+            match Term::try_from(data) {
+                Ok(term) => self.insert(term),
+                Err(error) => tracing::debug!(%error, "skipping category term"),
+            }
+        }
+    }
+}
+
+/// Returns the page after `page`, or an error if it starts past the API's result window.
+fn next_page(page: u32, what: &str, total: u64) -> Result<u32, AcresError> {
+    if u64::from(page) * u64::from(PAGE_LIMIT) >= RESULT_WINDOW {
+        return Err(AcresError::Unexpected(anyhow!(
+            "there are {total} {what}, more than the {RESULT_WINDOW} the API pages through"
+        )));
+    }
+    Ok(page + 1)
+}
+
+/// Returns the preferred id, then the rest without repeating it.
+fn preferred_first<'a>(preferred: Option<&'a String>, ids: &'a [String]) -> Vec<&'a String> {
+    preferred
+        .into_iter()
+        .chain(ids.iter().filter(|id| Some(*id) != preferred))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::page;
+
+    fn vocabulary() -> Vocabulary {
+        let mut vocabulary = Vocabulary::new();
+        vocabulary.insert(Term::new(
+            Kind::Department,
+            "PC-10",
+            "Painting and Sculpture of Europe",
+        ));
+        vocabulary.insert(Term::new(Kind::ArtworkType, "1", "Painting"));
+        vocabulary.insert(Term::new(Kind::Style, "TM-7543", "Impressionism"));
+        vocabulary.insert(Term::new(Kind::Style, "TM-7544", "Post-Impressionism"));
+        vocabulary.insert(Term::new(Kind::Classification, "TM-11", "oil on canvas"));
+        vocabulary.insert(Term::new(Kind::Subject, "TM-12", "landscapes"));
+        vocabulary
+    }
+
+    #[test]
+    fn resolves_artwork_ids_to_labels() {
+        let artwork = artworks::Data {
+            department_id: Some("PC-10".into()),
+            artwork_type_id: Some(1),
+            style_id: Some("TM-7544".into()),
+            style_ids: vec!["TM-7543".into(), "TM-7544".into()],
+            classification_ids: vec!["TM-11".into()],
+            subject_ids: vec!["TM-12".into(), "TM-404".into()],
+            ..Default::default()
+        };
+
+        let labels = vocabulary().labels(&artwork);
+
+        assert_eq!(
+            labels.department.as_deref(),
+            Some("Painting and Sculpture of Europe")
+        );
+        assert_eq!(labels.artwork_type.as_deref(), Some("Painting"));
+        assert_eq!(labels.styles, vec!["Post-Impressionism", "Impressionism"]);
+        assert_eq!(labels.classifications, vec!["oil on canvas"]);
+        assert_eq!(labels.subjects, vec!["landscapes"]);
+    }
+
+    #[test]
+    fn looks_up_ids_by_label() {
+        let vocabulary = vocabulary();
+
+        assert_eq!(
+            vocabulary.id_of(Kind::Department, " painting and sculpture of europe "),
+            Some("PC-10")
+        );
+        assert_eq!(vocabulary.id_of(Kind::Style, "Painting"), None);
+        assert_eq!(
+            vocabulary.filter(Kind::ArtworkType, "painting"),
+            Some(json!({ "term": { "artwork_type_id": 1 } }))
+        );
+        assert_eq!(vocabulary.departments().count(), 1);
+    }
+
+    #[test]
+    fn saves_and_loads() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("vocabulary.json");
+
+        assert_eq!(Vocabulary::load(&path).unwrap(), None);
+        vocabulary().save(&path).unwrap();
+        assert_eq!(Vocabulary::load(&path).unwrap(), Some(vocabulary()));
+
+        std::fs::write(&path, b"{ not json").unwrap();
+        assert_eq!(Vocabulary::load(&path).unwrap(), None);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn fetches_every_page() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/category-terms"))
+            .and(wiremock::matchers::query_param("page", "1"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(page(
+                json!([
+                    { "id": "PC-10", "title": "Painting and Sculpture of Europe", "subtype": "department" },
+                    { "id": "TM-7543", "title": "Impressionism", "subtype": "style" }
+                ]),
+                3,
+                1,
+                2,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/category-terms"))
+            .and(wiremock::matchers::query_param("page", "2"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(page(
+                json!([
+                    { "id": "TM-12", "title": "landscapes", "subtype": "subject", "parent_id": "TM-1" },
+                    { "id": "TM-99", "title": "Something new", "subtype": "mood" }
+                ]),
+                3,
+                2,
+                2,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/artwork-types"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(page(
+                json!([{ "id": 1, "title": "Painting" }]),
+                3,
+                1,
+                1,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+
+        let vocabulary = Vocabulary::fetch(&api).await.unwrap();
+
+        assert_eq!(vocabulary.len(), 4);
+        assert_eq!(vocabulary.label(Kind::ArtworkType, "1"), Some("Painting"));
+        assert_eq!(
+            vocabulary
+                .term(Kind::Subject, "TM-12")
+                .unwrap()
+                .parent_id
+                .as_deref(),
+            Some("TM-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn fetches_only_missing_terms() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/category-terms"))
+            .and(wiremock::matchers::query_param("ids", "TM-13"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(page(
+                json!([{ "id": "TM-13", "title": "cities", "subtype": "subject" }]),
+                3,
+                1,
+                1,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+        let mut vocabulary = vocabulary();
+        let artwork = artworks::Data {
+            artwork_type_id: Some(1),
+            style_id: Some("TM-7543".into()),
+            subject_ids: vec!["TM-12".into(), "TM-13".into()],
+            ..Default::default()
+        };
+
+        vocabulary.fetch_missing(&api, &artwork).await.unwrap();
+
+        assert_eq!(
+            vocabulary.labels(&artwork).subjects,
+            vec!["landscapes", "cities"]
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_rather_than_page_past_the_result_window() {
+        let mock_server = wiremock::MockServer::start().await;
+        let mock_uri = format!("{}/api/v1", mock_server.uri());
+        wiremock::Mock::given(wiremock::matchers::path("/api/v1/category-terms"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(page(
+                json!([{ "id": "TM-12", "title": "landscapes", "subtype": "subject" }]),
+                10_001,
+                1,
+                101,
+            )))
+            .expect(100)
+            .mount(&mock_server)
+            .await;
+        let api = Api::builder().base_uri(&mock_uri).use_cache(false).build();
+
+        let error = Vocabulary::fetch(&api).await.unwrap_err();
+
+        assert!(error.to_string().contains("10001 category terms"));
+    }
+}
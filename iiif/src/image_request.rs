@@ -838,9 +838,10 @@ impl FromStr for ImageRequest {
 
         let uri = Uri::from_str(
             format!(
-                "{}://{}/{}",
+                "{}://{}{}/{}",
                 url.scheme(),
                 url.host_str().map_or("", |v| v),
+                url.port().map_or(String::new(), |port| format!(":{port}")),
                 params.join("/")
             )
             .as_str(),
@@ -881,6 +882,58 @@ impl ImageRequest {
     pub fn builder() -> Builder<Unset, Unset, Unset, Unset, Unset, Unset> {
         Builder::default()
     }
+
+    /// Returns the base URI of the image.
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// Returns the region of the image to retrieve.
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    /// Returns the size to scale the region to.
+    pub fn size(&self) -> &Size {
+        &self.size
+    }
+
+    /// Returns the rotation to apply.
+    pub fn rotation(&self) -> &Rotation {
+        &self.rotation
+    }
+
+    /// Returns the quality to retrieve.
+    pub fn quality(&self) -> &Quality {
+        &self.quality
+    }
+
+    /// Returns the image format to retrieve.
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// Returns the same request at a different size.
+    ///
+    /// ```rust
+    /// # use anyhow::Result;
+    /// use iiif::{ImageRequest, Size};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let request: ImageRequest = "https://example.org/images/12345/full/843,/0/default.jpg".parse()?;
+    /// assert_eq!(
+    ///     request.with_size(Size::Width(200)).to_string(),
+    ///     "https://example.org/images/12345/full/200,/0/default.jpg"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_size(&self, size: Size) -> Self {
+        Self {
+            size,
+            ..self.clone()
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -1169,4 +1222,11 @@ mod tests {
             format!("invalid format: {value}")
         )
     }
+
+    #[rstest]
+    #[case("https://example.org/images/12345/full/1024,/0/default.png")]
+    #[case("http://127.0.0.1:5555/iiif/2/12345/full/843,/0/default.jpg")]
+    fn image_request_round_trips(#[case] value: &str) {
+        assert_eq!(ImageRequest::from_str(value).unwrap().to_string(), value);
+    }
 }
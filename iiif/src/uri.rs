@@ -75,6 +75,24 @@ impl FromStr for Uri {
     }
 }
 
+impl Uri {
+    /// Returns the image identifier.
+    ///
+    /// ```rust
+    /// # use anyhow::Result;
+    /// use iiif::Uri;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let uri: Uri = "https://example.org/images/12345".parse()?;
+    /// assert_eq!(uri.identifier(), "12345");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+}
+
 impl Display for Uri {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
@@ -9,7 +9,7 @@ use std::{
 };
 
 use anyhow::Result;
-use image::io::Reader;
+use image::{DynamicImage, imageops::FilterType, io::Reader};
 use img_to_ascii::{
     convert::{
         char_rows_to_terminal_color_string, get_conversion_algorithm, get_converter,
@@ -678,11 +678,7 @@ impl Builder {
     /// Builds ASCII.
     pub fn build(self) -> Result<Ascii, ImageToAsciiBuilderError> {
         tracing::info!("converting bytes to ascii");
-        let dyn_img = Reader::new(Cursor::new(self.input_bytes))
-            .with_guessed_format()
-            .context("image reader failed")?
-            .decode()
-            .context("image decode failed")?;
+        let dyn_img = decode(self.input_bytes)?;
         Ok(Ascii(char_rows_to_terminal_color_string(
             &img_to_char_rows(
                 &font::Font::from_bdf_stream(
@@ -699,4 +695,69 @@ impl Builder {
             &dyn_img,
         )))
     }
+
+    /// Builds a quick, low-detail preview.
+    ///
+    /// Rather than matching the font's glyphs against the image, each char is a half block colored
+    /// with two pixels of the scaled image. That's fast, and it works for tiny images, such as an
+    /// artwork's placeholder, that would otherwise only be a char or two wide. Only the input and
+    /// width are used; the width defaults to 64 chars.
+    ///
+    /// ```rust
+    /// # use anyhow::Result;
+    /// use image_to_ascii_builder::{Ascii, CharWidth};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let jpeg = include_bytes!("../../acres-cli-ascii-art-77333.jpg");
+    /// let preview = Ascii::builder()
+    ///     .input(jpeg.as_slice().into())
+    ///     .chars_wide(CharWidth::CharsWide(20))
+    ///     .build_preview()?;
+    /// assert_eq!(preview.to_string().lines().next().unwrap().matches('▀').count(), 20);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_preview(self) -> Result<Ascii, ImageToAsciiBuilderError> {
+        tracing::info!("converting bytes to preview");
+        let dyn_img = decode(self.input_bytes)?;
+        let width = Option::<usize>::from(self.chars_wide)
+            .unwrap_or(PREVIEW_WIDTH)
+            .max(1) as u32;
+        // Chars are about twice as tall as they are wide, and each one covers two pixels.
+        let rows = (f64::from(width) * f64::from(dyn_img.height())
+            / f64::from(dyn_img.width().max(1))
+            / 2.0)
+            .round()
+            .max(1.0) as u32;
+        let pixels = dyn_img
+            .resize_exact(width, rows * 2, FilterType::Triangle)
+            .to_rgb8();
+        Ok(Ascii(
+            (0..rows)
+                .map(|row| {
+                    let mut line = (0..width)
+                        .map(|x| {
+                            let [r, g, b] = pixels.get_pixel(x, row * 2).0;
+                            let [br, bg, bb] = pixels.get_pixel(x, row * 2 + 1).0;
+                            format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{br};{bg};{bb}m\u{2580}")
+                        })
+                        .collect::<String>();
+                    line.push_str("\x1b[0m");
+                    line
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+        ))
+    }
+}
+
+/// Width, in chars, of previews when no width is set.
+const PREVIEW_WIDTH: usize = 64;
+
+fn decode(bytes: Bytes) -> Result<DynamicImage, ImageToAsciiBuilderError> {
+    Ok(Reader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .context("image reader failed")?
+        .decode()
+        .context("image decode failed")?)
 }